@@ -0,0 +1,100 @@
+//! Derive macros for `bevy_quickmenu`, enabled via its `derive` feature
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Path, Type};
+
+/// Implements `ScreenTrait` for an enum of screens. The enum names its action
+/// and state types, and each variant names the function that builds its menu:
+///
+/// ```ignore
+/// #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Screen)]
+/// #[screen(action = Actions, state = BasicState)]
+/// enum Screens {
+///     #[menu(root_menu)]
+///     Root,
+///     // The fields of a variant are passed after the state
+///     #[menu(player_menu)]
+///     Player(usize),
+/// }
+///
+/// fn root_menu(state: &BasicState) -> Menu<Screens> { ... }
+/// fn player_menu(state: &BasicState, player: usize) -> Menu<Screens> { ... }
+/// ```
+#[proc_macro_derive(Screen, attributes(screen, menu))]
+pub fn derive_screen(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`Screen` can only be derived for enums",
+        ));
+    };
+
+    let mut action: Option<Type> = None;
+    let mut state: Option<Type> = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("screen")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("action") {
+                action = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("state") {
+                state = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `action` or `state`"));
+            }
+            Ok(())
+        })?;
+    }
+    let missing = |name: &str| {
+        syn::Error::new_spanned(&input.ident, format!("missing `#[screen({name} = ...)]`"))
+    };
+    let action = action.ok_or_else(|| missing("action"))?;
+    let state = state.ok_or_else(|| missing("state"))?;
+
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let Some(attr) = variant.attrs.iter().find(|a| a.path().is_ident("menu")) else {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                "missing `#[menu(function)]`",
+            ));
+        };
+        let function: Path = attr.parse_args()?;
+        let ident = &variant.ident;
+        let arm = match &variant.fields {
+            Fields::Unit => quote! { Self::#ident => #function(state) },
+            Fields::Unnamed(fields) => {
+                let names: Vec<_> = (0..fields.unnamed.len())
+                    .map(|i| format_ident!("field{i}"))
+                    .collect();
+                quote! { Self::#ident(#(#names),*) => #function(state, #(*#names),*) }
+            }
+            Fields::Named(fields) => {
+                let names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+                quote! { Self::#ident { #(#names),* } => #function(state, #(*#names),*) }
+            }
+        };
+        arms.push(arm);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::bevy_quickmenu::ScreenTrait for #ident #type_generics #where_clause {
+            type Action = #action;
+            type State = #state;
+            fn resolve(&self, state: &#state) -> ::bevy_quickmenu::Menu<Self> {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
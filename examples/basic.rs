@@ -41,7 +41,7 @@ impl Plugin for BasicPlugin {
             .add_plugins(QuickMenuPlugin::<Screens>::new())
             // Some systems
             .add_systems(Startup, setup)
-            .add_systems(Update, event_reader);
+            .add_systems(Update, (event_reader, close_on_key));
     }
 }
 
@@ -137,3 +137,11 @@ fn event_reader(mut commands: Commands, mut event_reader: MessageReader<BasicEve
         }
     }
 }
+
+/// Menus can also be closed from gameplay code, here with `Q`. Once the menu
+/// is gone, there's no `MenuState` left to close
+fn close_on_key(keys: Res<ButtonInput<KeyCode>>, menu_state: Option<ResMut<MenuState<Screens>>>) {
+    if let (true, Some(mut menu_state)) = (keys.just_pressed(KeyCode::KeyQ), menu_state) {
+        menu_state.close();
+    }
+}
@@ -0,0 +1,100 @@
+//! Confirm Example
+//! Shows how to ask for confirmation before a destructive action,
+//! here quitting the app
+use bevy::prelude::*;
+
+use bevy_quickmenu::{
+    style::Stylesheet, ActionTrait, Menu, MenuIcon, MenuItem, MenuState, QuickMenuPlugin,
+    ScreenTrait,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ConfirmPlugin)
+        .run();
+}
+
+/// Written by the action handler once "Yes" was selected
+#[derive(Debug, Message)]
+enum ConfirmEvent {
+    Quit,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ConfirmState;
+
+pub struct ConfirmPlugin;
+
+impl Plugin for ConfirmPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ConfirmEvent>()
+            .add_plugins(QuickMenuPlugin::<Screens>::new())
+            .add_systems(Startup, setup)
+            .add_systems(Update, event_reader);
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera3d::default());
+    commands.insert_resource(MenuState::new(
+        ConfirmState,
+        Screens::Root,
+        Some(Stylesheet::default()),
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Actions {
+    Quit,
+}
+
+impl ActionTrait for Actions {
+    type State = ConfirmState;
+    type Event = ConfirmEvent;
+    fn handle(&self, _state: &mut ConfirmState, event_writer: &mut MessageWriter<ConfirmEvent>) {
+        match self {
+            Actions::Quit => {
+                event_writer.write(ConfirmEvent::Quit);
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Screens {
+    Root,
+}
+
+impl ScreenTrait for Screens {
+    type Action = Actions;
+    type State = ConfirmState;
+    fn resolve(&self, _state: &ConfirmState) -> Menu<Screens> {
+        match self {
+            Screens::Root => Menu::new(
+                "root",
+                vec![
+                    MenuItem::headline("Confirm Example"),
+                    // `Actions::Quit` is only performed for "Yes", "No" and `Back` close
+                    // the dialog
+                    MenuItem::action("Quit", Actions::Quit)
+                        .with_icon(MenuIcon::Back)
+                        .with_confirm("Really quit?"),
+                ],
+            ),
+        }
+    }
+}
+
+fn event_reader(
+    mut event_reader: MessageReader<ConfirmEvent>,
+    mut app_exit: MessageWriter<AppExit>,
+) {
+    for event in event_reader.read() {
+        match event {
+            ConfirmEvent::Quit => {
+                app_exit.write(AppExit::Success);
+            }
+        }
+    }
+}
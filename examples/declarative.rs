@@ -0,0 +1,127 @@
+//! Declarative Example
+//! The basic example, with the menus declared via `quick_menu!`.
+//! Both examples build exactly the same menus
+use bevy::prelude::*;
+
+use bevy_quickmenu::{
+    quick_menu, style::Stylesheet, ActionTrait, Menu, MenuIcon, MenuState, QuickMenuPlugin,
+    ScreenTrait,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(BasicPlugin)
+        .run();
+}
+
+/// This custom event can be emitted by the action handler (below) in order to
+/// process actions with access to the bevy ECS
+#[derive(Debug, Message)]
+enum BasicEvent {
+    Close,
+}
+
+/// This state represents the UI. Mutations to this state (via `MenuState::state_mut`)
+/// cause a re-render of the menu UI
+#[derive(Debug, Clone, Default)]
+struct BasicState {
+    boolean1: bool,
+    boolean2: bool,
+}
+
+pub struct BasicPlugin;
+
+impl Plugin for BasicPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register a event that can be called from your action handler
+            .add_message::<BasicEvent>()
+            // The plugin
+            .add_plugins(QuickMenuPlugin::<Screens>::new())
+            // Some systems
+            .add_systems(Startup, setup)
+            .add_systems(Update, event_reader);
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera3d::default());
+    // Create a default stylesheet. You can customize these as you wish
+    let sheet = Stylesheet::default();
+
+    commands.insert_resource(MenuState::new(
+        BasicState::default(),
+        Screens::Root,
+        Some(sheet),
+    ))
+}
+
+/// The possible actions in our settings
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Actions {
+    Close,
+    Toggle1,
+    Toggle2,
+}
+
+/// Handle the possible actions
+impl ActionTrait for Actions {
+    type State = BasicState;
+    type Event = BasicEvent;
+    fn handle(&self, state: &mut BasicState, event_writer: &mut MessageWriter<BasicEvent>) {
+        match self {
+            Actions::Close => {
+                event_writer.write(BasicEvent::Close);
+            }
+            Actions::Toggle1 => {
+                state.boolean1 = !state.boolean1;
+            }
+            Actions::Toggle2 => {
+                state.boolean2 = !state.boolean2;
+            }
+        }
+    }
+}
+
+/// All possible screens in our example
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Screens {
+    Root,
+    Booleans,
+}
+
+/// Map from from `Screens` to the actual menu. Each item is a `MenuItem` constructor
+/// with its builder methods, e.g. `MenuItem::action(..).with_icon(..)`
+impl ScreenTrait for Screens {
+    type Action = Actions;
+    type State = BasicState;
+    fn resolve(&self, state: &BasicState) -> Menu<Screens> {
+        quick_menu! {
+            match self {
+                // The `root` menu that is displayed first
+                Screens::Root => "root" [
+                    headline("Basic Example"),
+                    action("Close", Actions::Close).with_icon(MenuIcon::Back),
+                    label("A submenu"),
+                    // The boolean menu is declared where its entry is
+                    screen("Boolean", Screens::Booleans) => "boolean" [
+                        label("Toggles some booleans"),
+                        action("Toggle Boolean 1", Actions::Toggle1).checked(state.boolean1),
+                        action("Toggle Boolean 2", Actions::Toggle2).checked(state.boolean2),
+                    ],
+                ],
+            }
+        }
+    }
+}
+
+/// This allows to react to actions with custom bevy resources or eventwriters or queries.
+/// In this example we use it to close the menu
+fn event_reader(mut commands: Commands, mut event_reader: MessageReader<BasicEvent>) {
+    for event in event_reader.read() {
+        match event {
+            BasicEvent::Close => bevy_quickmenu::cleanup(&mut commands),
+        }
+    }
+}
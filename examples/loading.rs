@@ -0,0 +1,155 @@
+//! Loading Example
+//! Shows how to fetch what a screen displays once it is entered,
+//! here a list of save games that takes a moment to load
+use bevy::prelude::*;
+
+use bevy_quickmenu::{
+    style::Stylesheet, ActionTrait, Menu, MenuIcon, MenuItem, MenuState, QuickMenuPlugin,
+    ScreenTrait,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(LoadingPlugin)
+        .run();
+}
+
+#[derive(Debug, Message)]
+enum LoadingEvent {
+    /// Written by `Screens::LoadGame` when it is entered
+    FetchSaves,
+    Load(usize),
+}
+
+#[derive(Debug, Clone, Default)]
+struct LoadingState {
+    /// `None` while the list is being fetched
+    saves: Option<Vec<String>>,
+}
+
+/// Pretends that reading the save games from disk takes a while
+#[derive(Resource)]
+struct Fetching(Timer);
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<LoadingEvent>()
+            .add_plugins(QuickMenuPlugin::<Screens>::new())
+            .add_systems(Startup, setup)
+            .add_systems(Update, (event_reader, fetch_saves));
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera3d::default());
+    commands.insert_resource(MenuState::new(
+        LoadingState::default(),
+        Screens::Root,
+        Some(Stylesheet::default()),
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Actions {
+    Load(usize),
+}
+
+impl ActionTrait for Actions {
+    type State = LoadingState;
+    type Event = LoadingEvent;
+    fn handle(&self, _state: &mut LoadingState, event_writer: &mut MessageWriter<LoadingEvent>) {
+        match self {
+            Actions::Load(index) => {
+                event_writer.write(LoadingEvent::Load(*index));
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Screens {
+    Root,
+    LoadGame,
+}
+
+impl ScreenTrait for Screens {
+    type Action = Actions;
+    type State = LoadingState;
+    fn resolve(&self, state: &LoadingState) -> Menu<Screens> {
+        match self {
+            Screens::Root => Menu::new(
+                "root",
+                vec![
+                    MenuItem::headline("Loading Example"),
+                    MenuItem::screen("Load Game", Screens::LoadGame).with_icon(MenuIcon::Settings),
+                ],
+            ),
+            Screens::LoadGame => {
+                let mut items = vec![MenuItem::headline("Load Game")];
+                match &state.saves {
+                    None => items.push(MenuItem::label("Loading...")),
+                    Some(saves) => items.extend(
+                        saves
+                            .iter()
+                            .enumerate()
+                            .map(|(index, name)| MenuItem::action(name, Actions::Load(index))),
+                    ),
+                }
+                Menu::new("load", items)
+            }
+        }
+    }
+
+    /// Only runs when the screen is shown, not for every re-render
+    fn on_enter(&self, _state: &mut LoadingState, event_writer: &mut MessageWriter<LoadingEvent>) {
+        if let Screens::LoadGame = self {
+            event_writer.write(LoadingEvent::FetchSaves);
+        }
+    }
+
+    /// Fetch the list again the next time, the files may have changed
+    fn on_exit(&self, state: &mut LoadingState, _event_writer: &mut MessageWriter<LoadingEvent>) {
+        if let Screens::LoadGame = self {
+            state.saves = None;
+        }
+    }
+}
+
+fn event_reader(mut commands: Commands, mut event_reader: MessageReader<LoadingEvent>) {
+    for event in event_reader.read() {
+        match event {
+            LoadingEvent::FetchSaves => {
+                commands.insert_resource(Fetching(Timer::from_seconds(1.0, TimerMode::Once)));
+            }
+            LoadingEvent::Load(index) => info!("Loading save game {index}"),
+        }
+    }
+}
+
+fn fetch_saves(
+    mut commands: Commands,
+    time: Res<Time>,
+    fetching: Option<ResMut<Fetching>>,
+    mut menu_state: ResMut<MenuState<Screens>>,
+) {
+    let Some(mut fetching) = fetching else {
+        return;
+    };
+    if !fetching.0.tick(time.delta()).is_finished() {
+        return;
+    }
+    commands.remove_resource::<Fetching>();
+    // The screen may have been left in the meantime
+    if menu_state.screens().last() != Some(&Screens::LoadGame) {
+        return;
+    }
+    // Changing the state re-renders the menu
+    menu_state.state_mut().saves = Some(vec![
+        "Chapter 1 - The Harbour".to_string(),
+        "Chapter 2 - The Lighthouse".to_string(),
+        "Chapter 3 - The Storm".to_string(),
+    ]);
+}
@@ -0,0 +1,158 @@
+//! Lobbies Example
+//! Shows a server browser whose list of lobbies changes while it is open.
+//! The focus stays on the same lobby when the list is sorted again
+use bevy::prelude::*;
+
+use bevy_quickmenu::{
+    style::Stylesheet, ActionTrait, Menu, MenuItem, MenuState, QuickMenuPlugin, ScreenTrait,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(LobbiesPlugin)
+        .run();
+}
+
+#[derive(Debug, Message)]
+enum LobbiesEvent {
+    Join(u32),
+}
+
+#[derive(Debug, Clone)]
+struct Lobby {
+    id: u32,
+    name: String,
+    players: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct LobbiesState {
+    /// Sorted by the number of players, the fullest first
+    lobbies: Vec<Lobby>,
+}
+
+/// Pretends that the server sends an update every now and then
+#[derive(Resource)]
+struct Refresh {
+    timer: Timer,
+    round: u32,
+}
+
+pub struct LobbiesPlugin;
+
+impl Plugin for LobbiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<LobbiesEvent>()
+            .add_plugins(QuickMenuPlugin::<Screens>::new())
+            .insert_resource(Refresh {
+                timer: Timer::from_seconds(2.0, TimerMode::Repeating),
+                round: 0,
+            })
+            .add_systems(Startup, setup)
+            .add_systems(Update, (event_reader, refresh_lobbies));
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera3d::default());
+    commands.insert_resource(MenuState::new(
+        LobbiesState {
+            lobbies: lobbies(0),
+        },
+        Screens::Browser,
+        Some(Stylesheet::default()),
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Actions {
+    Join(u32),
+}
+
+impl ActionTrait for Actions {
+    type State = LobbiesState;
+    type Event = LobbiesEvent;
+    fn handle(&self, _state: &mut LobbiesState, event_writer: &mut MessageWriter<LobbiesEvent>) {
+        match self {
+            Actions::Join(id) => {
+                event_writer.write(LobbiesEvent::Join(*id));
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Screens {
+    Browser,
+}
+
+impl ScreenTrait for Screens {
+    type Action = Actions;
+    type State = LobbiesState;
+    fn resolve(&self, state: &LobbiesState) -> Menu<Screens> {
+        match self {
+            Screens::Browser => {
+                let mut items = vec![MenuItem::headline("Lobbies")];
+                if state.lobbies.is_empty() {
+                    items.push(MenuItem::label("No open lobbies"));
+                }
+                Menu::new("browser", items).with_list(
+                    &state.lobbies,
+                    |lobby| lobby.id,
+                    |lobby| {
+                        MenuItem::action(
+                            format!("{} ({}/8)", lobby.name, lobby.players),
+                            Actions::Join(lobby.id),
+                        )
+                    },
+                )
+            }
+        }
+    }
+}
+
+/// The lobbies of the server in the given round of updates. Players come and go,
+/// and every few rounds a lobby closes
+fn lobbies(round: u32) -> Vec<Lobby> {
+    let mut lobbies: Vec<_> = ["Harbour", "Lighthouse", "Storm", "Reef", "Lagoon"]
+        .into_iter()
+        .zip(1u32..)
+        .filter(|(_, id)| !(round + id).is_multiple_of(5))
+        .map(|(name, id)| Lobby {
+            id,
+            name: name.to_string(),
+            players: ((round * 3 + id * 5) % 9) as usize,
+        })
+        .collect();
+    lobbies.sort_by_key(|lobby| std::cmp::Reverse(lobby.players));
+    lobbies
+}
+
+fn refresh_lobbies(
+    time: Res<Time>,
+    mut refresh: ResMut<Refresh>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+) {
+    // The menu is gone once a lobby was joined
+    let Some(mut menu_state) = menu_state else {
+        return;
+    };
+    if !refresh.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    refresh.round += 1;
+    // Changing the state re-renders the menu
+    menu_state.state_mut().lobbies = lobbies(refresh.round);
+}
+
+fn event_reader(mut commands: Commands, mut event_reader: MessageReader<LobbiesEvent>) {
+    for event in event_reader.read() {
+        match event {
+            LobbiesEvent::Join(id) => {
+                info!("Joining lobby {id}");
+                bevy_quickmenu::cleanup(&mut commands);
+            }
+        }
+    }
+}
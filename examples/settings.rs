@@ -9,8 +9,8 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 
 use bevy_quickmenu::{
-    style::Stylesheet, ActionTrait, Menu, MenuIcon, MenuItem, MenuState, QuickMenuPlugin,
-    ScreenTrait,
+    style::Stylesheet, ActionTrait, Menu, MenuIcon, MenuItem, MenuState, MenuValue,
+    QuickMenuPlugin, ScreenTrait,
 };
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, States, Default)]
@@ -59,6 +59,7 @@ mod settings {
     #[derive(Debug, Clone)]
     struct CustomState {
         sound_on: bool,
+        volume: f32,
         gamepads: Vec<usize>,
         controls: HashMap<usize, ControlDevice>,
         logo: Handle<Image>,
@@ -84,11 +85,20 @@ mod settings {
 
     fn setup_system(mut commands: Commands, assets: Res<AssetServer>) {
         // Create a default stylesheet. You can customize these as you wish
-        let sheet = Stylesheet::default().with_background(BackgroundColor(Color::BLACK));
+        let sheet = Stylesheet::default()
+            .with_background(BackgroundColor(Color::BLACK))
+            // Explain the focussed item below the menu
+            .with_description_pane(Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.0),
+                left: Val::Px(0.0),
+                ..default()
+            });
 
         commands.insert_resource(MenuState::new(
             CustomState {
                 sound_on: true,
+                volume: 0.8,
                 gamepads: Vec::new(),
                 controls: [
                     (0, ControlDevice::keyboard1()),
@@ -133,6 +143,7 @@ mod settings {
         Close,
         SoundOn,
         SoundOff,
+        Volume,
         Control(usize, ControlDevice),
     }
 
@@ -151,11 +162,23 @@ mod settings {
                 Actions::SoundOff => {
                     state.sound_on = false;
                 }
+                Actions::Volume => {}
                 Actions::Control(p, d) => {
                     state.controls.insert(*p, *d);
                 }
             }
         }
+
+        fn handle_value(
+            &self,
+            state: &mut CustomState,
+            value: &MenuValue,
+            _event_writer: &mut MessageWriter<MyEvent>,
+        ) {
+            if let (Actions::Volume, MenuValue::Float(volume)) = (self, value) {
+                state.volume = *volume;
+            }
+        }
     }
 
     /// All possible screens in our settings
@@ -200,8 +223,15 @@ mod settings {
             "sound",
             vec![
                 MenuItem::label("Toggles sound and music"),
-                MenuItem::action("On", Actions::SoundOn).checked(state.sound_on),
-                MenuItem::action("Off", Actions::SoundOff).checked(!state.sound_on),
+                MenuItem::action("On", Actions::SoundOn)
+                    .checked(state.sound_on)
+                    .with_description("Play sound effects and music"),
+                MenuItem::action("Off", Actions::SoundOff)
+                    .checked(!state.sound_on)
+                    .with_description("Mute the game"),
+                MenuItem::slider("Volume", Actions::Volume, state.volume, 0.0..=1.0, 0.1)
+                    .with_format(|v| format!("{:.0}%", v * 100.0))
+                    .with_description("Change with left and right"),
             ],
         )
     }
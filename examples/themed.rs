@@ -0,0 +1,161 @@
+//! Themed Example
+//! Shows how to draw the menu and its buttons on nine-slice plates. The plates are
+//! generated here, a game would load them with the `AssetServer`
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use bevy_quickmenu::{
+    style::{ControlImages, ControlState, RowStyle, Stylesheet},
+    ActionTrait, Menu, MenuItem, MenuState, QuickMenuPlugin, ScreenTrait,
+};
+
+/// The size of the plate textures
+const PLATE_SIZE: u32 = 24;
+/// The width of the frame of a plate, kept as is by the slicing
+const PLATE_BORDER: u32 = 6;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ThemedPlugin)
+        .run();
+}
+
+#[derive(Debug, Message)]
+enum ThemedEvent {
+    Close,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ThemedState;
+
+pub struct ThemedPlugin;
+
+impl Plugin for ThemedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ThemedEvent>()
+            .add_plugins(QuickMenuPlugin::<Screens>::new())
+            .add_systems(Startup, setup)
+            .add_systems(Update, event_reader);
+    }
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn(Camera3d::default());
+
+    let mut plate = |frame: [u8; 4], fill: [u8; 4]| {
+        ImageNode::new(images.add(plate_image(frame, fill))).with_mode(NodeImageMode::Sliced(
+            TextureSlicer {
+                border: BorderRect::all(PLATE_BORDER as f32),
+                ..default()
+            },
+        ))
+    };
+    let buttons = ControlImages::new(plate([120, 90, 40, 255], [40, 30, 20, 255]))
+        .with_hover(plate([200, 160, 70, 255], [40, 30, 20, 255]))
+        .with_selected(plate([250, 210, 90, 255], [90, 60, 25, 255]));
+    let panel = plate([90, 70, 40, 255], [20, 15, 10, 230]);
+
+    // The plates replace the background colors of the buttons
+    let clear = |fg| ControlState::new(fg, Color::NONE);
+    let sheet = Stylesheet::default()
+        .with_button_states(
+            clear(Color::srgb(0.9, 0.85, 0.7)),
+            clear(Color::WHITE),
+            clear(Color::WHITE),
+        )
+        .with_button_images(buttons)
+        .with_panel(panel)
+        .with_rows(RowStyle {
+            gap: 4.0,
+            horizontal_padding: 10.0,
+            min_height: 40.0,
+            ..default()
+        });
+
+    commands.insert_resource(MenuState::new(ThemedState, Screens::Root, Some(sheet)))
+}
+
+/// A square texture with a `frame` of `PLATE_BORDER` pixels around the `fill`
+fn plate_image(frame: [u8; 4], fill: [u8; 4]) -> Image {
+    let data = (0..PLATE_SIZE * PLATE_SIZE)
+        .flat_map(|pixel| {
+            let (x, y) = (pixel % PLATE_SIZE, pixel / PLATE_SIZE);
+            let inside = PLATE_BORDER..PLATE_SIZE - PLATE_BORDER;
+            if inside.contains(&x) && inside.contains(&y) {
+                fill
+            } else {
+                frame
+            }
+        })
+        .collect();
+    Image::new(
+        Extent3d {
+            width: PLATE_SIZE,
+            height: PLATE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Actions {
+    Close,
+}
+
+impl ActionTrait for Actions {
+    type State = ThemedState;
+    type Event = ThemedEvent;
+    fn handle(&self, _state: &mut ThemedState, event_writer: &mut MessageWriter<ThemedEvent>) {
+        match self {
+            Actions::Close => {
+                event_writer.write(ThemedEvent::Close);
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Screens {
+    Root,
+    Options,
+}
+
+impl ScreenTrait for Screens {
+    type Action = Actions;
+    type State = ThemedState;
+    fn resolve(&self, _state: &ThemedState) -> Menu<Screens> {
+        match self {
+            Screens::Root => Menu::new(
+                "root",
+                vec![
+                    MenuItem::headline("Themed Example"),
+                    MenuItem::screen("Options", Screens::Options),
+                    MenuItem::action("Close", Actions::Close),
+                ],
+            ),
+            Screens::Options => Menu::new(
+                "options",
+                vec![
+                    MenuItem::label("Every button sits on a plate"),
+                    MenuItem::back("Back"),
+                ],
+            ),
+        }
+    }
+}
+
+fn event_reader(mut commands: Commands, mut event_reader: MessageReader<ThemedEvent>) {
+    for event in event_reader.read() {
+        match event {
+            ThemedEvent::Close => bevy_quickmenu::cleanup(&mut commands),
+        }
+    }
+}
@@ -1,25 +1,42 @@
 #![doc = include_str!("../Readme.md")]
 
 // pub mod helpers;
+mod macros;
 mod navigation_menu;
 pub mod style;
 mod systems;
+#[cfg(test)]
+mod tests;
 mod types;
 mod widgets;
 
+use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
 use style::Stylesheet;
-use types::{CleanUpUI, MenuAssets};
+use types::{
+    CapturedInput, CleanUpUI, ClosingMenus, Fade, MenuAssets, MenuPlugins, NavigationSounds,
+    OpenedMenus, PendingBuild, RefocusEvent, TypeSearch, WidgetId,
+};
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::time::Duration;
 
 pub use navigation_menu::NavigationMenu;
 pub use types::{
-    ButtonComponent, Menu, MenuIcon, MenuItem, MenuOptions, MenuSelection, NavigationEvent,
-    PrimaryMenu, RedrawEvent, RichTextEntry, Selections, VerticalMenuComponent,
+    Binding, Bindings, ButtonComponent, CurrentSelection, CustomBuild, DescriptionPane,
+    FocusChangedEvent, GamepadBindings, GamepadGlyphs, GamepadLayout, GamepadLostEvent,
+    GamepadNavigationEvent, Haptic, InputContext, ItemActivatedEvent, KeyBindings, LastInputDevice,
+    Localization, Menu, MenuBounds, MenuHapticEvent, MenuHaptics, MenuIcon, MenuInputs, MenuItem,
+    MenuOptions, MenuSelection, MenuSound, MenuSoundEvent, MenuValue, NavigationConfig,
+    NavigationEvent, PrimaryMenu, RedrawEvent, RichTextEntry, ScreenChangedEvent, Selections,
+    StickAxes, UnhandledNavigationEvent, VerticalMenuComponent, WidgetLabel,
 };
 
+#[cfg(feature = "derive")]
+pub use bevy_quickmenu_derive::Screen;
+
 use crate::types::GamepadActivation;
 
 /// The quickmenu plugin.
@@ -163,6 +180,10 @@ where
 {
     s: std::marker::PhantomData<S>,
     options: Option<MenuOptions>,
+    /// Handle the mouse and touches, see `without_pointer`
+    pointer: bool,
+    /// Panic if there is no `MenuState<S>` after `Startup`, see `with_startup_check`
+    startup_check: bool,
 }
 
 impl<S> QuickMenuPlugin<S>
@@ -174,15 +195,38 @@ where
         Self {
             s: Default::default(),
             options: None,
+            pointer: true,
+            startup_check: false,
         }
     }
 
+    /// Use `options` for all menus. Plugins of other menus that are added with `new`
+    /// keep them
     pub fn with_options(options: MenuOptions) -> Self {
         Self {
             s: Default::default(),
             options: Some(options),
+            pointer: true,
+            startup_check: false,
         }
     }
+
+    /// Leave out the systems for clicks, taps and hovering, for menus that are only
+    /// navigated with the keyboard and gamepads, e.g. on consoles. The mouse wheel is
+    /// translated into `NavigationEvent`s with the keyboard and still scrolls. For the
+    /// opposite, mouse-only menus, see `MenuInputs::Pointer`
+    pub fn without_pointer(mut self) -> Self {
+        self.pointer = false;
+        self
+    }
+
+    /// Panic with a hint if no `MenuState<S>` was inserted by the end of `Startup`,
+    /// for menus that are shown right away. Without it, a missing state or one for
+    /// another screen type just shows nothing, as menus may as well be opened later
+    pub fn with_startup_check(mut self) -> Self {
+        self.startup_check = true;
+        self
+    }
 }
 
 impl<State, A, S> Plugin for QuickMenuPlugin<S>
@@ -192,44 +236,253 @@ where
     S: ScreenTrait<Action = A, State = State> + 'static,
 {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.insert_resource(self.options.unwrap_or_default())
+        // Shared by all menus, e.g. one per player
+        if !app.is_plugin_added::<InputPlugin>() {
+            app.add_plugins(InputPlugin);
+        }
+        app.init_resource::<MenuPlugins>()
+            .init_resource::<MenuBounds<S>>()
+            .add_systems(
+                PostUpdate,
+                systems::menu_bounds_system::<S>.after(bevy::ui::UiSystems::Layout),
+            )
+            // Before the menus check whether they have the input context
+            .add_systems(
+                Update,
+                systems::opened_menus_system::<S>.in_set(QuickMenuSet::Input),
+            );
+        app.world_mut().resource_mut::<MenuPlugins>().0 += 1;
+
+        // The options are shared, so a plugin without any keeps those of another one
+        if let Some(options) = self.options {
+            app.insert_resource(options);
+        }
+        app.init_resource::<MenuOptions>()
             .init_resource::<MenuAssets>()
-            .insert_resource(Selections::default())
-            .add_message::<NavigationEvent>()
             .add_message::<RedrawEvent>()
+            .add_message::<RefocusEvent>()
+            .add_message::<MenuSoundEvent>()
+            .add_message::<ScreenChangedEvent<S>>()
+            .add_message::<FocusChangedEvent<S>>()
+            .add_message::<ItemActivatedEvent<S>>()
+            .add_message::<UnhandledNavigationEvent<S>>()
             .add_systems(
                 Update,
-                systems::cleanup_system::<S>.run_if(resource_exists::<CleanUpUI>),
+                (
+                    systems::cleanup_system::<S>.run_if(closing::<S>),
+                    systems::fade_system::<S>
+                        .after(systems::redraw_system::<S>)
+                        .before(systems::cleanup_system::<S>)
+                        .run_if(resource_exists::<MenuState<S>>),
+                )
+                    .in_set(QuickMenuSet::Redraw),
             )
             .add_systems(
+                Update,
+                (
+                    (
+                        systems::input_system::<S>,
+                        // Keys that end editing must not navigate afterwards
+                        systems::text_input_system::<S>.before(systems::input_system::<S>),
+                        systems::binding_capture_system::<S>.before(systems::input_system::<S>),
+                        systems::type_to_search_system::<S>.before(systems::input_system::<S>),
+                        systems::gamepad_failover_system::<S>.before(systems::input_system::<S>),
+                        systems::screen_changed_system::<S>.after(systems::input_system::<S>),
+                        systems::focus_changed_system::<S>.after(systems::input_system::<S>),
+                    )
+                        .in_set(QuickMenuSet::Apply),
+                    (
+                        systems::stylesheet_system::<S>.before(systems::window_scale_system::<S>),
+                        systems::window_scale_system::<S>.before(systems::redraw_system::<S>),
+                        systems::redraw_system::<S>,
+                        systems::highlight_system::<S>.after(systems::redraw_system::<S>),
+                    )
+                        .in_set(QuickMenuSet::Redraw),
+                )
+                    .run_if(resource_exists::<MenuState<S>>.and(not(closing::<S>))),
+            );
+        #[cfg(feature = "accesskit")]
+        app.init_resource::<bevy_input_focus::InputFocus>()
+            .add_systems(
+                Update,
+                systems::accessibility_system::<S>
+                    .after(systems::redraw_system::<S>)
+                    .in_set(QuickMenuSet::Redraw)
+                    .run_if(resource_exists::<MenuState<S>>.and(not(closing::<S>))),
+            );
+        if self.startup_check {
+            app.add_systems(PostStartup, systems::startup_check_system::<S>);
+        }
+        if self.pointer {
+            app.add_systems(
                 Update,
                 (
                     systems::mouse_system::<S>,
-                    systems::input_system::<S>,
-                    systems::redraw_system::<S>,
+                    systems::breadcrumb_system::<S>,
+                    systems::tab_system::<S>,
+                )
+                    .before(systems::screen_changed_system::<S>)
+                    .before(systems::focus_changed_system::<S>)
+                    .in_set(QuickMenuSet::Apply)
+                    .run_if(resource_exists::<MenuState<S>>.and(not(closing::<S>))),
+            );
+        }
+    }
+}
+
+/// The systems of all menus run in these sets, one after the other, in `Update`.
+/// Order your own systems against them, e.g. to write `NavigationEvent`s before
+/// `QuickMenuSet::Apply` or to edit the spawned UI after `QuickMenuSet::Redraw`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuickMenuSet {
+    /// Keyboard, mouse wheel and gamepad input becomes `NavigationEvent`s
+    Input,
+    /// Navigation, clicks and taps change the `MenuState`
+    Apply,
+    /// The UI is re-rendered and removed
+    Redraw,
+}
+
+/// Turns keyboard, mouse wheel and gamepad input into navigation events.
+/// Added once, no matter how many menus there are
+struct InputPlugin;
+
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        // Keep a `NavigationConfig` the user inserted beforehand
+        app.init_resource::<NavigationConfig>();
+        let config = app.world().resource::<NavigationConfig>().validated();
+        app.insert_resource(config);
+        app.init_resource::<KeyBindings>()
+            .init_resource::<GamepadBindings>()
+            .init_resource::<GamepadGlyphs>()
+            .init_resource::<CapturedInput>()
+            .init_resource::<MenuHaptics>()
+            .init_resource::<LastInputDevice>()
+            .init_resource::<InputContext>()
+            .init_resource::<OpenedMenus>()
+            .init_resource::<NavigationSounds>()
+            .init_resource::<ClosingMenus>()
+            .add_message::<NavigationEvent>()
+            .add_message::<GamepadNavigationEvent>()
+            .add_message::<MenuSoundEvent>()
+            .add_message::<MenuHapticEvent>()
+            .add_message::<GamepadLostEvent>()
+            .configure_sets(
+                Update,
+                (
+                    QuickMenuSet::Input,
+                    QuickMenuSet::Apply,
+                    QuickMenuSet::Redraw,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    // Held sticks of a lost gamepad must not repeat
+                    systems::gamepad_disconnect_system.before(systems::keyboard_input_system),
                     systems::keyboard_input_system,
+                    systems::mouse_wheel_system,
                     systems::insert_gamepad_activation_system
                         .run_if(any_match_filter::<(With<Gamepad>, Without<GamepadActivation>)>),
                 )
-                    .run_if(
-                        resource_exists::<MenuState<S>>
-                            .and(|res: Option<Res<CleanUpUI>>| !resource_exists(res)),
-                    ),
+                    .in_set(QuickMenuSet::Input)
+                    .run_if(not(resource_exists::<CleanUpUI>)),
+            )
+            // Also while no menu is shown
+            .add_systems(
+                Update,
+                systems::last_input_device_system.in_set(QuickMenuSet::Input),
+            )
+            // After the sounds of all menus were written
+            .add_systems(
+                Update,
+                (systems::navigation_sound_system, systems::haptics_system)
+                    .chain()
+                    .after(QuickMenuSet::Apply)
+                    .before(QuickMenuSet::Redraw),
+            )
+            .add_systems(
+                Update,
+                systems::dropdown_placement_system.in_set(QuickMenuSet::Redraw),
+            )
+            // After the `cleanup_system` of all menus
+            .add_systems(
+                Update,
+                systems::cleanup_done_system
+                    .after(QuickMenuSet::Redraw)
+                    .run_if(resource_exists::<CleanUpUI>),
             );
     }
 }
 
-/// Remove the menu
+/// Remove all menus. Use [`MenuState::close`] to remove a single one
 pub fn cleanup(commands: &mut Commands) {
     commands.init_resource::<CleanUpUI>();
 }
 
+/// Whether the menu for `S` is being removed, by `cleanup` or `MenuState::close`
+fn closing<S>(cleanup: Option<Res<CleanUpUI>>, menu_state: Option<Res<MenuState<S>>>) -> bool
+where
+    S: ScreenTrait + 'static,
+{
+    cleanup.is_some() || menu_state.is_some_and(|state| state.close_requested)
+}
+
+/// A run condition that is true while the menu for `S` is shown and not hidden via
+/// [`MenuState::set_visible`]. Gameplay systems
+/// can use `.run_if(not(menu_is_open::<S>()))` so that the input meant for the menu
+/// doesn't also reach them
+pub fn menu_is_open<S>(
+) -> impl FnMut(Option<Res<MenuState<S>>>, Option<Res<CleanUpUI>>) -> bool + Clone
+where
+    S: ScreenTrait + 'static,
+{
+    |menu_state, cleanup| {
+        cleanup.is_none()
+            && menu_state.is_some_and(|state| {
+                state.root.is_some() && state.visible && !state.close_requested
+            })
+    }
+}
+
 /// A type conforming to this trait is used to handle the events that
 /// are generated as the user interacts with the menu
 pub trait ActionTrait: Debug + PartialEq + Eq + Clone + Copy + Hash + Send + Sync {
     type State;
     type Event: Message + Send + Sync + 'static;
     fn handle(&self, state: &mut Self::State, event_writer: &mut MessageWriter<Self::Event>);
+
+    /// Called when the value of an item that is edited in place (e.g. a slider)
+    /// changes. The default implementation ignores the value and calls `handle`
+    fn handle_value(
+        &self,
+        state: &mut Self::State,
+        value: &MenuValue,
+        event_writer: &mut MessageWriter<Self::Event>,
+    ) {
+        let _ = value;
+        self.handle(state, event_writer);
+    }
+
+    /// Called for every action with the `Selections` of all menus, e.g. to apply a
+    /// combination of settings at once. `value` is set for items that are edited in
+    /// place. The default implementation ignores the selections and calls `handle_value`
+    /// or `handle`
+    fn handle_with_selections(
+        &self,
+        state: &mut Self::State,
+        value: Option<&MenuValue>,
+        selections: &Selections,
+        event_writer: &mut MessageWriter<Self::Event>,
+    ) {
+        let _ = selections;
+        match value {
+            Some(value) => self.handle_value(state, value, event_writer),
+            None => self.handle(state, event_writer),
+        }
+    }
 }
 
 /// Each Menu / Screen uses this trait to define which menu items lead
@@ -238,9 +491,33 @@ pub trait ScreenTrait: Debug + PartialEq + Eq + Clone + Copy + Hash + Send + Syn
     type Action: ActionTrait<State = Self::State>;
     type State: Send + Sync + 'static;
     fn resolve(&self, state: &<<Self as ScreenTrait>::Action as ActionTrait>::State) -> Menu<Self>;
+
+    /// Called when this screen ends up on top of the menu, e.g. to start loading what
+    /// it shows. This happens once per transition, together with `ScreenChangedEvent`,
+    /// also when coming back from a screen above it and for the first screen of a new
+    /// menu. Re-renders don't call it. The default implementation does nothing
+    fn on_enter(
+        &self,
+        state: &mut Self::State,
+        event_writer: &mut MessageWriter<<Self::Action as ActionTrait>::Event>,
+    ) {
+        let _ = (state, event_writer);
+    }
+
+    /// Called when this screen stops being on top of the menu, because it was left or
+    /// another screen was shown above it, and when the menu is closed. Like `on_enter`,
+    /// only transitions call it. The default implementation does nothing
+    fn on_exit(
+        &self,
+        state: &mut Self::State,
+        event_writer: &mut MessageWriter<<Self::Action as ActionTrait>::Event>,
+    ) {
+        let _ = (state, event_writer);
+    }
 }
 
-/// The primary state resource of the menu
+/// The primary state resource of the menu. There is one per screen type, so several
+/// menus, e.g. one per player, need a screen type each
 #[derive(Resource)]
 pub struct MenuState<S>
 where
@@ -248,6 +525,37 @@ where
 {
     menu: NavigationMenu<S>,
     pub initial_render_done: bool,
+    /// Set when the screens were changed from outside of the menu
+    redraw_requested: bool,
+    /// When each kind of event was last handled, for `NavigationConfig::cooldown`
+    handled_at: HashMap<NavigationEvent, Duration>,
+    /// The selected rows and edited values of this menu
+    selections: Selections,
+    /// The rendered menu, if any
+    root: Option<Entity>,
+    /// The user's node the menu is attached to, see `with_parent`
+    parent: Option<Entity>,
+    /// Handled like input by `input_system`, e.g. after a click on the backdrop
+    queued: Option<NavigationEvent>,
+    /// Set by `close`, the menu is removed by `cleanup_system`
+    close_requested: bool,
+    /// Which inputs navigate this menu
+    inputs: MenuInputs,
+    /// Hidden menus keep their UI, but ignore input
+    visible: bool,
+    /// Set when `visible` wasn't applied to the UI yet
+    visibility_changed: bool,
+    /// The running fade, see `MenuOptions::transition_duration`
+    fade: Option<Fade>,
+    /// The frame around the focussed item, see `MenuOptions::highlight_duration`
+    highlight: Option<Entity>,
+    /// The menu that replaces `root` once it is built, see `MenuOptions::max_spawns_per_frame`
+    pending: Option<PendingBuild>,
+    /// The letters typed so far, if typing jumps to items, see `with_type_to_search`
+    search: Option<TypeSearch>,
+    /// The item below the cursor that `NavigationConfig::hover_selects` focussed last.
+    /// A row that is rebuilt below the cursor is hovered again, but keeps its focus
+    pub(crate) hovered: Option<(WidgetId, usize)>,
 }
 
 impl<S> MenuState<S>
@@ -258,9 +566,147 @@ where
         Self {
             menu: NavigationMenu::new(state, screen, sheet),
             initial_render_done: false,
+            redraw_requested: false,
+            handled_at: HashMap::new(),
+            selections: Selections::default(),
+            root: None,
+            parent: None,
+            queued: None,
+            close_requested: false,
+            inputs: MenuInputs::All,
+            visible: true,
+            visibility_changed: false,
+            fade: None,
+            highlight: None,
+            pending: None,
+            search: None,
+            hovered: None,
+        }
+    }
+
+    /// Typing letters focusses the next item whose label starts with them, e.g. "au"
+    /// for "Audio" in a long list. The letters start over after `reset_after` without
+    /// a key press. Keys bound in `KeyBindings` still navigate, and text inputs that
+    /// are being edited get the letters instead
+    pub fn with_type_to_search(mut self, reset_after: Duration) -> Self {
+        self.search = Some(TypeSearch {
+            reset_after,
+            typed: String::new(),
+            typed_at: Duration::ZERO,
+        });
+        self
+    }
+
+    /// Hide or show the menu without removing it, e.g. for a pause menu that is
+    /// toggled often. The screens and selections are kept, and a hidden menu
+    /// ignores all input. Hiding it ends editing a text and waiting for a binding
+    pub fn set_visible(&mut self, visible: bool) {
+        if self.visible != visible {
+            self.visible = visible;
+            self.visibility_changed = true;
         }
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Only navigate this menu with some inputs, e.g. a single gamepad for
+    /// local multiplayer
+    pub fn with_inputs(mut self, inputs: MenuInputs) -> Self {
+        self.inputs = inputs;
+        self
+    }
+
+    /// Remove this menu like [`cleanup`] does, but without closing the menus of
+    /// other screen types. After fading out, the `MenuState` resource is removed,
+    /// so closing a menu that is already closing does nothing
+    pub fn close(&mut self) {
+        self.close_requested = true;
+    }
+
+    /// Whether `close` was called and the menu is being removed
+    pub fn is_closing(&self) -> bool {
+        self.close_requested
+    }
+
+    /// Attach the menu to `parent`, e.g. a side panel of a larger HUD, instead of
+    /// spawning it on its own. The menu then takes part in the flex layout of
+    /// `parent`. `parent` has to be a UI node that outlives the menu: closing the
+    /// menu only removes the menu itself, and despawning `parent` removes the
+    /// menu along with it
+    pub fn with_parent(mut self, parent: Entity) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Show the path of screens above the menu, e.g. "Main > Settings > Audio", with
+    /// `screen_title` naming each screen. Clicking a title goes back to that screen.
+    /// The bar is hidden on the root screen and styled by `Stylesheet::breadcrumb`
+    pub fn with_breadcrumbs(mut self, screen_title: fn(&S) -> String) -> Self {
+        self.menu.breadcrumbs = Some(screen_title);
+        self
+    }
+
+    /// Change which inputs navigate this menu, e.g. once a gamepad connects
+    pub fn set_inputs(&mut self, inputs: MenuInputs) {
+        self.inputs = inputs;
+    }
+
+    /// The selected rows and edited values of this menu
+    pub fn selections(&self) -> &Selections {
+        &self.selections
+    }
+
+    /// A copy of the selected rows and edited values, e.g. to save them with
+    /// the `serde` feature
+    pub fn export_selections(&self) -> Selections {
+        self.selections.clone()
+    }
+
+    /// Restore what `export_selections` returned. A menu that wasn't shown yet opens
+    /// with these rows selected. Menus that no longer exist are ignored, and rows
+    /// beyond the end of a menu select its last item
+    pub fn import_selections(&mut self, selections: Selections) {
+        self.selections = selections;
+        self.redraw_requested = true;
+    }
+
+    /// The value of an item of the shown screens, e.g. the index of a stepper. Items
+    /// are identified by the id of their menu and their index among its selectable
+    /// items, like in the `Selections`
+    pub fn get_selection(&self, menu_identifier: &(WidgetId, usize)) -> Option<MenuValue> {
+        self.menu.value(menu_identifier, &self.selections)
+    }
+
+    /// Change the value of an item of the shown screens, e.g. to restore the defaults,
+    /// without calling `ActionTrait::handle_value`. Sliders are clamped to their range
+    /// and steppers to their options. Returns `false` and changes nothing if there is
+    /// no such item or `value` is of another kind. The menu is re-rendered in the
+    /// next frame
+    pub fn set_selection(&mut self, menu_identifier: (WidgetId, usize), value: MenuValue) -> bool {
+        let set = self
+            .menu
+            .set_value(&menu_identifier, value, &mut self.selections);
+        self.redraw_requested |= set;
+        set
+    }
+
+    /// Change the `Stylesheet::scale` of the menu, e.g. from a text size setting.
+    /// The menu is re-rendered in the next frame
+    pub fn set_scale(&mut self, scale: f32) {
+        self.redraw_requested |= self.menu.set_scale(scale);
+    }
+
+    /// Replace the `Stylesheet` of the menu, e.g. when the player switches between a
+    /// light and a dark theme. The menu is re-rendered in the next frame and keeps its
+    /// screens, focus and selections, as well as the scale of `set_scale`.
+    /// To restyle all menus at once, insert the `Stylesheet` as a resource instead
+    pub fn set_stylesheet(&mut self, sheet: Stylesheet) {
+        self.menu.set_stylesheet(sheet);
+        self.redraw_requested = true;
+    }
+
     /// Get a mutable reference to the state in order to change it.
     /// Changing something here will cause a re-render in the next frame.
     /// Due to the way bevy works, just getting this reference, without actually performing
@@ -273,4 +719,140 @@ where
     pub fn state(&self) -> &S::State {
         &self.menu.state
     }
+
+    /// Show `screen` on top of the current screens, as if the user had selected it.
+    /// The menu is re-rendered in the next frame.
+    pub fn push_screen(&mut self, screen: S) {
+        self.menu.push(screen);
+        self.redraw_requested = true;
+    }
+
+    /// Go back one screen, as if the user had pressed `Back`. The root screen is
+    /// never removed, in which case `None` is returned.
+    pub fn pop_screen(&mut self) -> Option<S> {
+        let popped = self.menu.pop();
+        self.redraw_requested |= popped.is_some();
+        popped
+    }
+
+    /// Go back to the root screen, as if the user had sent `NavigationEvent::Root`.
+    /// The selections of the removed screens are kept. Returns whether any screen
+    /// was removed
+    pub fn pop_to_root(&mut self) -> bool {
+        let popped = self.menu.pop_to_root();
+        self.redraw_requested |= popped;
+        popped
+    }
+
+    /// Go back to `screen` if it is currently shown, otherwise push it.
+    pub fn navigate_to(&mut self, screen: S) {
+        self.menu.navigate_to(screen);
+        self.redraw_requested = true;
+    }
+
+    /// The focussed item of the topmost screen, e.g. to show a description next to
+    /// the menu. Reflects all input handled so far, and is `None` if the screen has
+    /// nothing to focus
+    pub fn current_selection(&self) -> Option<CurrentSelection> {
+        self.menu.current_selection(&self.selections)
+    }
+
+    /// The whole stack of shown screens, from the root screen to the topmost one, e.g.
+    /// for breadcrumbs. It reflects every change made so far, be it by input, clicks
+    /// on items of lower screens or `push_screen` and friends
+    pub fn screens(&self) -> &[S] {
+        self.menu.screens()
+    }
+
+    /// Whether `event` closes the menu: `Close` on the root screen, unless it
+    /// closes the list of an open dropdown or a confirm dialog
+    pub(crate) fn closes_with(&self, event: &NavigationEvent) -> bool {
+        *event == NavigationEvent::Close
+            && self.menu.screens().len() == 1
+            && self.menu.dropdown().is_none()
+            && !self.menu.is_confirming()
+    }
+
+    /// Whether `event` may be handled at `now`, given the `cooldown` since the last
+    /// event of the same kind. Marks the event as handled if so
+    pub(crate) fn cooled_down(
+        &mut self,
+        event: NavigationEvent,
+        now: Duration,
+        cooldown: Duration,
+    ) -> bool {
+        if let Some(last) = self.handled_at.get(&event) {
+            if now.saturating_sub(*last) < cooldown {
+                return false;
+            }
+        }
+        self.handled_at.insert(event, now);
+        true
+    }
+
+    /// Feed `events` into the menu without a running app, the same way user input
+    /// would be handled. Returns the events written by the action handlers and by
+    /// `ScreenTrait::on_enter` / `on_exit` of the screens that were shown or left.
+    /// `Close` on the root screen closes the menu (see `is_closing`) and the events
+    /// after it are ignored. This is meant for testing menu trees:
+    ///
+    /// ```ignore
+    /// let mut menu = MenuState::new(BasicState::default(), Screens::Root, None);
+    ///
+    /// // Dive into the submenu and toggle the first boolean
+    /// menu.simulate(&[NavigationEvent::Down, NavigationEvent::Select]);
+    /// assert_eq!(menu.screens(), &[Screens::Root, Screens::Booleans]);
+    /// menu.simulate(&[NavigationEvent::Select]);
+    /// assert!(menu.state().boolean1);
+    ///
+    /// // Go back and close the menu
+    /// let events = menu.simulate(&[
+    ///     NavigationEvent::Back,
+    ///     NavigationEvent::Up,
+    ///     NavigationEvent::Select,
+    /// ]);
+    /// assert_eq!(menu.screens(), &[Screens::Root]);
+    /// assert!(matches!(events[..], [BasicEvent::Close]));
+    /// ```
+    pub fn simulate(
+        &mut self,
+        events: &[NavigationEvent],
+    ) -> Vec<<S::Action as ActionTrait>::Event> {
+        // Action handlers need a `MessageWriter`, which needs a world
+        let mut world = World::new();
+        world.init_resource::<Messages<<S::Action as ActionTrait>::Event>>();
+        let mut writer_state =
+            SystemState::<MessageWriter<<S::Action as ActionTrait>::Event>>::new(&mut world);
+        let mut writer = writer_state.get_mut(&mut world);
+        // The state may have changed since the last call, as before a redraw
+        self.menu.sync_focus(&mut self.selections);
+        for event in events {
+            // A closing menu ignores all input
+            if self.close_requested {
+                break;
+            }
+            if self.closes_with(event) {
+                self.close();
+                continue;
+            }
+            let previous = self.menu.screens().last().copied();
+            if let (Some(selection), _) = self.menu.apply_event(event, &mut self.selections) {
+                self.menu
+                    .handle_selection(&selection, &self.selections, &mut writer);
+            }
+            // Like `screen_changed_system`, but for every event instead of every frame
+            let current = self.menu.screens().last().copied();
+            if let (Some(previous), Some(current)) = (previous, current) {
+                if previous != current {
+                    previous.on_exit(&mut self.menu.state, &mut writer);
+                    current.on_enter(&mut self.menu.state, &mut writer);
+                }
+            }
+            self.menu.sync_focus(&mut self.selections);
+        }
+        world
+            .resource_mut::<Messages<<S::Action as ActionTrait>::Event>>()
+            .drain()
+            .collect()
+    }
 }
@@ -0,0 +1,165 @@
+//! The `quick_menu!` macro, a declarative shorthand for building menus
+
+/// Declare menus without spelling out `Menu::new` and `MenuItem::` for every entry.
+/// Each item names a `MenuItem` constructor, followed by any of its builder methods.
+/// The macro only expands to the equivalent builder calls:
+///
+/// ```ignore
+/// quick_menu!("boolean" [
+///     label("Toggles some booleans"),
+///     action("Toggle Boolean 1", Actions::Toggle1).checked(state.boolean1),
+/// ])
+///
+/// // is the same as
+/// Menu::new(
+///     "boolean",
+///     vec![
+///         MenuItem::label("Toggles some booleans"),
+///         MenuItem::action("Toggle Boolean 1", Actions::Toggle1).checked(state.boolean1),
+///     ],
+/// )
+/// ```
+///
+/// A whole `ScreenTrait::resolve` can be written as a `match`, with the `Menu`
+/// builder methods following the items. Identifiers that aren't literals go in
+/// parentheses:
+///
+/// ```ignore
+/// fn resolve(&self, state: &BasicState) -> Menu<Screens> {
+///     quick_menu! {
+///         match self {
+///             Screens::Root => "root" [
+///                 headline("Basic Example"),
+///                 screen("Boolean", Screens::Booleans),
+///             ],
+///             Screens::Player(p) => (format!("player {p}")) [
+///                 action("Join", Actions::Join(*p)),
+///             ].wrapping(),
+///         }
+///     }
+/// }
+/// ```
+///
+/// Inside a `match`, a `screen` item can declare the menu it opens in place, so the
+/// screens read as the tree they form. The screen is used as the pattern of its own
+/// arm, so it can't bind anything. The arms still end up in a single `match`, which
+/// keeps the check that every screen has a menu:
+///
+/// ```ignore
+/// quick_menu! {
+///     match self {
+///         Screens::Root => "root" [
+///             headline("Basic Example"),
+///             screen("Boolean", Screens::Booleans) => "boolean" [
+///                 action("Toggle Boolean 1", Actions::Toggle1).checked(state.boolean1),
+///                 screen("More", Screens::More) => "more" [
+///                     back("Back"),
+///                 ],
+///             ].wrapping(),
+///         ],
+///     }
+/// }
+/// ```
+///
+/// Items that are computed, e.g. from a list, need a hand-written `Menu::new`.
+#[macro_export]
+macro_rules! quick_menu {
+    // Every screen waits in a queue of `{ (pattern) id [items] [menu methods] }`
+    // until its items are turned into an arm of the `match`
+    (@menus $screen:tt [ $($arms:tt)* ] []) => {
+        match $screen {
+            $($arms)*
+        }
+    };
+    // Menus without nested screens take a single step
+    (@menus $screen:tt [ $($arms:tt)* ] [
+        {
+            ( $($pattern:tt)* ) $id:tt [
+                $(
+                    $kind:ident ( $($arg:tt)* )
+                    $( . $method:ident ( $($method_arg:tt)* ) )*
+                ),* $(,)?
+            ]
+            [ $($menu_method:tt)* ]
+        }
+        $($queue:tt)*
+    ]) => {
+        $crate::quick_menu!(@menus $screen [
+            $($arms)*
+            $($pattern)* => $crate::Menu::new(
+                $id,
+                vec![$( $crate::MenuItem::$kind($($arg)*) $( .$method($($method_arg)*) )* ),*],
+            ) $($menu_method)*,
+        ] [ $($queue)* ])
+    };
+    (@menus $screen:tt $arms:tt [
+        { $pattern:tt $id:tt [ $($items:tt)* ] $menu_methods:tt }
+        $($queue:tt)*
+    ]) => {
+        $crate::quick_menu!(@items $screen $arms [ $($queue)* ]
+            { $pattern $id $menu_methods } [] $($items)*)
+    };
+    // All items of a menu are done, add its arm
+    (@items $screen:tt [ $($arms:tt)* ] $queue:tt
+        { ( $($pattern:tt)* ) $id:tt [ $($menu_method:tt)* ] } [ $($done:tt)* ]) => {
+        $crate::quick_menu!(@menus $screen [
+            $($arms)*
+            $($pattern)* => $crate::Menu::new($id, vec![$($done)*]) $($menu_method)*,
+        ] $queue)
+    };
+    (@items $screen:tt $arms:tt $queue:tt $menu:tt $done:tt , $($rest:tt)*) => {
+        $crate::quick_menu!(@items $screen $arms $queue $menu $done $($rest)*)
+    };
+    // A screen that declares its menu in place. The menu joins the queue once the
+    // screen is split off the arguments
+    (@items $screen:tt $arms:tt $queue:tt $menu:tt [ $($done:tt)* ]
+        screen ( $($arg:tt)* ) $( . $method:ident ( $($method_arg:tt)* ) )*
+        => $id:tt [ $($items:tt)* ] $( . $menu_method:ident ( $($menu_arg:tt)* ) )*
+        $( , $($rest:tt)* )?) => {
+        $crate::quick_menu!(@child $screen $arms $queue $menu [
+            $($done)*
+            $crate::MenuItem::screen($($arg)*) $( .$method($($method_arg)*) )*,
+        ] ( $($arg)* ) [ $id [ $($items)* ] [ $( .$menu_method($($menu_arg)*) )* ] ]
+            $($($rest)*)?)
+    };
+    (@items $screen:tt $arms:tt $queue:tt $menu:tt [ $($done:tt)* ]
+        $kind:ident ( $($arg:tt)* ) $( . $method:ident ( $($method_arg:tt)* ) )*
+        $( , $($rest:tt)* )?) => {
+        $crate::quick_menu!(@items $screen $arms $queue $menu [
+            $($done)*
+            $crate::MenuItem::$kind($($arg)*) $( .$method($($method_arg)*) )*,
+        ] $($($rest)*)?)
+    };
+    // Skip the label up to the first `,`, what follows is the screen
+    (@child $screen:tt $arms:tt [ $($queue:tt)* ] $menu:tt $done:tt
+        ( , $($pattern:tt)* ) [ $id:tt $items:tt $menu_methods:tt ] $($rest:tt)*) => {
+        $crate::quick_menu!(@items $screen $arms [
+            $($queue)* { ( $($pattern)* ) $id $items $menu_methods }
+        ] $menu $done $($rest)*)
+    };
+    (@child $screen:tt $arms:tt $queue:tt $menu:tt $done:tt
+        ( $skip:tt $($arg:tt)* ) $child:tt $($rest:tt)*) => {
+        $crate::quick_menu!(@child $screen $arms $queue $menu $done ( $($arg)* ) $child $($rest)*)
+    };
+    (match $screen:tt {
+        $(
+            $pattern:pat => $id:tt [ $($items:tt)* ]
+            $( . $method:ident ( $($arg:tt)* ) )*
+        ),* $(,)?
+    }) => {
+        $crate::quick_menu!(@menus $screen [] [
+            $( { ( $pattern ) $id [ $($items)* ] [ $( .$method($($arg)*) )* ] } )*
+        ])
+    };
+    ($id:tt [
+        $(
+            $kind:ident ( $($arg:expr),* $(,)? )
+            $( . $method:ident ( $($method_arg:expr),* $(,)? ) )*
+        ),* $(,)?
+    ]) => {
+        $crate::Menu::new(
+            $id,
+            vec![$( $crate::MenuItem::$kind($($arg),*) $( .$method($($method_arg),*) )* ),*],
+        )
+    };
+}
@@ -2,11 +2,21 @@
 //! This is the primary horizontal menu which is used to host the various
 //! screens / vertical menus.
 use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use crate::{
     style::Stylesheet,
-    types::{MenuAssets, PrimaryMenu, QuickMenuComponent},
+    types::{
+        Binding, CurrentSelection, Menu, MenuAssets, MenuBackdrop, MenuEntity, MenuItem,
+        MenuRowComponent, MenuValue, PrimaryMenu, Prompt, QuickMenuComponent, ValueKey, WidgetId,
+        WidgetLabel,
+    },
+    widgets::{
+        BreadcrumbsWidget, DescriptionPaneWidget, PromptBarWidget, TabBarWidget, VerticalMenu,
+        Widget,
+    },
     Selections,
 };
 
@@ -24,8 +34,83 @@ where
     stack: Vec<S>,
     /// The custom state
     pub(crate) state: S::State,
-    /// The style to use
+    /// The style to use, with the sizes scaled
     pub(crate) stylesheet: Stylesheet,
+    /// `stylesheet` with the spacing of `Stylesheet::rows`, for the items of the menus
+    rows: Stylesheet,
+    /// The style as it was passed in
+    unscaled: Stylesheet,
+    /// What the sizes of `stylesheet` were multiplied by
+    scale: f32,
+    /// The height of the primary window, for `Stylesheet::reference_height`
+    window_height: Option<f32>,
+    /// The first visible entry of menus with `max_rows`
+    scroll: HashMap<WidgetId, usize>,
+    /// The text input that is being edited
+    editing: Option<TextEdit<S::Action>>,
+    /// The binding capture that waits for a key or button
+    capturing: Option<BindingTarget<S::Action>>,
+    /// The dropdown whose list is open
+    dropdown: Option<OpenDropdown<S::Action>>,
+    /// The dialog of a `MenuItem::with_confirm` action, shown on top of the screens
+    dialog: Option<ConfirmDialog<S::Action>>,
+    /// Shown below the menu if the stylesheet asks for `prompts`
+    pub(crate) prompts: Vec<Prompt>,
+    /// The titles of the screens above the menu, see `MenuState::with_breadcrumbs`
+    pub(crate) breadcrumbs: Option<fn(&S) -> String>,
+    /// The key of the focussed row of lists, with the index it was focussed at
+    focussed_keys: HashMap<WidgetId, (usize, String)>,
+    /// The focus of each screen below the topmost one, as it was when the next screen
+    /// was entered. Restored when going back to it
+    returns: Vec<(S, Option<usize>)>,
+}
+
+/// A `MenuItem::TextInput` that is being edited, or a slider or stepper whose value
+/// is typed
+#[derive(Debug)]
+struct TextEdit<A> {
+    menu: WidgetId,
+    index: usize,
+    action: A,
+    max_len: usize,
+    /// The typed number of a slider or stepper, which keeps its value until the
+    /// number is committed
+    number: Option<String>,
+    /// Where the value is kept, and the value the state passed to the item
+    value: (ValueKey, Option<MenuValue>),
+}
+
+/// The longest number that can be typed into a slider or stepper
+const MAX_NUMBER_LEN: usize = 16;
+
+/// A `MenuItem::BindingCapture` that waits for input
+#[derive(Debug)]
+struct BindingTarget<A> {
+    menu: WidgetId,
+    index: usize,
+    action: A,
+    /// Where the value is kept, and the value the state passed to the item
+    value: (ValueKey, Option<MenuValue>),
+}
+
+/// A `MenuItem::Dropdown` whose list of options is open
+#[derive(Debug)]
+struct OpenDropdown<A> {
+    menu: WidgetId,
+    index: usize,
+    action: A,
+    options: usize,
+    highlighted: usize,
+    /// Where the value is kept, and the value the state passed to the item
+    value: (ValueKey, Option<MenuValue>),
+}
+
+/// The question of an action with `MenuItem::with_confirm` that was selected
+#[derive(Debug)]
+struct ConfirmDialog<A> {
+    id: WidgetId,
+    message: WidgetLabel,
+    on_yes: A,
 }
 
 impl<S> NavigationMenu<S>
@@ -33,10 +118,24 @@ where
     S: ScreenTrait,
 {
     pub fn new(state: S::State, root: S, sheet: Option<Stylesheet>) -> Self {
+        let sheet = sheet.unwrap_or_default();
         Self {
             stack: vec![root],
             state,
-            stylesheet: sheet.unwrap_or_default(),
+            stylesheet: sheet.scaled(sheet.scale),
+            rows: sheet.scaled(sheet.scale).for_rows(),
+            scale: sheet.scale,
+            unscaled: sheet,
+            window_height: None,
+            scroll: HashMap::new(),
+            editing: None,
+            capturing: None,
+            dropdown: None,
+            dialog: None,
+            prompts: Vec::new(),
+            breadcrumbs: None,
+            focussed_keys: HashMap::new(),
+            returns: Vec::new(),
         }
     }
 }
@@ -45,8 +144,29 @@ impl<S> NavigationMenu<S>
 where
     S: ScreenTrait + 'static,
 {
-    pub fn show(&self, assets: &MenuAssets, selections: &Selections, commands: &mut Commands) {
-        let node = self
+    /// Spawn the menu and return the root entity, which is the backdrop if
+    /// the menu has one
+    pub fn show(
+        &self,
+        assets: &MenuAssets,
+        selections: &Selections,
+        commands: &mut Commands,
+    ) -> Entity {
+        self.show_deferred(assets, selections, commands, None).0
+    }
+
+    /// Like `show`, but only builds `budget` rows of selectable items. The others are
+    /// returned to be filled by `build_rows`, the menu is hidden until then
+    pub(crate) fn show_deferred(
+        &self,
+        assets: &MenuAssets,
+        selections: &Selections,
+        commands: &mut Commands,
+        budget: Option<usize>,
+    ) -> (Entity, Vec<(Entity, WidgetId, usize)>) {
+        let mut budget = budget.unwrap_or(usize::MAX);
+        let mut deferred = Vec::new();
+        let mut node = self
             .stylesheet
             .node
             .as_ref()
@@ -58,71 +178,1121 @@ where
                 ..default()
             });
 
+        let transform = self
+            .stylesheet
+            .layout
+            .map(|layout| layout.apply(&mut node))
+            .unwrap_or_default();
+
         let background_color = self
             .stylesheet
             .background
             .unwrap_or_else(|| Color::NONE.into());
 
-        commands
-            .spawn((node, background_color))
-            .insert(PrimaryMenu)
+        let mut root = commands.spawn((node, transform, background_color));
+        if let Some(panel) = &self.stylesheet.panel {
+            root.insert(panel.clone());
+        }
+        root.insert(PrimaryMenu)
             .with_children(|parent| {
                 for entry in self.stack.iter() {
-                    let menu_desc = entry.resolve(&self.state);
-                    super::widgets::VerticalMenu {
-                        id: menu_desc.id,
-                        items: &menu_desc.entries,
-                        stylesheet: &self.stylesheet,
-                        assets,
-                        style: menu_desc.style.as_ref(),
-                        background: menu_desc.background.as_ref(),
+                    let menu_desc = self.resolve(entry, selections);
+                    if menu_desc.tabs.is_empty() {
+                        self.vertical_menu(&menu_desc, assets, selections).build(
+                            selections,
+                            parent,
+                            &mut budget,
+                            &mut deferred,
+                        );
+                        continue;
                     }
-                    .build(selections, parent);
+                    // The tabs sit above the menu
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            TabBarWidget::new(
+                                &menu_desc.tabs,
+                                *entry,
+                                &self.stylesheet.tab,
+                                self.stylesheet.rtl,
+                            )
+                            .build(parent, assets);
+                            self.vertical_menu(&menu_desc, assets, selections).build(
+                                selections,
+                                parent,
+                                &mut budget,
+                                &mut deferred,
+                            );
+                        });
+                }
+                if let Some(menu_desc) = self.dialog_menu(selections) {
+                    self.vertical_menu(&menu_desc, assets, selections).build(
+                        selections,
+                        parent,
+                        &mut budget,
+                        &mut deferred,
+                    );
+                }
+                if let Some(node) = &self.stylesheet.description_pane {
+                    let current = self.current_selection(selections);
+                    let description = current.as_ref().and_then(|c| c.description.as_ref());
+                    DescriptionPaneWidget::new(node, description, &self.stylesheet.label)
+                        .build(parent, assets);
+                }
+                if self.stylesheet.prompts {
+                    PromptBarWidget::new(&self.prompts, &self.stylesheet.label)
+                        .build(parent, assets);
+                }
+                // The root screen has no path to show
+                if let (Some(title), true) = (self.breadcrumbs, self.stack.len() > 1) {
+                    BreadcrumbsWidget::new(
+                        &self.stack,
+                        title,
+                        &self.stylesheet.breadcrumb,
+                        self.stylesheet.rtl,
+                    )
+                    .build(parent, assets);
                 }
             })
             .insert(QuickMenuComponent);
+        if let Some(backdrop) = self.stylesheet.backdrop {
+            // Clicks on the menu itself must not reach the backdrop
+            if backdrop.on_click.is_some() {
+                root.insert(FocusPolicy::Block);
+            }
+            let menu = root.id();
+            root = commands.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Vw(100.0),
+                    height: Val::Vh(100.0),
+                    ..default()
+                },
+                BackgroundColor(backdrop.color),
+                MenuBackdrop,
+                QuickMenuComponent,
+            ));
+            root.add_child(menu);
+            if backdrop.on_click.is_some() {
+                root.insert(Interaction::default());
+            }
+        }
+        root.insert(MenuEntity::<S>::default());
+        if let Some(z_index) = self.stylesheet.z_index {
+            root.insert(GlobalZIndex(z_index));
+        }
+        if let Some(camera) = self.stylesheet.target_camera {
+            root.insert(UiTargetCamera(camera));
+        }
+        if !deferred.is_empty() {
+            root.insert(Visibility::Hidden);
+        }
+        (root.id(), deferred)
+    }
+
+    /// Fill the rows left empty by `show_deferred`
+    pub(crate) fn build_rows(
+        &self,
+        assets: &MenuAssets,
+        selections: &Selections,
+        commands: &mut Commands,
+        rows: impl IntoIterator<Item = (Entity, WidgetId, usize)>,
+    ) {
+        let mut menus = HashMap::new();
+        for (entity, id, index) in rows {
+            if !menus.contains_key(&id) {
+                let Some(menu_desc) = self.shown_menu(&id, selections) else {
+                    continue;
+                };
+                menus.insert(id.clone(), menu_desc);
+            }
+            self.vertical_menu(&menus[&id], assets, selections)
+                .rebuild_row(&mut commands.entity(entity), index, selections);
+        }
+    }
+
+    /// Rebuild the rows of the items at `indexes` in the topmost menu, which has to be `menu`
+    pub(crate) fn refocus<'a>(
+        &self,
+        assets: &MenuAssets,
+        selections: &Selections,
+        commands: &mut Commands,
+        rows: impl IntoIterator<Item = (Entity, &'a MenuRowComponent)>,
+        menu: &WidgetId,
+        indexes: [usize; 2],
+    ) {
+        let Some(menu_desc) = self.topmost(selections) else {
+            return;
+        };
+        if menu_desc.id != *menu {
+            return;
+        }
+        let vertical_menu = self.vertical_menu(&menu_desc, assets, selections);
+        for (entity, MenuRowComponent((id, index))) in rows {
+            if id == menu && indexes.contains(index) {
+                vertical_menu.rebuild_row(&mut commands.entity(entity), *index, selections);
+            }
+        }
+    }
+
+    /// Show the description of the focussed item in the description pane
+    pub(crate) fn refresh_description(
+        &self,
+        assets: &MenuAssets,
+        selections: &Selections,
+        commands: &mut Commands,
+        pane: Entity,
+    ) {
+        let Some(node) = &self.stylesheet.description_pane else {
+            return;
+        };
+        let current = self.current_selection(selections);
+        let description = current.as_ref().and_then(|c| c.description.as_ref());
+        DescriptionPaneWidget::new(node, description, &self.stylesheet.label)
+            .rebuild(&mut commands.entity(pane), assets);
+    }
+
+    /// The id of the topmost menu and the index of its focussed item
+    pub(crate) fn focus(&self, selections: &Selections) -> Option<(WidgetId, usize)> {
+        let menu_desc = self.topmost(selections)?;
+        let index = VerticalMenu::focussed_index(&menu_desc, selections)?;
+        Some((menu_desc.id, index))
+    }
+
+    /// The focussed item of the topmost menu, with its label and value
+    pub(crate) fn current_selection(&self, selections: &Selections) -> Option<CurrentSelection> {
+        let menu_desc = self.topmost(selections)?;
+        let (index, item, value) = VerticalMenu::focussed_item(&menu_desc, selections)?;
+        Some(CurrentSelection {
+            index,
+            label: item.text().cloned(),
+            value,
+            description: item.description().cloned(),
+            menu: menu_desc.id.clone(),
+        })
+    }
+
+    /// The shown menu with the id `menu`
+    fn shown_menu(&self, menu: &WidgetId, selections: &Selections) -> Option<Menu<S>> {
+        self.stack
+            .iter()
+            .map(|screen| self.resolve(screen, selections))
+            .chain(self.dialog_menu(selections))
+            .find(|menu_desc| menu_desc.id == *menu)
+    }
+
+    /// The menu that navigation applies to: the open dialog, or the topmost screen
+    fn topmost(&self, selections: &Selections) -> Option<Menu<S>> {
+        self.dialog_menu(selections).or_else(|| {
+            self.stack
+                .last()
+                .map(|screen| self.resolve(screen, selections))
+        })
+    }
+
+    /// The `Menu::confirm` of the open dialog
+    fn dialog_menu(&self, selections: &Selections) -> Option<Menu<S>> {
+        self.dialog.as_ref().map(|dialog| {
+            Menu::confirm(dialog.id.clone(), dialog.message.clone(), dialog.on_yes)
+                .flattened(selections)
+        })
+    }
+
+    /// Ask the question of the focussed item of `menu_desc`, if it is an action with
+    /// `MenuItem::with_confirm`
+    fn open_dialog(&mut self, menu_desc: &Menu<S>, selections: &Selections) -> bool {
+        let Some((
+            index,
+            MenuItem::Action {
+                action,
+                confirm: Some(message),
+                ..
+            },
+        )) = VerticalMenu::focussed_enabled(menu_desc, selections)
+        else {
+            return false;
+        };
+        // Unique among the items of all menus, so that each dialog keeps its own focus
+        let id = WidgetId::new(format!("{} confirm {index}", menu_desc.id.as_str()));
+        self.dialog = Some(ConfirmDialog {
+            id,
+            message: message.clone(),
+            on_yes: *action,
+        });
+        true
+    }
+
+    /// Close the open dialog without performing its action
+    pub(crate) fn close_dialog(&mut self) -> bool {
+        self.dialog.take().is_some()
+    }
+
+    pub(crate) fn is_confirming(&self) -> bool {
+        self.dialog.is_some()
+    }
+
+    /// Is the item at `key` in one of the shown menus a repeatable action
+    pub(crate) fn is_repeatable(&self, key: &(WidgetId, usize), selections: &Selections) -> bool {
+        self.shown_menu(&key.0, selections)
+            .is_some_and(|menu_desc| {
+                VerticalMenu::selectable(&menu_desc, key.1).is_some_and(MenuItem::is_repeatable)
+            })
+    }
+
+    /// The value of the item at `key` in one of the shown menus
+    pub(crate) fn value(
+        &self,
+        key: &(WidgetId, usize),
+        selections: &Selections,
+    ) -> Option<MenuValue> {
+        let menu_desc = self.shown_menu(&key.0, selections)?;
+        let item = VerticalMenu::selectable(&menu_desc, key.1)?;
+        VerticalMenu::item_value(&menu_desc.value_key(key.1), item, selections)
+    }
+
+    /// The AccessKit node of the item at `key` in one of the shown menus: its label,
+    /// a role matching its kind and its value
+    #[cfg(feature = "accesskit")]
+    pub(crate) fn accessibility_node(
+        &self,
+        key: &(WidgetId, usize),
+        assets: &MenuAssets,
+        selections: &Selections,
+    ) -> Option<accesskit::Node> {
+        use accesskit::{Node, Role, Toggled};
+        let menu_desc = self.shown_menu(&key.0, selections)?;
+        let item = VerticalMenu::selectable(&menu_desc, key.1)?;
+        let value = VerticalMenu::item_value(&menu_desc.value_key(key.1), item, selections);
+        let mut node = match (item.unstyled(), value) {
+            (MenuItem::Toggle { .. }, Some(MenuValue::Bool(on))) => {
+                let mut node = Node::new(Role::CheckBox);
+                node.set_toggled(if on { Toggled::True } else { Toggled::False });
+                node
+            }
+            (MenuItem::Radio { .. }, Some(MenuValue::Bool(on))) => {
+                let mut node = Node::new(Role::RadioButton);
+                node.set_toggled(if on { Toggled::True } else { Toggled::False });
+                node
+            }
+            (MenuItem::Collapsible { .. }, Some(MenuValue::Bool(expanded))) => {
+                let mut node = Node::new(Role::Button);
+                node.set_expanded(expanded);
+                node
+            }
+            (
+                MenuItem::Slider {
+                    min,
+                    max,
+                    step,
+                    format,
+                    ..
+                },
+                Some(MenuValue::Float(value)),
+            ) => {
+                let mut node = Node::new(Role::Slider);
+                node.set_min_numeric_value(*min as f64);
+                node.set_max_numeric_value(*max as f64);
+                node.set_numeric_value_step(*step as f64);
+                node.set_numeric_value(value as f64);
+                node.set_value(format(value));
+                node
+            }
+            _ => Node::new(Role::Button),
+        };
+        if let Some(label) = item.text() {
+            let label = label.translated(assets).debug_text();
+            node.set_label(label.trim());
+        }
+        if item.is_disabled() {
+            node.set_disabled();
+        }
+        Some(node)
+    }
+
+    /// Replace the value of the item at `key` in one of the shown menus. Returns
+    /// whether the item exists and `value` is of its kind
+    pub(crate) fn set_value(
+        &self,
+        key: &(WidgetId, usize),
+        value: MenuValue,
+        selections: &mut Selections,
+    ) -> bool {
+        let Some(menu_desc) = self.shown_menu(&key.0, selections) else {
+            return false;
+        };
+        let Some(item) = VerticalMenu::selectable(&menu_desc, key.1) else {
+            return false;
+        };
+        let Some(value) = VerticalMenu::fitted_value(item, value) else {
+            return false;
+        };
+        // Like selecting it, activating a radio option clears the rest of its group
+        if let (MenuItem::Radio { group, .. }, MenuValue::Bool(true)) = (item.unstyled(), &value) {
+            let others = menu_desc.entries.iter().filter(|e| e.is_selectable());
+            for (index, other) in others.enumerate() {
+                if let MenuItem::Radio {
+                    group: g, active, ..
+                } = other.unstyled()
+                {
+                    if g == group {
+                        selections.set_edited(
+                            menu_desc.value_key(index),
+                            MenuValue::Bool(false),
+                            Some(MenuValue::Bool(*active)),
+                        );
+                    }
+                }
+            }
+        }
+        selections.set_edited(
+            menu_desc.value_key(key.1),
+            value,
+            VerticalMenu::initial_value(item),
+        );
+        true
+    }
+
+    /// Change `Stylesheet::scale`. Returns whether the sizes changed
+    pub(crate) fn set_scale(&mut self, scale: f32) -> bool {
+        self.unscaled.scale = scale;
+        self.rescale()
     }
 
+    /// Replace the style, e.g. to switch to a dark theme. Keeps the `scale`, which may
+    /// have been changed with `set_scale`
+    pub(crate) fn set_stylesheet(&mut self, mut sheet: Stylesheet) {
+        sheet.scale = self.unscaled.scale;
+        self.unscaled = sheet;
+        self.stylesheet = self.unscaled.scaled(self.scale);
+        self.rows = self.stylesheet.for_rows();
+        // The new sheet may have another `reference_height`
+        self.rescale();
+    }
+
+    pub(crate) fn window_height(&self) -> Option<f32> {
+        self.window_height
+    }
+
+    /// Follow the height of the primary window if the stylesheet has a
+    /// `reference_height`. Returns whether the sizes changed
+    pub(crate) fn set_window_height(&mut self, height: Option<f32>) -> bool {
+        self.window_height = height;
+        self.unscaled.reference_height.is_some() && self.rescale()
+    }
+
+    fn rescale(&mut self) -> bool {
+        let window = self
+            .unscaled
+            .reference_height
+            .zip(self.window_height)
+            .filter(|(reference, height)| *reference > 0.0 && *height > 0.0)
+            .map(|(reference, height)| height / reference)
+            .unwrap_or(1.0);
+        let scale = self.unscaled.scale * window;
+        if scale == self.scale {
+            return false;
+        }
+        self.scale = scale;
+        self.stylesheet = self.unscaled.scaled(scale);
+        self.rows = self.stylesheet.for_rows();
+        true
+    }
+
+    /// The first visible entry of the topmost menu
+    pub(crate) fn scroll(&self) -> usize {
+        self.stack
+            .last()
+            .map(|screen| screen.resolve(&self.state).id)
+            .and_then(|id| self.scroll.get(&id).copied())
+            .unwrap_or_default()
+    }
+
+    /// Keep the focus where the user left it, after the stack or the state changed
+    pub(crate) fn sync_focus(&mut self, selections: &mut Selections) {
+        self.restore_focus(selections);
+        self.follow_keys(selections);
+        self.forget_stale_values(selections);
+    }
+
+    /// Forget the edited values of the shown items the state passed another value
+    /// to since, so that the state wins
+    fn forget_stale_values(&self, selections: &mut Selections) {
+        for screen in &self.stack {
+            let menu_desc = self.resolve(screen, selections);
+            let items = menu_desc.entries.iter().filter(|e| e.is_selectable());
+            for (index, item) in items.enumerate() {
+                let initial = VerticalMenu::initial_value(item);
+                selections.forget_stale(&menu_desc.value_key(index), initial.as_ref());
+            }
+        }
+    }
+
+    /// Focus the row a screen was left from when going back to it, even if a screen
+    /// above it changed the `Selections` of the same menu id
+    fn restore_focus(&mut self, selections: &mut Selections) {
+        let below = self.stack.len().saturating_sub(1);
+        // Forget the screens that were left or replaced
+        let kept = self
+            .returns
+            .iter()
+            .zip(&self.stack)
+            .take_while(|((entered, _), screen)| entered == *screen)
+            .count();
+        if let (Some((entered, Some(index))), Some(top)) =
+            (self.returns.get(below), self.stack.last())
+        {
+            // Shown again by going back
+            if kept > below && entered == top {
+                let id = self.resolve(top, selections).id;
+                selections.set_row(id, *index);
+            }
+        }
+        self.returns.truncate(kept.min(below));
+        for depth in self.returns.len()..below {
+            let screen = self.stack[depth];
+            let menu_desc = self.resolve(&screen, selections);
+            let index = VerticalMenu::focussed_index(&menu_desc, selections);
+            self.returns.push((screen, index));
+        }
+    }
+
+    /// Move the focus of lists from `Menu::with_list` to the row it was on before the
+    /// data changed. Only if the focus stayed where it was, otherwise the user moved it
+    fn follow_keys(&mut self, selections: &mut Selections) {
+        for screen in self.stack.clone() {
+            let menu_desc = self.resolve(&screen, selections);
+            if menu_desc.keys.is_empty() {
+                self.focussed_keys.remove(&menu_desc.id);
+                continue;
+            }
+            let Some(mut index) = VerticalMenu::focussed_index(&menu_desc, selections) else {
+                self.focussed_keys.remove(&menu_desc.id);
+                continue;
+            };
+            let stored = selections.row(&menu_desc.id).unwrap_or(index);
+            if let Some((focussed, key)) = self.focussed_keys.get(&menu_desc.id) {
+                if *focussed == stored && menu_desc.keys.get(&stored) != Some(key) {
+                    // The row is gone if it can't be found, keep the clamped index
+                    index = menu_desc
+                        .keys
+                        .iter()
+                        .find(|(_, k)| *k == key)
+                        .map(|(i, _)| *i)
+                        .unwrap_or(index);
+                    selections.set_row(menu_desc.id.clone(), index);
+                }
+            }
+            match menu_desc.keys.get(&index) {
+                Some(key) => {
+                    self.focussed_keys
+                        .insert(menu_desc.id.clone(), (index, key.clone()));
+                }
+                None => {
+                    self.focussed_keys.remove(&menu_desc.id);
+                }
+            }
+        }
+    }
+
+    /// The menu of `screen`, with the children of collapsibles behind them
+    fn resolve(&self, screen: &S, selections: &Selections) -> Menu<S> {
+        screen.resolve(&self.state).flattened(selections)
+    }
+
+    fn vertical_menu<'a>(
+        &'a self,
+        menu_desc: &'a Menu<S>,
+        assets: &'a MenuAssets,
+        selections: &Selections,
+    ) -> VerticalMenu<'a, S> {
+        let first = self.scroll.get(&menu_desc.id).copied().unwrap_or_default();
+        let scroll = menu_desc.visible_entries().map(|rows| {
+            (
+                VerticalMenu::scroll_into_view(menu_desc, selections, first),
+                rows,
+            )
+        });
+        VerticalMenu {
+            id: menu_desc.id.clone(),
+            items: &menu_desc.entries,
+            value_ids: &menu_desc.value_ids,
+            stylesheet: &self.rows,
+            scale: self.scale,
+            assets,
+            style: menu_desc.style.as_ref(),
+            background: menu_desc.background.as_ref(),
+            horizontal: menu_desc.horizontal,
+            columns: menu_desc.columns,
+            initial_focus: menu_desc.initial_focus,
+            scroll,
+            editing: self
+                .editing
+                .as_ref()
+                .filter(|edit| edit.menu == menu_desc.id)
+                .map(|edit| edit.index),
+            typed: self
+                .editing
+                .as_ref()
+                .filter(|edit| edit.menu == menu_desc.id)
+                .and_then(|edit| edit.number.as_deref()),
+            capturing: self
+                .capturing
+                .as_ref()
+                .filter(|target| target.menu == menu_desc.id)
+                .map(|target| target.index),
+            dropdown: self
+                .dropdown
+                .as_ref()
+                .filter(|open| open.menu == menu_desc.id)
+                .map(|open| (open.index, open.highlighted)),
+        }
+    }
+
+    /// Start editing the focussed item of the topmost menu, if it is a text input
+    pub(crate) fn begin_text_input(&mut self, selections: &mut Selections) -> bool {
+        let Some(menu_desc) = self.topmost(selections) else {
+            return false;
+        };
+        let Some((
+            index,
+            MenuItem::TextInput {
+                action,
+                value,
+                max_len,
+                ..
+            },
+        )) = VerticalMenu::focussed_enabled(&menu_desc, selections)
+        else {
+            return false;
+        };
+        let key = menu_desc.value_key(index);
+        let initial = Some(MenuValue::Text(value.clone()));
+        let text = VerticalMenu::<S>::text_value(&key, value, selections);
+        selections.set_edited(key.clone(), MenuValue::Text(text), initial.clone());
+        self.editing = Some(TextEdit {
+            menu: menu_desc.id.clone(),
+            index,
+            action: *action,
+            max_len: *max_len,
+            number: None,
+            value: (key, initial),
+        });
+        true
+    }
+
+    /// Start typing a number into the focussed item of the topmost menu, if it is a
+    /// slider or stepper
+    pub(crate) fn begin_number_input(&mut self, selections: &Selections) -> bool {
+        let Some(menu_desc) = self.topmost(selections) else {
+            return false;
+        };
+        let Some((
+            index,
+            item @ (MenuItem::Slider { action, .. } | MenuItem::Stepper { action, .. }),
+        )) = VerticalMenu::focussed_enabled(&menu_desc, selections)
+        else {
+            return false;
+        };
+        self.editing = Some(TextEdit {
+            menu: menu_desc.id.clone(),
+            index,
+            action: *action,
+            max_len: MAX_NUMBER_LEN,
+            number: Some(String::new()),
+            value: (
+                menu_desc.value_key(index),
+                VerticalMenu::initial_value(item),
+            ),
+        });
+        true
+    }
+
+    /// Is a number being typed instead of a text
+    pub(crate) fn is_typing_number(&self) -> bool {
+        self.editing
+            .as_ref()
+            .is_some_and(|edit| edit.number.is_some())
+    }
+
+    /// The typed number of the slider or stepper that is being edited, if it fits
+    /// the item: within the range of a slider, or one of the options of a stepper
+    pub(crate) fn typed_value(&self, selections: &Selections) -> Option<MenuValue> {
+        let editing = self.editing.as_ref()?;
+        let number: f32 = editing.number.as_ref()?.parse().ok()?;
+        let menu_desc = self.shown_menu(&editing.menu, selections)?;
+        match VerticalMenu::selectable(&menu_desc, editing.index)?.unstyled() {
+            MenuItem::Slider { min, max, .. } if (*min..=*max).contains(&number) => {
+                Some(MenuValue::Float(number))
+            }
+            MenuItem::Stepper { options, .. } => options
+                .iter()
+                .position(|option| option.trim().parse() == Ok(number))
+                .map(MenuValue::Index),
+            _ => None,
+        }
+    }
+
+    /// Stop typing a number without changing the value
+    pub(crate) fn cancel_number_input(&mut self) {
+        if self.is_typing_number() {
+            self.editing = None;
+        }
+    }
+
+    pub(crate) fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// The menu and index of the text input that is being edited
+    pub(crate) fn editing_key(&self) -> Option<(WidgetId, usize)> {
+        self.editing
+            .as_ref()
+            .map(|edit| (edit.menu.clone(), edit.index))
+    }
+
+    /// Change the text of the text input that is being edited, or the typed number
+    pub(crate) fn edit_text(
+        &mut self,
+        selections: &mut Selections,
+        edit: impl FnOnce(&mut String),
+    ) {
+        let Some(editing) = &mut self.editing else {
+            return;
+        };
+        let text = match &mut editing.number {
+            Some(number) => number,
+            None => match selections.edited_mut(&editing.value.0) {
+                Some(MenuValue::Text(text)) => text,
+                _ => return,
+            },
+        };
+        edit(text);
+        if text.chars().count() > editing.max_len {
+            *text = text.chars().take(editing.max_len).collect();
+        }
+    }
+
+    /// Stop editing and return the entered text, or the typed number if it fits
+    /// the item (see `typed_value`)
+    pub(crate) fn commit_text(&mut self, selections: &mut Selections) -> Option<MenuSelection<S>> {
+        let value = self.typed_value(selections);
+        let editing = self.editing.take()?;
+        let (key, initial) = editing.value;
+        if editing.number.is_some() {
+            let value = value?;
+            selections.set_edited(key, value.clone(), initial);
+            return Some(MenuSelection::Value(editing.action, value));
+        }
+        let text = match selections.edited(&key, initial.as_ref()) {
+            Some(MenuValue::Text(text)) => text.clone(),
+            _ => String::new(),
+        };
+        Some(MenuSelection::Value(editing.action, MenuValue::Text(text)))
+    }
+
+    /// Wait for a key or button if the focussed item of the topmost menu is a binding capture
+    pub(crate) fn begin_capture(&mut self, selections: &Selections) -> bool {
+        let Some(menu_desc) = self.topmost(selections) else {
+            return false;
+        };
+        let Some((index, item @ MenuItem::BindingCapture { action, .. })) =
+            VerticalMenu::focussed_enabled(&menu_desc, selections)
+        else {
+            return false;
+        };
+        self.capturing = Some(BindingTarget {
+            menu: menu_desc.id.clone(),
+            index,
+            action: *action,
+            value: (
+                menu_desc.value_key(index),
+                VerticalMenu::initial_value(item),
+            ),
+        });
+        true
+    }
+
+    pub(crate) fn is_capturing(&self) -> bool {
+        self.capturing.is_some()
+    }
+
+    /// Stop waiting for input. A captured binding is shown from now on and returned
+    /// for the action, `None` cancels
+    pub(crate) fn end_capture(
+        &mut self,
+        binding: Option<Binding>,
+        selections: &mut Selections,
+    ) -> Option<MenuSelection<S>> {
+        let target = self.capturing.take()?;
+        let binding = binding?;
+        let (key, initial) = target.value;
+        selections.set_edited(key, MenuValue::Binding(binding), initial);
+        Some(MenuSelection::Value(
+            target.action,
+            MenuValue::Binding(binding),
+        ))
+    }
+
+    /// The item of the open dropdown and its highlighted option
+    pub(crate) fn dropdown(&self) -> Option<((WidgetId, usize), usize)> {
+        self.dropdown
+            .as_ref()
+            .map(|open| ((open.menu.clone(), open.index), open.highlighted))
+    }
+
+    /// Highlight `option` of the open dropdown, e.g. because it was clicked
+    pub(crate) fn highlight_option(&mut self, option: usize) {
+        if let Some(open) = &mut self.dropdown {
+            open.highlighted = option.min(open.options.saturating_sub(1));
+        }
+    }
+
+    /// Close the list of the open dropdown without choosing an option
+    pub(crate) fn close_dropdown(&mut self) -> bool {
+        self.dropdown.take().is_some()
+    }
+
+    /// Open the list of the focussed item of the topmost menu, if it is a dropdown
+    fn open_dropdown(&mut self, menu_desc: &Menu<S>, selections: &Selections) -> bool {
+        let Some((
+            index,
+            MenuItem::Dropdown {
+                action,
+                options,
+                index: initial,
+                ..
+            },
+        )) = VerticalMenu::focussed_enabled(menu_desc, selections)
+        else {
+            return false;
+        };
+        if options.is_empty() {
+            return false;
+        }
+        let key = menu_desc.value_key(index);
+        self.dropdown = Some(OpenDropdown {
+            menu: menu_desc.id.clone(),
+            index,
+            action: *action,
+            options: options.len(),
+            highlighted: VerticalMenu::<S>::stepper_value(&key, *initial, options, selections),
+            value: (key, Some(MenuValue::Index(*initial))),
+        });
+        true
+    }
+
+    /// Move within or close the list of the open dropdown. Only the options can be
+    /// reached until it is closed
+    fn apply_dropdown_event(
+        &mut self,
+        event: &NavigationEvent,
+        selections: &mut Selections,
+    ) -> (Option<MenuSelection<S>>, bool) {
+        let Some(open) = &mut self.dropdown else {
+            return (None, false);
+        };
+        let last = open.options - 1;
+        let highlighted = match event {
+            NavigationEvent::Up => open.highlighted.saturating_sub(1),
+            NavigationEvent::Down => (open.highlighted + 1).min(last),
+            NavigationEvent::First | NavigationEvent::PageUp => 0,
+            NavigationEvent::Last | NavigationEvent::PageDown => last,
+            NavigationEvent::Select => {
+                let open = self.dropdown.take().expect("the dropdown is open");
+                let (key, initial) = open.value;
+                let value = MenuValue::Index(open.highlighted);
+                selections.set_edited(key, value.clone(), initial);
+                return (Some(MenuSelection::Value(open.action, value)), true);
+            }
+            NavigationEvent::Back | NavigationEvent::Close => {
+                self.dropdown = None;
+                return (None, true);
+            }
+            _ => open.highlighted,
+        };
+        let changed = highlighted != open.highlighted;
+        open.highlighted = highlighted;
+        (None, changed)
+    }
+
+    /// Apply `event` to the topmost menu. Also returns whether anything besides the
+    /// focus changed: a screen was popped, the menu scrolled or a value was edited
     pub fn apply_event(
         &mut self,
         event: &NavigationEvent,
         selections: &mut Selections,
-    ) -> Option<MenuSelection<S>> {
-        if self.stack.len() > 1 && &NavigationEvent::Back == event {
-            self.stack.pop();
+    ) -> (Option<MenuSelection<S>>, bool) {
+        // The screens may have changed since the dropdown was opened
+        let topmost = self.topmost(selections);
+        // Screens without tabs jump to their first or last item instead, as the
+        // shoulder buttons are bound to the tabs
+        let tabs = topmost
+            .as_ref()
+            .is_some_and(|menu_desc| !menu_desc.tabs.is_empty());
+        let event = &match *event {
+            NavigationEvent::PreviousTab if !tabs => NavigationEvent::First,
+            NavigationEvent::NextTab if !tabs => NavigationEvent::Last,
+            event if self.stylesheet.rtl => event.mirrored(),
+            event => event,
+        };
+        if let Some(open) = &self.dropdown {
+            let still_shown = topmost.as_ref().is_some_and(|menu_desc| {
+                menu_desc.id == open.menu
+                    && matches!(
+                        VerticalMenu::focussed_enabled(menu_desc, selections),
+                        Some((index, MenuItem::Dropdown { .. })) if index == open.index
+                    )
+            });
+            if still_shown {
+                return self.apply_dropdown_event(event, selections);
+            }
+            self.dropdown = None;
+        }
+        if let (NavigationEvent::PreviousTab | NavigationEvent::NextTab, Some(menu_desc)) =
+            (event, &topmost)
+        {
+            let forward = *event == NavigationEvent::NextTab;
+            return (None, self.switch_tab(menu_desc, forward));
         }
-        for (index, entry) in self.stack.iter().enumerate() {
-            let is_last = (index + 1) == self.stack.len();
-            let menu_desc = entry.resolve(&self.state);
-            if is_last {
-                return super::widgets::VerticalMenu::apply_event(
-                    event,
-                    menu_desc.id,
-                    &menu_desc.entries,
-                    selections,
-                );
+        if let (NavigationEvent::Select, Some(menu_desc)) = (event, &topmost) {
+            if self.open_dropdown(menu_desc, selections) || self.open_dialog(menu_desc, selections)
+            {
+                // Nothing to handle, but the list or dialog has to be shown
+                return (Some(MenuSelection::None), true);
+            }
+        }
+        // An open dialog is closed before any screen
+        let popped = match event {
+            NavigationEvent::Back | NavigationEvent::Close => {
+                self.close_dialog() || self.pop().is_some()
+            }
+            NavigationEvent::Root => {
+                let closed = self.close_dialog();
+                self.pop_to_root() || closed
             }
+            _ => false,
+        };
+        let Some(menu_desc) = self.topmost(selections) else {
+            return (None, popped);
+        };
+        let before = selections.clone();
+        let selection = VerticalMenu::apply_event(event, &menu_desc, selections);
+        let mut scrolled = false;
+        if menu_desc.max_rows.is_some() {
+            let previous = self.scroll.get(&menu_desc.id).copied().unwrap_or_default();
+            // Without a focus to follow, e.g. in credits, the navigation scrolls the menu
+            let first = if menu_desc.entries.iter().any(MenuItem::is_selectable) {
+                previous
+            } else {
+                Self::scroll_unfocussed(&menu_desc, event, previous)
+            };
+            let first = VerticalMenu::scroll_into_view(&menu_desc, selections, first);
+            self.scroll.insert(menu_desc.id, first);
+            scrolled = first != previous;
         }
-        None
+        (
+            selection,
+            popped || scrolled || !selections.same_values(&before),
+        )
     }
 
+    /// The first shown entry of a menu without selectable items after `event`.
+    /// `VerticalMenu::scroll_into_view` keeps it within the menu
+    fn scroll_unfocussed(menu_desc: &Menu<S>, event: &NavigationEvent, first: usize) -> usize {
+        let columns = menu_desc.columns.unwrap_or(1);
+        let page = menu_desc.visible_entries().unwrap_or(columns);
+        match event {
+            NavigationEvent::Up => first.saturating_sub(columns),
+            NavigationEvent::Down => first + columns,
+            NavigationEvent::PageUp => first.saturating_sub(page),
+            NavigationEvent::PageDown => first + page,
+            NavigationEvent::First => 0,
+            NavigationEvent::Last => usize::MAX,
+            _ => first,
+        }
+    }
+
+    /// Focus the next enabled item of the topmost menu whose label starts with `prefix`,
+    /// ignoring case. `from_next` starts looking after the focussed item instead of at
+    /// it, so that typing the same letter again moves on. Returns whether the focus moved
+    pub(crate) fn jump_to_prefix(
+        &mut self,
+        prefix: &str,
+        from_next: bool,
+        assets: &MenuAssets,
+        selections: &mut Selections,
+    ) -> bool {
+        let Some(menu_desc) = self.topmost(selections) else {
+            return false;
+        };
+        let Some(focus) = VerticalMenu::focussed_index(&menu_desc, selections) else {
+            return false;
+        };
+        let prefix = prefix.to_lowercase();
+        let labels: Vec<_> = menu_desc
+            .entries
+            .iter()
+            .filter(|item| item.is_selectable())
+            .map(|item| {
+                item.text()
+                    .filter(|_| !item.is_hidden() && !item.is_disabled())
+                    .map(|label| label.translated(assets).debug_text().to_lowercase())
+            })
+            .collect();
+        let start = focus + usize::from(from_next);
+        let found = (0..labels.len())
+            .map(|offset| (start + offset) % labels.len())
+            .find(|index| {
+                labels[*index]
+                    .as_ref()
+                    .is_some_and(|label| label.trim_start().starts_with(&prefix))
+            });
+        let Some(index) = found.filter(|index| *index != focus) else {
+            return false;
+        };
+        selections.set_row(menu_desc.id.clone(), index);
+        if menu_desc.max_rows.is_some() {
+            let previous = self.scroll.get(&menu_desc.id).copied().unwrap_or_default();
+            let first = VerticalMenu::scroll_into_view(&menu_desc, selections, previous);
+            self.scroll.insert(menu_desc.id, first);
+        }
+        true
+    }
+
+    /// Perform the action of `selection` or change the screens. Returns whether
+    /// the state or the screens may have changed
     pub fn handle_selection(
         &mut self,
         selection: &MenuSelection<S>,
+        selections: &Selections,
         event_writer: &mut MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
-    ) {
+    ) -> bool {
         match selection {
-            MenuSelection::Action(a) => a.handle(&mut self.state, event_writer),
-            MenuSelection::Screen(s) => self.stack.push(*s),
-            MenuSelection::None => (),
+            MenuSelection::Action(a) => {
+                a.handle_with_selections(&mut self.state, None, selections, event_writer);
+                true
+            }
+            MenuSelection::Screen(s) => {
+                self.stack.push(*s);
+                true
+            }
+            MenuSelection::Value(a, v) => {
+                a.handle_with_selections(&mut self.state, Some(v), selections, event_writer);
+                true
+            }
+            MenuSelection::Back(a) => {
+                if let Some(a) = a {
+                    a.handle_with_selections(&mut self.state, None, selections, event_writer);
+                }
+                // The answers of a dialog only close it
+                let popped = self.close_dialog() || self.pop().is_some();
+                popped || a.is_some()
+            }
+            MenuSelection::None => false,
+        }
+    }
+
+    /// Show `screen` on top of the current screens
+    pub(crate) fn push(&mut self, screen: S) {
+        self.stack.push(screen);
+    }
+
+    /// Remove the topmost screen. The root screen is never removed
+    pub(crate) fn pop(&mut self) -> Option<S> {
+        if self.stack.len() > 1 {
+            self.stack.pop()
+        } else {
+            None
         }
     }
 
-    pub fn pop_to_selection(&mut self, selection: &MenuSelection<S>) {
+    /// Remove all screens but the root screen. Returns whether there were any
+    pub(crate) fn pop_to_root(&mut self) -> bool {
+        let popped = self.stack.len() > 1;
+        self.stack.truncate(1);
+        popped
+    }
+
+    /// Replace the topmost screen with the previous or next of the tabs of `menu_desc`,
+    /// its menu. Returns whether the screen changed
+    fn switch_tab(&mut self, menu_desc: &Menu<S>, forward: bool) -> bool {
+        let Some(current) = self.stack.last().copied() else {
+            return false;
+        };
+        let tabs = &menu_desc.tabs;
+        let Some(position) = tabs.iter().position(|(_, screen)| *screen == current) else {
+            return false;
+        };
+        let next = if forward {
+            (position + 1) % tabs.len()
+        } else {
+            (position + tabs.len() - 1) % tabs.len()
+        };
+        let screen = tabs[next].1;
+        self.replace_top(screen)
+    }
+
+    /// Show `screen` in place of the topmost screen, if it is one of the tabs of
+    /// the topmost menu. Returns whether the screen changed
+    pub(crate) fn show_tab(&mut self, screen: S, selections: &Selections) -> bool {
+        let Some(current) = self.stack.last() else {
+            return false;
+        };
+        let menu_desc = self.resolve(current, selections);
+        menu_desc.tabs.iter().any(|(_, tab)| *tab == screen) && self.replace_top(screen)
+    }
+
+    fn replace_top(&mut self, screen: S) -> bool {
+        match self.stack.last_mut() {
+            Some(top) if *top != screen => {
+                *top = screen;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the screens above the one at `depth`. Returns whether there were any
+    pub(crate) fn pop_to_depth(&mut self, depth: usize) -> bool {
+        let popped = self.stack.len() > depth + 1;
+        self.stack.truncate(depth + 1);
+        popped
+    }
+
+    /// Pop back to `screen` if it is already shown, otherwise push it
+    pub(crate) fn navigate_to(&mut self, screen: S) {
+        match self.stack.iter().position(|s| *s == screen) {
+            Some(index) => self.stack.truncate(index + 1),
+            None => self.stack.push(screen),
+        }
+    }
+
+    /// The currently shown screens, starting with the root
+    pub(crate) fn screens(&self) -> &[S] {
+        &self.stack
+    }
+
+    pub fn pop_to_selection(&mut self, selection: &MenuSelection<S>) -> bool {
+        // Clicking an item outside of the open dialog closes it
+        let in_dialog = self
+            .dialog_menu(&Selections::default())
+            .is_some_and(|menu_desc| {
+                menu_desc
+                    .entries
+                    .iter()
+                    .any(|entry| &entry.as_selection() == selection)
+            });
+        let closed = !in_dialog && self.close_dialog();
         let mut found = false;
         let mut items = 0;
         for entry in self.stack.iter() {
-            let menu_desc = entry.resolve(&self.state);
+            // Include the children of all collapsibles
+            let menu_desc = self.resolve(entry, &Selections::default());
 
             if found {
                 items += 1;
@@ -134,10 +1304,12 @@ where
                 }
             }
         }
+        let popped = self.stack.len() > 1 && items > 0;
         if self.stack.len() > 1 {
             for _ in 0..items {
                 self.stack.pop();
             }
         }
+        popped || closed
     }
 }
@@ -5,13 +5,19 @@
 
 use bevy::prelude::*;
 
-#[derive(Debug, Clone, Copy)]
+use crate::types::{MenuAssets, NavigationEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ControlState {
     pub fg: Color,
     pub bg: Color,
 }
 
 impl ControlState {
+    pub fn new(fg: Color, bg: Color) -> Self {
+        Self { fg, bg }
+    }
+
     fn clear(fg: Color) -> Self {
         Self {
             fg,
@@ -38,6 +44,13 @@ impl ControlState {
             bg: Color::WHITE,
         }
     }
+
+    fn disabled() -> Self {
+        Self {
+            fg: Color::srgb(0.5, 0.5, 0.5),
+            bg: Color::srgba(0.0, 0.0, 0.5, 0.4),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +76,118 @@ impl Default for IconStyle {
     }
 }
 
+/// The font of a control state. Fields that are `None` use the `font` and `size`
+/// of the [`StyleEntry`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FontState {
+    pub font: Option<Handle<Font>>,
+    pub size: Option<f32>,
+}
+
+impl FontState {
+    pub fn is_empty(&self) -> bool {
+        self.font.is_none() && self.size.is_none()
+    }
+
+    fn scaled(&self, factor: f32) -> Self {
+        Self {
+            font: self.font.clone(),
+            size: self.size.map(|size| size * factor),
+        }
+    }
+}
+
+/// Images drawn behind an item, e.g. the plates of a themed menu. Use a
+/// `NodeImageMode::Sliced` image so that the corners keep their size however big
+/// the item is. `hover` and `selected` fall back to `normal`, the colors of the
+/// [`ControlState`]s still tint the texts and fill the space behind the images
+///
+/// ```ignore
+/// let plate = |path| ImageNode::new(assets.load(path)).with_mode(NodeImageMode::Sliced(TextureSlicer {
+///     border: BorderRect::all(12.0),
+///     ..default()
+/// }));
+/// ControlImages::new(plate("plate.png")).with_selected(plate("plate_lit.png"))
+/// ```
+#[derive(Debug, Clone)]
+pub struct ControlImages {
+    pub normal: ImageNode,
+    pub hover: Option<ImageNode>,
+    pub selected: Option<ImageNode>,
+}
+
+impl ControlImages {
+    pub fn new(normal: ImageNode) -> Self {
+        Self {
+            normal,
+            hover: None,
+            selected: None,
+        }
+    }
+
+    /// Shown while the mouse hovers the item
+    pub fn with_hover(mut self, hover: ImageNode) -> Self {
+        self.hover = Some(hover);
+        self
+    }
+
+    /// Shown while the item is focussed
+    pub fn with_selected(mut self, selected: ImageNode) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    /// The image of an item that is `selected` or not. Disabled items use `normal`
+    pub(crate) fn state(&self, selected: bool) -> &ImageNode {
+        match &self.selected {
+            Some(image) if selected => image,
+            _ => &self.normal,
+        }
+    }
+
+    pub(crate) fn hovered(&self) -> &ImageNode {
+        self.hover.as_ref().unwrap_or(&self.normal)
+    }
+
+    fn scaled(&self, factor: f32) -> Self {
+        Self {
+            normal: scale_image(&self.normal, factor),
+            hover: self.hover.as_ref().map(|image| scale_image(image, factor)),
+            selected: self
+                .selected
+                .as_ref()
+                .map(|image| scale_image(image, factor)),
+        }
+    }
+}
+
+/// `image` with the corners of a sliced image allowed to grow by `factor`, like the
+/// rest of a scaled menu. Corners never get bigger than the node has room for
+fn scale_image(image: &ImageNode, factor: f32) -> ImageNode {
+    let mut image = image.clone();
+    if let NodeImageMode::Sliced(slicer) = &mut image.image_mode {
+        slicer.max_corner_scale *= factor;
+    }
+    image
+}
+
+/// `val` multiplied by `factor` if it is in pixels
+fn scale_val(val: Val, factor: f32) -> Val {
+    match val {
+        Val::Px(px) => Val::Px(px * factor),
+        val => val,
+    }
+}
+
+fn scale_rect(rect: UiRect, factor: f32) -> UiRect {
+    UiRect {
+        left: scale_val(rect.left, factor),
+        right: scale_val(rect.right, factor),
+        top: scale_val(rect.top, factor),
+        bottom: scale_val(rect.bottom, factor),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StyleEntry {
     pub size: f32,
@@ -71,7 +196,23 @@ pub struct StyleEntry {
     pub normal: ControlState,
     pub hover: ControlState,
     pub selected: ControlState,
+    /// Used for items that are disabled
+    pub disabled: ControlState,
     pub icon_style: IconStyle,
+    /// Places the items within vertical menus, and the lines of wrapped labels
+    pub text_align: Justify,
+    /// Labels that are wider than this wrap onto the next line
+    pub wrap_width: Option<Val>,
+    /// The least height of the items, e.g. to make them easier to tap
+    pub min_height: Val,
+    /// Images behind the items instead of plain colors, see [`ControlImages`]
+    pub images: Option<ControlImages>,
+    /// The font of the texts, `MenuAssets::font` if `None`
+    pub font: Option<Handle<Font>>,
+    /// The font while the mouse hovers the item
+    pub hover_font: FontState,
+    /// The font of the focussed item, e.g. a bigger size
+    pub selected_font: FontState,
 }
 
 impl StyleEntry {
@@ -83,7 +224,15 @@ impl StyleEntry {
             normal: ControlState::normal(),
             hover: ControlState::hover(),
             selected: ControlState::selected(),
+            disabled: ControlState::disabled(),
             icon_style: IconStyle::default(),
+            text_align: Justify::Left,
+            wrap_width: None,
+            min_height: Val::Auto,
+            images: None,
+            font: None,
+            hover_font: FontState::default(),
+            selected_font: FontState::default(),
         }
     }
 
@@ -96,7 +245,15 @@ impl StyleEntry {
             normal: ControlState::clear(gray),
             hover: ControlState::clear(gray),
             selected: ControlState::clear(gray),
+            disabled: ControlState::clear(gray),
             icon_style: IconStyle::default(),
+            text_align: Justify::Left,
+            wrap_width: None,
+            min_height: Val::Auto,
+            images: None,
+            font: None,
+            hover_font: FontState::default(),
+            selected_font: FontState::default(),
         }
     }
 
@@ -108,7 +265,432 @@ impl StyleEntry {
             normal: ControlState::clear(Color::WHITE),
             hover: ControlState::clear(Color::WHITE),
             selected: ControlState::clear(Color::WHITE),
+            disabled: ControlState::clear(Color::WHITE),
             icon_style: IconStyle::default(),
+            text_align: Justify::Left,
+            wrap_width: None,
+            min_height: Val::Auto,
+            images: None,
+            font: None,
+            hover_font: FontState::default(),
+            selected_font: FontState::default(),
+        }
+    }
+
+    /// Paragraphs of `MenuItem::body`, wrapped at 480 pixels
+    pub fn body() -> Self {
+        let gray = Color::srgb(0.85, 0.85, 0.85);
+        Self {
+            size: 18.0,
+            margin: UiRect::all(Val::Px(5.0)),
+            padding: UiRect::all(Val::Px(5.0)),
+            normal: ControlState::clear(gray),
+            hover: ControlState::clear(gray),
+            selected: ControlState::clear(gray),
+            disabled: ControlState::clear(gray),
+            icon_style: IconStyle::default(),
+            text_align: Justify::Left,
+            wrap_width: Some(Val::Px(480.0)),
+            min_height: Val::Auto,
+            images: None,
+            font: None,
+            hover_font: FontState::default(),
+            selected_font: FontState::default(),
+        }
+    }
+
+    /// The titles of `MenuState::with_breadcrumbs`. `hover` is used while the mouse
+    /// is over a title, `selected` for the topmost screen and `disabled` for the
+    /// separators
+    pub fn breadcrumb() -> Self {
+        let gray = Color::srgb(0.7, 0.7, 0.7);
+        Self {
+            size: 16.0,
+            margin: UiRect::all(Val::Px(5.0)),
+            padding: UiRect::axes(Val::Px(5.0), Val::Px(2.0)),
+            normal: ControlState::clear(gray),
+            hover: ControlState::clear(Color::srgb(1.0, 1.0, 0.0)),
+            selected: ControlState::clear(Color::WHITE),
+            disabled: ControlState::clear(Color::srgb(0.5, 0.5, 0.5)),
+            icon_style: IconStyle::default(),
+            text_align: Justify::Left,
+            wrap_width: None,
+            min_height: Val::Auto,
+            images: None,
+            font: None,
+            hover_font: FontState::default(),
+            selected_font: FontState::default(),
+        }
+    }
+
+    /// The tabs of `Menu::with_tabs`. The tab of the shown screen uses `selected`
+    pub fn tab() -> Self {
+        let gray = Color::srgb(0.7, 0.7, 0.7);
+        Self {
+            size: 20.0,
+            margin: UiRect::all(Val::Px(5.0)),
+            padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+            normal: ControlState::clear(gray),
+            hover: ControlState::clear(Color::srgb(1.0, 1.0, 0.0)),
+            selected: ControlState::selected(),
+            disabled: ControlState::disabled(),
+            icon_style: IconStyle::default(),
+            text_align: Justify::Left,
+            wrap_width: None,
+            min_height: Val::Auto,
+            images: None,
+            font: None,
+            hover_font: FontState::default(),
+            selected_font: FontState::default(),
+        }
+    }
+
+    /// The font of the texts in the normal state, or in `state` if it is set
+    pub(crate) fn text_font(&self, assets: &MenuAssets, state: Option<&FontState>) -> TextFont {
+        let state = state.cloned().unwrap_or_default();
+        TextFont {
+            font: state
+                .font
+                .or_else(|| self.font.clone())
+                .unwrap_or_else(|| assets.font.clone()),
+            font_size: state.size.unwrap_or(self.size),
+            ..default()
+        }
+    }
+
+    /// The node and layout of the texts of an item
+    pub(crate) fn text_layout(&self) -> (Node, TextLayout) {
+        (
+            Node {
+                max_width: self.wrap_width.unwrap_or_default(),
+                ..default()
+            },
+            TextLayout::new_with_justify(self.text_align),
+        )
+    }
+
+    /// The sizes of this entry multiplied by `factor`
+    fn scaled(&self, factor: f32) -> Self {
+        Self {
+            size: self.size * factor,
+            margin: scale_rect(self.margin, factor),
+            padding: scale_rect(self.padding, factor),
+            icon_style: IconStyle {
+                width: scale_val(self.icon_style.width, factor),
+                height: scale_val(self.icon_style.height, factor),
+                padding: scale_rect(self.icon_style.padding, factor),
+                ..self.icon_style.clone()
+            },
+            wrap_width: self.wrap_width.map(|width| scale_val(width, factor)),
+            min_height: scale_val(self.min_height, factor),
+            images: self.images.as_ref().map(|images| images.scaled(factor)),
+            hover_font: self.hover_font.scaled(factor),
+            selected_font: self.selected_font.scaled(factor),
+            ..self.clone()
+        }
+    }
+
+    /// How an item is placed across a vertical menu
+    pub(crate) fn align_self(&self) -> AlignSelf {
+        match self.text_align {
+            Justify::Left | Justify::Justified => AlignSelf::FlexStart,
+            Justify::Center => AlignSelf::Center,
+            Justify::Right => AlignSelf::FlexEnd,
+        }
+    }
+}
+
+/// Replaces some of the fields of a [`StyleEntry`] for a single menu item.
+/// Fields that are `None` keep the value from the stylesheet.
+///
+/// ```ignore
+/// MenuItem::action("Delete", Actions::Delete).styled(StyleOverride {
+///     normal: Some(ControlState { fg: Color::WHITE, bg: Color::srgb(0.6, 0.0, 0.0) }),
+///     ..default()
+/// })
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StyleOverride {
+    pub size: Option<f32>,
+    pub margin: Option<UiRect>,
+    pub padding: Option<UiRect>,
+    pub normal: Option<ControlState>,
+    pub hover: Option<ControlState>,
+    pub selected: Option<ControlState>,
+    pub disabled: Option<ControlState>,
+    pub icon_style: Option<IconStyle>,
+    pub font: Option<Handle<Font>>,
+    pub hover_font: Option<FontState>,
+    pub selected_font: Option<FontState>,
+    pub text_align: Option<Justify>,
+    pub wrap_width: Option<Val>,
+    pub min_height: Option<Val>,
+    pub images: Option<ControlImages>,
+}
+
+impl StyleOverride {
+    /// `base` with the fields of this override replaced
+    pub fn apply(&self, base: &StyleEntry) -> StyleEntry {
+        StyleEntry {
+            size: self.size.unwrap_or(base.size),
+            margin: self.margin.unwrap_or(base.margin),
+            padding: self.padding.unwrap_or(base.padding),
+            normal: self.normal.unwrap_or(base.normal),
+            hover: self.hover.unwrap_or(base.hover),
+            selected: self.selected.unwrap_or(base.selected),
+            disabled: self.disabled.unwrap_or(base.disabled),
+            icon_style: self
+                .icon_style
+                .clone()
+                .unwrap_or_else(|| base.icon_style.clone()),
+            font: self.font.clone().or_else(|| base.font.clone()),
+            hover_font: self
+                .hover_font
+                .clone()
+                .unwrap_or_else(|| base.hover_font.clone()),
+            selected_font: self
+                .selected_font
+                .clone()
+                .unwrap_or_else(|| base.selected_font.clone()),
+            text_align: self.text_align.unwrap_or(base.text_align),
+            wrap_width: self.wrap_width.or(base.wrap_width),
+            min_height: self.min_height.unwrap_or(base.min_height),
+            images: self.images.clone().or_else(|| base.images.clone()),
+        }
+    }
+
+    /// The sizes of this override multiplied by `factor`, like those of a scaled stylesheet
+    pub(crate) fn scaled(&self, factor: f32) -> Self {
+        Self {
+            size: self.size.map(|size| size * factor),
+            margin: self.margin.map(|margin| scale_rect(margin, factor)),
+            padding: self.padding.map(|padding| scale_rect(padding, factor)),
+            icon_style: self.icon_style.as_ref().map(|icon| IconStyle {
+                width: scale_val(icon.width, factor),
+                height: scale_val(icon.height, factor),
+                padding: scale_rect(icon.padding, factor),
+                ..icon.clone()
+            }),
+            hover_font: self.hover_font.as_ref().map(|font| font.scaled(factor)),
+            selected_font: self.selected_font.as_ref().map(|font| font.scaled(factor)),
+            wrap_width: self.wrap_width.map(|width| scale_val(width, factor)),
+            min_height: self.min_height.map(|height| scale_val(height, factor)),
+            images: self.images.as_ref().map(|images| images.scaled(factor)),
+            ..self.clone()
+        }
+    }
+
+    /// Combine two overrides. The fields of `other` win
+    pub fn merge(self, other: StyleOverride) -> StyleOverride {
+        StyleOverride {
+            size: other.size.or(self.size),
+            margin: other.margin.or(self.margin),
+            padding: other.padding.or(self.padding),
+            normal: other.normal.or(self.normal),
+            hover: other.hover.or(self.hover),
+            selected: other.selected.or(self.selected),
+            disabled: other.disabled.or(self.disabled),
+            icon_style: other.icon_style.or(self.icon_style),
+            font: other.font.or(self.font),
+            hover_font: other.hover_font.or(self.hover_font),
+            selected_font: other.selected_font.or(self.selected_font),
+            text_align: other.text_align.or(self.text_align),
+            wrap_width: other.wrap_width.or(self.wrap_width),
+            min_height: other.min_height.or(self.min_height),
+            images: other.images.or(self.images),
+        }
+    }
+}
+
+/// The point of the window a menu is placed at, see [`MenuLayout`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MenuAnchor {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+/// Where on the window a menu is placed, e.g. in a corner for a HUD.
+/// Each menu can have its own layout via its stylesheet
+///
+/// ```ignore
+/// let sheet = Stylesheet::default().with_layout(MenuLayout {
+///     anchor: MenuAnchor::BottomCenter,
+///     offset: (Val::Px(0.0), Val::Px(20.0)),
+///     max_width: Some(Val::Px(400.0)),
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MenuLayout {
+    pub anchor: MenuAnchor,
+    /// The distance from the edges of the anchor. On centered axes, positive
+    /// values move the menu to the right / down
+    pub offset: (Val, Val),
+    /// Longer labels wrap instead of making the menu wider
+    pub max_width: Option<Val>,
+}
+
+impl Default for MenuLayout {
+    fn default() -> Self {
+        Self {
+            anchor: MenuAnchor::default(),
+            offset: (Val::Px(0.0), Val::Px(0.0)),
+            max_width: None,
+        }
+    }
+}
+
+impl MenuLayout {
+    pub fn new(anchor: MenuAnchor) -> Self {
+        Self {
+            anchor,
+            ..default()
+        }
+    }
+
+    pub fn with_offset(mut self, x: Val, y: Val) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    pub fn with_max_width(mut self, width: Val) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Position `node` at the anchor. The returned transform centers the node
+    /// on centered axes
+    pub(crate) fn apply(&self, node: &mut Node) -> UiTransform {
+        use MenuAnchor::*;
+        let horizontal = match self.anchor {
+            TopLeft | CenterLeft | BottomLeft => Align::Start,
+            TopCenter | Center | BottomCenter => Align::Center,
+            TopRight | CenterRight | BottomRight => Align::End,
+        };
+        let vertical = match self.anchor {
+            TopLeft | TopCenter | TopRight => Align::Start,
+            CenterLeft | Center | CenterRight => Align::Center,
+            BottomLeft | BottomCenter | BottomRight => Align::End,
+        };
+        node.position_type = PositionType::Absolute;
+        let mut translation = Val2::ZERO;
+        match horizontal {
+            Align::Start => node.left = self.offset.0,
+            Align::Center => {
+                node.left = Val::Percent(50.0);
+                node.margin.left = self.offset.0;
+                translation.x = Val::Percent(-50.0);
+            }
+            Align::End => node.right = self.offset.0,
+        }
+        match vertical {
+            Align::Start => node.top = self.offset.1,
+            Align::Center => {
+                node.top = Val::Percent(50.0);
+                node.margin.top = self.offset.1;
+                translation.y = Val::Percent(-50.0);
+            }
+            Align::End => node.bottom = self.offset.1,
+        }
+        if let Some(max_width) = self.max_width {
+            node.max_width = max_width;
+        }
+        UiTransform::from_translation(translation)
+    }
+}
+
+/// Where the menu is placed along one axis
+enum Align {
+    Start,
+    Center,
+    End,
+}
+
+/// A full-screen color behind the menu, e.g. to darken the game behind a pause menu.
+/// See [`Stylesheet::with_backdrop`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backdrop {
+    pub color: Color,
+    /// Sent to the menu when the backdrop is clicked, e.g. `NavigationEvent::Back`
+    pub on_click: Option<NavigationEvent>,
+}
+
+impl Default for Backdrop {
+    fn default() -> Self {
+        Self {
+            color: Color::srgba(0.0, 0.0, 0.0, 0.5),
+            on_click: None,
+        }
+    }
+}
+
+impl Backdrop {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            on_click: None,
+        }
+    }
+
+    /// Send `event` to the menu when the backdrop is clicked
+    pub fn with_click(mut self, event: NavigationEvent) -> Self {
+        self.on_click = Some(event);
+        self
+    }
+}
+
+/// The spacing of the rows of the menus, see [`Stylesheet::with_rows`]. The padding and
+/// height belong to the items, so they also grow the area that reacts to the mouse and taps
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RowStyle {
+    /// Pixels between two rows, or between the items of horizontal menus and grids
+    pub gap: f32,
+    /// Pixels added above and below each item, on top of the `padding` of its entry
+    pub vertical_padding: f32,
+    /// Pixels added left and right of each item
+    pub horizontal_padding: f32,
+    /// The least height of each item in pixels, `0.0` for none
+    pub min_height: f32,
+}
+
+impl RowStyle {
+    fn scaled(&self, factor: f32) -> Self {
+        Self {
+            gap: self.gap * factor,
+            vertical_padding: self.vertical_padding * factor,
+            horizontal_padding: self.horizontal_padding * factor,
+            min_height: self.min_height * factor,
+        }
+    }
+
+    /// `entry` with the padding and least height of the rows. A `min_height` of the
+    /// entry itself, e.g. from a `StyleOverride`, is kept
+    fn apply(&self, entry: &StyleEntry) -> StyleEntry {
+        let add = |val: Val, px: f32| match val {
+            Val::Px(val) => Val::Px(val + px),
+            Val::Auto => Val::Px(px),
+            val => val,
+        };
+        let padding = entry.padding;
+        StyleEntry {
+            padding: UiRect {
+                left: add(padding.left, self.horizontal_padding),
+                right: add(padding.right, self.horizontal_padding),
+                top: add(padding.top, self.vertical_padding),
+                bottom: add(padding.bottom, self.vertical_padding),
+            },
+            min_height: match entry.min_height {
+                Val::Auto if self.min_height > 0.0 => Val::Px(self.min_height),
+                height => height,
+            },
+            ..entry.clone()
         }
     }
 }
@@ -118,9 +700,44 @@ pub struct Stylesheet {
     pub button: StyleEntry,
     pub label: StyleEntry,
     pub headline: StyleEntry,
+    /// The paragraphs of `MenuItem::body`
+    pub body: StyleEntry,
+    /// The bar of `MenuState::with_breadcrumbs`
+    pub breadcrumb: StyleEntry,
+    /// The tabs of `Menu::with_tabs`
+    pub tab: StyleEntry,
     pub vertical_spacing: f32,
+    /// The gap, padding and least height of the rows, see [`Stylesheet::with_rows`]
+    pub rows: RowStyle,
     pub node: Option<Node>,
     pub background: Option<BackgroundColor>,
+    /// Drawn behind the whole menu, e.g. a nine-slice frame, see [`Stylesheet::with_panel`]
+    pub panel: Option<ImageNode>,
+    /// Show the inputs for `Select` and `Back` below the menu, with the labels
+    /// of the gamepad in use (see [`crate::GamepadGlyphs`])
+    pub prompts: bool,
+    /// Where to show the description of the focussed item, see
+    /// [`crate::MenuItem::with_description`]
+    pub description_pane: Option<Node>,
+    /// Where the menu is placed on the window. Without a layout, the menu is laid out
+    /// like any other node (or by `node`)
+    pub layout: Option<MenuLayout>,
+    /// Draws the menu above (or below) other UI, e.g. the HUD of the game.
+    /// Applied as a `GlobalZIndex`, so that it also works for menus with a parent
+    pub z_index: Option<i32>,
+    /// The camera that renders the menu, e.g. the one of a split-screen player.
+    /// UI ignores `RenderLayers`, so this is how a menu is limited to one camera
+    pub target_camera: Option<Entity>,
+    /// Covers the window behind the menu, see [`Backdrop`]
+    pub backdrop: Option<Backdrop>,
+    /// Mirror the menu for right-to-left languages, see [`Stylesheet::with_rtl`]
+    pub rtl: bool,
+    /// Multiplies the sizes of the menu, see [`Stylesheet::with_scale`]
+    pub scale: f32,
+    /// Scale the menu along with the window, see [`Stylesheet::with_reference_height`]
+    pub reference_height: Option<f32>,
+    /// Shown in front of the focussed item, see [`Stylesheet::with_pointer`]
+    pub pointer: Option<Handle<Image>>,
 }
 
 impl Default for Stylesheet {
@@ -129,21 +746,287 @@ impl Default for Stylesheet {
             button: StyleEntry::button(),
             label: StyleEntry::label(),
             headline: StyleEntry::headline(),
+            body: StyleEntry::body(),
+            breadcrumb: StyleEntry::breadcrumb(),
+            tab: StyleEntry::tab(),
             vertical_spacing: 10.0,
+            rows: RowStyle::default(),
             node: None,
             background: None,
+            panel: None,
+            prompts: false,
+            description_pane: None,
+            layout: None,
+            z_index: None,
+            target_camera: None,
+            backdrop: None,
+            rtl: false,
+            scale: 1.0,
+            reference_height: None,
+            pointer: None,
         }
     }
 }
 
 impl Stylesheet {
+    /// Light texts on dark gray buttons, the focussed button inverted and a hovered one
+    /// a little lighter. Change it further with the `with_*` setters like any other
+    pub fn dark() -> Self {
+        let text = Color::srgb(0.9, 0.9, 0.92);
+        Self::preset(
+            [
+                ControlState::new(text, Color::srgb(0.16, 0.16, 0.2)),
+                ControlState::new(Color::WHITE, Color::srgb(0.28, 0.28, 0.34)),
+                ControlState::new(Color::srgb(0.1, 0.1, 0.12), text),
+                ControlState::new(Color::srgb(0.45, 0.45, 0.5), Color::srgb(0.12, 0.12, 0.15)),
+            ],
+            text,
+            Color::srgb(0.6, 0.6, 0.65),
+            Color::srgba(0.05, 0.05, 0.07, 0.9),
+        )
+    }
+
+    /// Dark texts on light gray buttons, the focussed button inverted and a hovered one
+    /// a little darker
+    pub fn light() -> Self {
+        let text = Color::srgb(0.1, 0.1, 0.12);
+        Self::preset(
+            [
+                ControlState::new(text, Color::srgb(0.86, 0.86, 0.88)),
+                ControlState::new(Color::BLACK, Color::srgb(0.74, 0.74, 0.78)),
+                ControlState::new(Color::WHITE, Color::srgb(0.2, 0.2, 0.25)),
+                ControlState::new(Color::srgb(0.55, 0.55, 0.58), Color::srgb(0.92, 0.92, 0.93)),
+            ],
+            text,
+            Color::srgb(0.35, 0.35, 0.4),
+            Color::srgba(0.97, 0.97, 0.98, 0.9),
+        )
+    }
+
+    /// The default sizes with the `normal`, `hover`, `selected` and `disabled` states of
+    /// the buttons, texts for headlines and bodies and muted texts for labels
+    fn preset(button: [ControlState; 4], text: Color, muted: Color, background: Color) -> Self {
+        let [normal, hover, selected, disabled] = button;
+        let mut sheet = Self::default().with_button_states(normal, hover, selected);
+        sheet.button.disabled = disabled;
+        sheet.tab.normal = ControlState::clear(muted);
+        sheet.tab.hover = ControlState::clear(hover.fg);
+        sheet.tab.selected = selected;
+        sheet.tab.disabled = disabled;
+        for (entry, color) in [
+            (&mut sheet.label, muted),
+            (&mut sheet.headline, text),
+            (&mut sheet.body, text),
+        ] {
+            let state = ControlState::clear(color);
+            entry.normal = state;
+            entry.hover = state;
+            entry.selected = state;
+            entry.disabled = state;
+        }
+        sheet.breadcrumb.normal = ControlState::clear(muted);
+        sheet.breadcrumb.hover = ControlState::clear(hover.fg);
+        sheet.breadcrumb.selected = ControlState::clear(text);
+        sheet.breadcrumb.disabled = ControlState::clear(disabled.fg);
+        sheet.with_background(BackgroundColor(background))
+    }
+
+    /// The colors of the buttons, without the mouse, while it hovers them and while
+    /// they are focussed
+    pub fn with_button_states(
+        mut self,
+        normal: ControlState,
+        hover: ControlState,
+        selected: ControlState,
+    ) -> Self {
+        self.button.normal = normal;
+        self.button.hover = hover;
+        self.button.selected = selected;
+        self
+    }
+
+    /// Use `font` for all texts of the menu instead of the one in `MenuAssets`
+    pub fn with_font(mut self, font: Handle<Font>) -> Self {
+        for entry in [
+            &mut self.button,
+            &mut self.label,
+            &mut self.headline,
+            &mut self.body,
+            &mut self.breadcrumb,
+            &mut self.tab,
+        ] {
+            entry.font = Some(font.clone());
+        }
+        self
+    }
+
     pub fn with_background(mut self, bg: BackgroundColor) -> Self {
         self.background = Some(bg);
         self
     }
 
+    /// Draw `panel` behind the menu, above its `background`. With a
+    /// `NodeImageMode::Sliced` image, the frame fits the menu at every size
+    pub fn with_panel(mut self, panel: ImageNode) -> Self {
+        self.panel = Some(panel);
+        self
+    }
+
+    /// Draw `images` behind the buttons instead of their background colors, see
+    /// [`ControlImages`]. Other entries take images via their `images` field
+    pub fn with_button_images(mut self, images: ControlImages) -> Self {
+        self.button.images = Some(images);
+        self
+    }
+
     pub fn with_node(mut self, style: Node) -> Self {
         self.node = Some(style);
         self
     }
+
+    pub fn with_prompts(mut self) -> Self {
+        self.prompts = true;
+        self
+    }
+
+    /// Align the items of vertical menus and the lines of wrapped labels
+    pub fn with_text_align(mut self, align: Justify) -> Self {
+        for entry in [
+            &mut self.button,
+            &mut self.label,
+            &mut self.headline,
+            &mut self.body,
+        ] {
+            entry.text_align = align;
+        }
+        self
+    }
+
+    /// Wrap labels that are wider than `width`, e.g. for long translations. Bodies
+    /// keep their own `wrap_width`
+    pub fn with_wrap_width(mut self, width: Val) -> Self {
+        for entry in [&mut self.button, &mut self.label, &mut self.headline] {
+            entry.wrap_width = Some(width);
+        }
+        self
+    }
+
+    pub fn with_layout(mut self, layout: MenuLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Draw the menu above other UI with a lower `GlobalZIndex`, e.g. a pause
+    /// menu over the HUD. Each menu can have its own
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = Some(z_index);
+        self
+    }
+
+    /// Cover the window behind the menu, e.g. to darken the game behind a pause menu.
+    /// The backdrop is drawn below the menu, and with the `z_index` of the menu above
+    /// other UI
+    pub fn with_backdrop(mut self, backdrop: Backdrop) -> Self {
+        self.backdrop = Some(backdrop);
+        self
+    }
+
+    /// Render the menu with `camera` only
+    pub fn with_target_camera(mut self, camera: Entity) -> Self {
+        self.target_camera = Some(camera);
+        self
+    }
+
+    /// Mirror the menu for right-to-left languages such as Arabic or Hebrew: icons are
+    /// shown right of the labels, sliders fill from the right, horizontal menus are laid
+    /// out from right to left and `Left` / `Right` are swapped. Shaping the text itself
+    /// is up to the font
+    pub fn with_rtl(mut self) -> Self {
+        self.rtl = true;
+        self
+    }
+
+    /// Show `pointer` in front of the focussed item, e.g. an arrow, sized like the icons
+    /// of the buttons. The other items leave room for it, so that they don't move
+    /// when the focus does
+    pub fn with_pointer(mut self, pointer: Handle<Image>) -> Self {
+        self.pointer = Some(pointer);
+        self
+    }
+
+    /// Multiply the font sizes, margins, paddings, icon sizes and spacing of the menu,
+    /// e.g. from a setting for bigger text. Only sizes in `Val::Px` are scaled, custom
+    /// nodes such as `node` are kept. Unlike bevy's `UiScale`, this leaves the rest of
+    /// the UI alone. Pixels are logical, so high-DPI windows don't need a scale
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Scale the menu with the height of the primary window, so that it takes up the
+    /// same share of the screen at every resolution. At `height` logical pixels, the
+    /// menu has its original size (times `scale`)
+    pub fn with_reference_height(mut self, height: f32) -> Self {
+        self.reference_height = Some(height);
+        self
+    }
+
+    /// A copy of this stylesheet with its sizes multiplied by `factor`
+    pub(crate) fn scaled(&self, factor: f32) -> Self {
+        Self {
+            button: self.button.scaled(factor),
+            label: self.label.scaled(factor),
+            headline: self.headline.scaled(factor),
+            body: self.body.scaled(factor),
+            breadcrumb: self.breadcrumb.scaled(factor),
+            tab: self.tab.scaled(factor),
+            vertical_spacing: self.vertical_spacing * factor,
+            rows: self.rows.scaled(factor),
+            panel: self.panel.as_ref().map(|panel| scale_image(panel, factor)),
+            ..self.clone()
+        }
+    }
+
+    /// Show the description of the focussed item in a pane laid out by `node`,
+    /// e.g. absolutely positioned to the right of the menu. The pane is part of
+    /// the menu's root node
+    pub fn with_description_pane(mut self, node: Node) -> Self {
+        self.description_pane = Some(node);
+        self
+    }
+
+    /// Space the rows out, or pack them more densely than the `padding` of the
+    /// entries does. Only the items of the menus are affected, not the tabs, the
+    /// breadcrumbs or the description pane
+    ///
+    /// ```ignore
+    /// Stylesheet::default().with_rows(RowStyle { gap: 8.0, min_height: 48.0, ..default() })
+    /// ```
+    pub fn with_rows(mut self, rows: RowStyle) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// A copy of this stylesheet for the items of the menus, with the padding and
+    /// least height of `rows` added to their entries
+    pub(crate) fn for_rows(&self) -> Self {
+        Self {
+            button: self.rows.apply(&self.button),
+            label: self.rows.apply(&self.label),
+            headline: self.rows.apply(&self.headline),
+            body: self.rows.apply(&self.body),
+            ..self.clone()
+        }
+    }
+
+    /// A copy of this stylesheet with `style` applied to all entries
+    pub(crate) fn with_override(&self, style: &StyleOverride) -> Self {
+        Self {
+            button: style.apply(&self.button),
+            label: style.apply(&self.label),
+            headline: style.apply(&self.headline),
+            body: style.apply(&self.body),
+            ..self.clone()
+        }
+    }
 }
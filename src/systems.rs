@@ -1,100 +1,369 @@
 use std::collections::HashSet;
 
 use bevy::{
-    input::{gamepad::GamepadAxisChangedEvent, keyboard::KeyboardInput, ButtonState},
+    input::{
+        gamepad::{
+            GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadConnectionEvent,
+            GamepadInput,
+        },
+        keyboard::{Key, KeyboardFocusLost, KeyboardInput},
+        mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+        touch::TouchInput,
+        ButtonState,
+    },
     prelude::*,
+    ui::{ComputedUiRenderTargetInfo, FocusPolicy},
+    window::PrimaryWindow,
 };
 
 use crate::{
+    style::Stylesheet,
     types::{
-        self, ButtonComponent, CleanUpUI, GamepadActivation, MenuAssets, NavigationEvent,
-        QuickMenuComponent,
+        self, Binding, BreadcrumbComponent, ButtonComponent, CapturedInput, CleanUpUI,
+        ClosingMenus, DescriptionPane, DropdownOverlay, Fade, FadeAlpha, GamepadActivation,
+        GamepadBindings, GamepadGlyphs, GamepadLostEvent, GamepadNavigationEvent, Haptic,
+        InputContext, KeyBindings, LastInputDevice, Localization, MenuAssets, MenuBackdrop,
+        MenuBounds, MenuEntity, MenuHapticEvent, MenuHaptics, MenuHighlight, MenuOptions,
+        MenuPlugins, MenuRowComponent, MenuSelection, MenuSound, MenuSoundEvent, MenuValue,
+        NavigationConfig, NavigationEvent, NavigationRepeat, NavigationSounds, OpenedMenus,
+        PendingBuild, Prompt, RefocusEvent, TabComponent, WidgetId,
     },
-    ActionTrait, MenuState, RedrawEvent, ScreenTrait, Selections,
+    ActionTrait, FocusChangedEvent, ItemActivatedEvent, MenuInputs, MenuState, RedrawEvent,
+    ScreenChangedEvent, ScreenTrait, UnhandledNavigationEvent,
 };
 
-// TODO: make this configurable by consumers
-const STICK_THRESHOLD: f32 = 0.10;
-
+#[allow(clippy::too_many_arguments)]
 pub fn keyboard_input_system(
     mut keyboard_input: MessageReader<KeyboardInput>,
     mut writer: MessageWriter<NavigationEvent>,
+    mut gamepad_writer: MessageWriter<GamepadNavigationEvent>,
     mut axis_events: MessageReader<GamepadAxisChangedEvent>,
-    gamepads: Query<&Gamepad>,
-    mut gamepad_activations: Query<&mut GamepadActivation>,
+    mut button_events: MessageReader<GamepadButtonChangedEvent>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut gamepad_activations: Query<(Entity, &mut GamepadActivation)>,
+    config: Res<NavigationConfig>,
+    key_bindings: Res<KeyBindings>,
+    gamepad_bindings: Res<GamepadBindings>,
+    time: Res<Time>,
+    captured_input: Res<CapturedInput>,
+    mut held_key: Local<Option<KeyCode>>,
+    mut key_repeat: Local<NavigationRepeat>,
+    mut held_selects: Local<HashSet<KeyCode>>,
+    mut focus_lost: MessageReader<KeyboardFocusLost>,
 ) {
     use NavigationEvent::*;
+
+    // Keys are typed into the text input instead
+    if captured_input.keyboard() {
+        keyboard_input.clear();
+        *held_key = None;
+        key_repeat.release();
+    }
+    // Releases that were cleared above or went to another window never arrive
+    if captured_input.keyboard() || focus_lost.read().count() > 0 {
+        held_selects.clear();
+    }
+
+    // Repeat a held arrow key
+    writer.write_batch(key_repeat.tick(time.delta(), &config));
+
     for event in keyboard_input.read() {
-        if event.repeat || event.state == ButtonState::Released {
+        if event.state == ButtonState::Released {
+            if *held_key == Some(event.key_code) {
+                *held_key = None;
+                key_repeat.release();
+            }
+            held_selects.remove(&event.key_code);
             continue;
         }
-        match event.key_code {
-            KeyCode::ArrowDown => {
-                writer.write(Down);
-            }
-            KeyCode::ArrowUp => {
-                writer.write(Up);
-            }
-            KeyCode::Enter => {
-                writer.write(Select);
-            }
-            KeyCode::Backspace => {
-                writer.write(Back);
+        if event.repeat {
+            continue;
+        }
+        let Some(navigation) = key_bindings.event_for(event.key_code) else {
+            continue;
+        };
+        // A held `Select` must not also select the first item of the screen it opened,
+        // even where the OS doesn't flag its repeats. It takes a release to select again
+        if navigation == Select && !held_selects.insert(event.key_code) {
+            continue;
+        }
+        writer.write(navigation);
+        if config.key_repeat && navigation.is_direction() {
+            *held_key = Some(event.key_code);
+            key_repeat.hold(navigation);
+        }
+    }
+
+    // Repeat the directions of sticks and buttons that are still held
+    for (entity, mut gamepad_activation) in &mut gamepad_activations {
+        let gamepad = gamepads.get(entity).ok().map(|(_, gamepad)| gamepad);
+        gamepad_activation.release_button(|button| {
+            captured_input.gamepads() || !gamepad.is_some_and(|gamepad| gamepad.pressed(button))
+        });
+        gamepad_writer.write_batch(
+            gamepad_activation
+                .tick(time.delta(), &config)
+                .into_iter()
+                .map(|event| GamepadNavigationEvent {
+                    gamepad: entity,
+                    event,
+                }),
+        );
+    }
+
+    // Buttons are captured for a binding instead
+    for (entity, gamepad) in gamepads.iter().filter(|_| !captured_input.gamepads()) {
+        let Some((event, button)) = gamepad_bindings.first_pressed_input(|b| {
+            gamepad.just_pressed(b) && !(config.analog_triggers && is_trigger(b))
+        }) else {
+            continue;
+        };
+        gamepad_writer.write(GamepadNavigationEvent {
+            gamepad: entity,
+            event,
+        });
+        if event.is_direction() {
+            if let Ok((_, mut gamepad_activation)) = gamepad_activations.get_mut(entity) {
+                gamepad_activation.hold_button(button, event);
             }
-            _ => {}
+        }
+    }
+
+    for event in axis_events.read() {
+        let Ok((gamepad, mut gamepad_activation)) = gamepad_activations.get_mut(event.entity)
+        else {
+            continue;
+        };
+        if !config.stick_axes.is_enabled(event.axis) {
+            continue;
+        }
+        let directions = match event.axis {
+            GamepadAxis::LeftStickY | GamepadAxis::RightStickY => (Up, Down),
+            GamepadAxis::LeftStickX | GamepadAxis::RightStickX => (Right, Left),
+            _ => continue,
         };
+        let thresholds = (config.activate_threshold, config.release_threshold);
+        if let Some(event) = gamepad_activation.update(
+            GamepadInput::Axis(event.axis),
+            event.value,
+            directions,
+            thresholds,
+        ) {
+            gamepad_writer.write(GamepadNavigationEvent { gamepad, event });
+        }
     }
 
-    for gamepad in gamepads {
-        if gamepad.just_pressed(GamepadButton::DPadDown) {
-            writer.write(Down);
-        } else if gamepad.just_pressed(GamepadButton::DPadUp) {
-            writer.write(Up);
-        } else if gamepad.just_pressed(GamepadButton::DPadRight) {
-            writer.write(Back);
-        } else if gamepad.just_pressed(GamepadButton::South)
-            || gamepad.just_pressed(GamepadButton::West)
-        {
-            writer.write(Select);
-        } else if gamepad.just_pressed(GamepadButton::East)
-            || gamepad.just_pressed(GamepadButton::North)
+    // Triggers page through the menu, if enabled
+    for event in button_events.read() {
+        if !config.analog_triggers || captured_input.gamepads() {
+            continue;
+        }
+        let direction = match event.button {
+            GamepadButton::LeftTrigger2 => PageUp,
+            GamepadButton::RightTrigger2 => PageDown,
+            _ => continue,
+        };
+        let Ok((gamepad, mut gamepad_activation)) = gamepad_activations.get_mut(event.entity)
+        else {
+            continue;
+        };
+        let thresholds = (
+            config.trigger_activate_threshold,
+            config.trigger_release_threshold,
+        );
+        if let Some(event) = gamepad_activation.update(
+            GamepadInput::Button(event.button),
+            event.value,
+            (direction, direction),
+            thresholds,
+        ) {
+            gamepad_writer.write(GamepadNavigationEvent { gamepad, event });
+        }
+    }
+}
+
+fn is_trigger(button: GamepadButton) -> bool {
+    matches!(
+        button,
+        GamepadButton::LeftTrigger2 | GamepadButton::RightTrigger2
+    )
+}
+
+/// Write one cue per input for the `NavigationSounds` of all menus. A menu that handled
+/// the event wins over those that couldn't, e.g. because their focus is at the end
+pub fn navigation_sound_system(
+    mut sounds: ResMut<NavigationSounds>,
+    mut writer: MessageWriter<MenuSoundEvent>,
+) {
+    let mut written: Vec<MenuSoundEvent> = Vec::new();
+    for sound in sounds.0.drain(..) {
+        match written.iter_mut().find(|w| w.gamepad == sound.gamepad) {
+            Some(cue) if cue.kind == MenuSound::Invalid => *cue = sound,
+            Some(_) => (),
+            None => written.push(sound),
+        }
+    }
+    writer.write_batch(written);
+}
+
+/// Turn the sound cues of all menus into rumble cues, as configured in `MenuHaptics`
+pub fn haptics_system(
+    mut sounds: MessageReader<MenuSoundEvent>,
+    mut writer: MessageWriter<MenuHapticEvent>,
+    haptics: Res<MenuHaptics>,
+) {
+    for sound in sounds.read() {
+        if let Some(Haptic {
+            intensity,
+            duration,
+        }) = haptics.for_kind(sound.kind)
         {
-            writer.write(Back);
+            writer.write(MenuHapticEvent {
+                kind: sound.kind,
+                gamepad: sound.gamepad,
+                intensity,
+                duration,
+            });
         }
     }
+}
 
-    for event in axis_events.read() {
-        let Ok(mut gamepad_activation) = gamepad_activations.get_mut(event.entity) else {
+/// Remember the kind of device that was used last
+#[allow(clippy::too_many_arguments)]
+pub fn last_input_device_system(
+    mut keys: MessageReader<KeyboardInput>,
+    mut gamepad_buttons: MessageReader<GamepadButtonChangedEvent>,
+    mut gamepad_axes: MessageReader<GamepadAxisChangedEvent>,
+    mut mouse_motion: MessageReader<MouseMotion>,
+    mut mouse_buttons: MessageReader<MouseButtonInput>,
+    mut wheel_events: MessageReader<MouseWheel>,
+    mut touches: MessageReader<TouchInput>,
+    config: Res<NavigationConfig>,
+    mut last: ResMut<LastInputDevice>,
+) {
+    let mut device = None;
+    if keys.read().count() > 0 {
+        device = Some(LastInputDevice::Keyboard);
+    }
+    let pressed = gamepad_buttons
+        .read()
+        .any(|event| event.state == ButtonState::Pressed);
+    // Sticks that rest slightly off center send changes all the time
+    let moved = gamepad_axes
+        .read()
+        .any(|event| event.value.abs() >= config.activate_threshold);
+    if pressed || moved {
+        device = Some(LastInputDevice::Gamepad);
+    }
+    if mouse_motion.read().count() + mouse_buttons.read().count() > 0
+        || wheel_events.read().count() + touches.read().count() > 0
+    {
+        device = Some(LastInputDevice::Mouse);
+    }
+    if let Some(device) = device {
+        last.set_if_neq(device);
+    }
+}
+
+/// Pixels that count as one line of scrolling, for touchpads and the like
+const PIXELS_PER_LINE: f32 = 20.0;
+
+/// Translate vertical mouse wheel scrolling into `Up` / `Down` navigation
+pub fn mouse_wheel_system(
+    mut wheel_events: MessageReader<MouseWheel>,
+    mut writer: MessageWriter<NavigationEvent>,
+    config: Res<NavigationConfig>,
+    // Partial steps, so that slow scrolling adds up
+    mut accumulated: Local<f32>,
+) {
+    for event in wheel_events.read() {
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        };
+        let direction = if config.natural_scrolling { -1.0 } else { 1.0 };
+        *accumulated += lines * config.wheel_sensitivity * direction;
+    }
+    while *accumulated >= 1.0 {
+        *accumulated -= 1.0;
+        writer.write(NavigationEvent::Up);
+    }
+    while *accumulated <= -1.0 {
+        *accumulated += 1.0;
+        writer.write(NavigationEvent::Down);
+    }
+}
+
+/// Forget the held sticks and buttons of gamepads that were disconnected, so that they
+/// don't repeat, and write a `GamepadLostEvent`. They are tracked again on reconnect
+pub fn gamepad_disconnect_system(
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    mut commands: Commands,
+    mut writer: MessageWriter<GamepadLostEvent>,
+) {
+    for event in connections.read().filter(|event| event.disconnected()) {
+        if let Ok(mut gamepad) = commands.get_entity(event.gamepad) {
+            gamepad.remove::<GamepadActivation>();
+        }
+        writer.write(GamepadLostEvent {
+            gamepad: event.gamepad,
+        });
+    }
+}
+
+/// Hand a menu that is bound to a disconnected gamepad to another connected one. Only
+/// if there is a single menu, as the other gamepads belong to other players otherwise
+pub fn gamepad_failover_system<S>(
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    mut menu_state: ResMut<MenuState<S>>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    plugins: Res<MenuPlugins>,
+) where
+    S: ScreenTrait + 'static,
+{
+    for event in connections.read().filter(|event| event.disconnected()) {
+        if plugins.0 != 1 {
+            continue;
+        }
+        let lost = event.gamepad;
+        let Some(other) = gamepads.iter().find(|gamepad| *gamepad != lost) else {
             continue;
         };
-        let current = event.value;
-        let previous = gamepad_activation.insert(event.axis, event.value);
-        match event.axis {
-            GamepadAxis::LeftStickY | GamepadAxis::RightStickY => {
-                if cross_threshold(current, previous, STICK_THRESHOLD, true) {
-                    writer.write(Up);
-                } else if cross_threshold(current, previous, -STICK_THRESHOLD, false) {
-                    writer.write(Down);
-                }
+        let inputs = match menu_state.inputs {
+            MenuInputs::Gamepad(own) if own == lost => MenuInputs::Gamepad(other),
+            MenuInputs::GamepadAndKeyboard(own) if own == lost => {
+                MenuInputs::GamepadAndKeyboard(other)
             }
-            GamepadAxis::LeftStickX | GamepadAxis::RightStickX => {
-                if cross_threshold(current, previous, -STICK_THRESHOLD, false) {
-                    writer.write(Back);
-                }
-            }
-            _ => {}
-        }
+            _ => continue,
+        };
+        menu_state.set_inputs(inputs);
     }
 }
 
-fn cross_threshold(current: f32, previous: f32, v: f32, positive: bool) -> bool {
-    if positive {
-        current > v && v > previous
-    } else {
-        current < v && v < previous
+/// Keep track of the order in which the menus were opened, for `InputContext::MostRecent`.
+/// Replacing the `MenuState` or showing a hidden menu counts as opening it again
+pub fn opened_menus_system<S>(
+    menu_state: Option<Res<MenuState<S>>>,
+    mut opened: ResMut<OpenedMenus>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let id = std::any::TypeId::of::<S>();
+    let shown = menu_state
+        .as_ref()
+        .is_some_and(|state| state.is_visible() && !state.is_closing());
+    let replaced = menu_state.as_ref().is_some_and(|state| state.is_added());
+    let position = opened.0.iter().position(|menu| *menu == id);
+    if let Some(index) = position.filter(|_| !shown || replaced) {
+        opened.0.remove(index);
+    }
+    if shown && (position.is_none() || replaced) {
+        opened.0.push(id);
     }
 }
 
+/// Each gamepad tracks its own sticks, so that the repeats of a held stick
+/// are sent with the gamepad that is held
 pub fn insert_gamepad_activation_system(
     gamepads: Query<Entity, (With<Gamepad>, Without<GamepadActivation>)>,
     mut commands: Commands,
@@ -104,142 +373,1477 @@ pub fn insert_gamepad_activation_system(
     }
 }
 
+/// See `QuickMenuPlugin::with_startup_check`
+pub fn startup_check_system<S>(menu_state: Option<Res<MenuState<S>>>)
+where
+    S: ScreenTrait + 'static,
+{
+    if menu_state.is_none() {
+        let screens = std::any::type_name::<S>();
+        panic!(
+            "QuickMenuPlugin::<{screens}> expects a MenuState<{screens}> after Startup, but \
+             there is none. Insert `MenuState::<{screens}>::new(state, root, stylesheet)` in a \
+             `Startup` system. A `MenuState` for another screen type isn't shown by this plugin"
+        );
+    }
+}
+
+/// Restyle the menu whenever the `Stylesheet` resource is inserted or replaced, and
+/// menus that are opened while it exists
+pub fn stylesheet_system<S>(mut menu_state: ResMut<MenuState<S>>, sheet: Option<Res<Stylesheet>>)
+where
+    S: ScreenTrait + 'static,
+{
+    if let Some(sheet) = sheet.filter(|sheet| sheet.is_changed() || menu_state.is_added()) {
+        menu_state.set_stylesheet(sheet.clone());
+    }
+}
+
+/// Re-render menus with a `Stylesheet::reference_height` when the window is resized
+pub fn window_scale_system<S>(
+    mut menu_state: ResMut<MenuState<S>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    // Only touch the state if the size changed, so that the resource stays unchanged
+    let height = window.height();
+    if menu_state.menu.window_height() != Some(height) {
+        let state = &mut *menu_state;
+        state.redraw_requested |= state.menu.set_window_height(Some(height));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn redraw_system<S>(
     mut commands: Commands,
-    existing: Query<Entity, With<QuickMenuComponent>>,
     mut menu_state: ResMut<MenuState<S>>,
-    selections: Res<Selections>,
-    redraw_reader: MessageReader<RedrawEvent>,
-    assets: Res<MenuAssets>,
-    // mut initial_render_done: Local<bool>,
+    mut redraw_reader: MessageReader<RedrawEvent>,
+    mut refocus_reader: MessageReader<RefocusEvent>,
+    rows: Query<(Entity, &MenuRowComponent)>,
+    children: Query<&Children>,
+    panes: Query<Entity, With<DescriptionPane>>,
+    mut assets: ResMut<MenuAssets>,
+    asset_server: Res<AssetServer>,
+    options: Res<MenuOptions>,
+    localization: Option<Res<Localization>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    key_bindings: Res<KeyBindings>,
+    gamepad_bindings: Res<GamepadBindings>,
+    glyphs: Res<GamepadGlyphs>,
 ) where
     S: ScreenTrait + 'static,
 {
     let mut can_redraw = !redraw_reader.is_empty();
+    if let Some(localization) = localization.filter(|l| l.is_changed()) {
+        assets.localization = Some(localization.clone());
+        can_redraw = true;
+    }
+    // Defer the first render until the texts can be drawn with the font
+    if !menu_state.initial_render_done
+        && !options.skip_load_check
+        && assets.is_loading(&asset_server)
+    {
+        redraw_reader.clear();
+        return;
+    }
+    // Re-render when e.g. another kind of gamepad connects
+    if menu_state.menu.stylesheet.prompts {
+        let prompts = prompts(
+            menu_state.inputs,
+            &gamepads,
+            &key_bindings,
+            &gamepad_bindings,
+            &glyphs,
+        );
+        if prompts != menu_state.menu.prompts {
+            menu_state.menu.prompts = prompts;
+            can_redraw = true;
+        }
+    }
+    if std::mem::take(&mut menu_state.redraw_requested) {
+        can_redraw = true;
+    }
     if !menu_state.initial_render_done {
         menu_state.initial_render_done = true;
         can_redraw = true;
+        if !options.transition_duration.is_zero() {
+            menu_state.fade = Some(Fade::new(options.transition_duration, false));
+        }
     }
+    redraw_reader.clear();
+    let state = &mut *menu_state;
     if can_redraw {
-        for item in existing.iter() {
-            commands.entity(item).despawn();
+        // Everything is rebuilt anyway
+        refocus_reader.clear();
+        state.menu.sync_focus(&mut state.selections);
+        // Start over if the menu changed again before it was built
+        if let Some(pending) = state.pending.take() {
+            commands.entity(pending.root).try_despawn();
+        }
+        let (root, deferred) = state.menu.show_deferred(
+            &assets,
+            &state.selections,
+            &mut commands,
+            options.max_spawns_per_frame,
+        );
+        if deferred.is_empty() {
+            replace_root(&mut commands, state, &children, root, false);
+        } else {
+            state.pending = Some(PendingBuild {
+                root,
+                rows: deferred,
+            });
+        }
+    } else {
+        for event in refocus_reader.read() {
+            // Only look at the rows of this menu, others may use the same ids
+            if state.root != Some(event.root) {
+                continue;
+            }
+            // The menu that is being built has to show the same focus once it is done
+            let pending = state.pending.as_ref().map(|pending| pending.root);
+            for root in [event.root].into_iter().chain(pending) {
+                state.menu.refocus(
+                    &assets,
+                    &state.selections,
+                    &mut commands,
+                    rows.iter_many(children.iter_descendants(root)),
+                    &event.menu,
+                    [event.previous, event.current],
+                );
+                if let Some(pane) = panes.iter_many(children.iter_descendants(root)).next() {
+                    state
+                        .menu
+                        .refresh_description(&assets, &state.selections, &mut commands, pane);
+                }
+            }
+            // Remember the newly focussed row
+            state.menu.sync_focus(&mut state.selections);
+        }
+        if let Some(mut pending) = state.pending.take() {
+            let batch = options
+                .max_spawns_per_frame
+                .unwrap_or(usize::MAX)
+                .clamp(1, pending.rows.len());
+            state.menu.build_rows(
+                &assets,
+                &state.selections,
+                &mut commands,
+                pending.rows.drain(..batch),
+            );
+            if pending.rows.is_empty() {
+                replace_root(&mut commands, state, &children, pending.root, true);
+            } else {
+                state.pending = Some(pending);
+            }
+        }
+    }
+    if let (true, Some(root)) = (std::mem::take(&mut state.visibility_changed), state.root) {
+        commands.entity(root).insert(if state.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+/// Show `root` in place of the menu that was shown before. `hidden` roots were built
+/// over several frames and wait for their visibility
+fn replace_root<S>(
+    commands: &mut Commands,
+    state: &mut MenuState<S>,
+    children: &Query<&Children>,
+    root: Entity,
+    hidden: bool,
+) where
+    S: ScreenTrait + 'static,
+{
+    // Keep the place of the menu among the other children of the user's node
+    let position = state
+        .parent
+        .and_then(|parent| children.get(parent).ok())
+        .and_then(|siblings| siblings.iter().position(|e| Some(e) == state.root));
+    if let Some(root) = state.root.take() {
+        commands.entity(root).try_despawn();
+    }
+    // The user's node may have been despawned in the meantime
+    if let Some(Ok(mut parent)) = state.parent.map(|parent| commands.get_entity(parent)) {
+        match position {
+            Some(position) => parent.insert_children(position, &[root]),
+            None => parent.add_child(root),
+        };
+    }
+    state.root = Some(root);
+    state.visibility_changed = hidden || !state.visible;
+}
+
+/// Fade the backgrounds, texts and images of a menu that opens or closes
+#[allow(clippy::type_complexity)]
+pub fn fade_system<S>(
+    mut commands: Commands,
+    mut menu_state: ResMut<MenuState<S>>,
+    time: Res<Time>,
+    children: Query<&Children>,
+    mut nodes: Query<(
+        Option<&mut BackgroundColor>,
+        Option<&mut TextColor>,
+        Option<&mut ImageNode>,
+        Option<&FadeAlpha>,
+    )>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let state = &mut *menu_state;
+    let (Some(fade), Some(root)) = (state.fade.as_mut(), state.root) else {
+        return;
+    };
+    fade.timer.tick(time.delta());
+    let alpha = fade.alpha();
+    // A closed menu is removed by `cleanup_system`
+    let opened = fade.timer.is_finished() && !fade.closing;
+    for entity in std::iter::once(root).chain(children.iter_descendants(root)) {
+        let Ok((background, text, image, original)) = nodes.get_mut(entity) else {
+            continue;
+        };
+        // Entities that were rebuilt during the fade start from their own colors
+        let original = original.copied().unwrap_or_else(|| {
+            let original = FadeAlpha {
+                background: background.as_ref().map_or(1.0, |c| c.0.alpha()),
+                text: text.as_ref().map_or(1.0, |c| c.0.alpha()),
+                image: image.as_ref().map_or(1.0, |i| i.color.alpha()),
+            };
+            commands.entity(entity).insert(original);
+            original
+        });
+        if let Some(mut background) = background {
+            background.0.set_alpha(original.background * alpha);
+        }
+        if let Some(mut text) = text {
+            text.0.set_alpha(original.text * alpha);
         }
-        menu_state.menu.show(&assets, &selections, &mut commands);
+        if let Some(mut image) = image {
+            image.color.set_alpha(original.image * alpha);
+        }
+        if opened {
+            commands.entity(entity).remove::<FadeAlpha>();
+        }
+    }
+    if opened {
+        state.fade = None;
+    }
+}
+
+/// The inputs for `Select` and `Back` of the gamepad that navigates the menu,
+/// or of the keyboard if there is none
+fn prompts(
+    inputs: MenuInputs,
+    gamepads: &Query<(Entity, &Gamepad)>,
+    key_bindings: &KeyBindings,
+    gamepad_bindings: &GamepadBindings,
+    glyphs: &GamepadGlyphs,
+) -> Vec<Prompt> {
+    let gamepad = gamepads
+        .iter()
+        .find(|(entity, _)| inputs.accepts_gamepad(*entity));
+    [
+        (NavigationEvent::Select, "Select"),
+        (NavigationEvent::Back, "Back"),
+    ]
+    .into_iter()
+    .filter_map(|(event, action)| match gamepad {
+        Some((_, gamepad)) => {
+            let layout = glyphs.layout_for(gamepad);
+            let button = gamepad_bindings.first_input(event)?;
+            Some(Prompt {
+                glyph: glyphs.glyph(layout, button),
+                input: layout.label(button).to_string(),
+                action,
+            })
+        }
+        None if inputs.accepts_keyboard() => Some(Prompt {
+            glyph: None,
+            input: Binding::Key(key_bindings.first_input(event)?).name(),
+            action,
+        }),
+        None => None,
+    })
+    .collect()
+}
+
+/// Describe the items to screen readers via AccessKit, and move the focus of
+/// AccessKit along with the focus of the menu
+#[cfg(feature = "accesskit")]
+pub fn accessibility_system<S>(
+    mut commands: Commands,
+    menu_state: Res<MenuState<S>>,
+    assets: Res<MenuAssets>,
+    mut input_focus: ResMut<bevy_input_focus::InputFocus>,
+    buttons: Query<(Entity, Ref<ButtonComponent<S>>)>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let mut added = false;
+    for (entity, button) in buttons.iter().filter(|(_, button)| button.is_added()) {
+        added = true;
+        let Some(node) = menu_state.menu.accessibility_node(
+            &button.menu_identifier,
+            &assets,
+            &menu_state.selections,
+        ) else {
+            continue;
+        };
+        commands
+            .entity(entity)
+            .insert(bevy_a11y::AccessibilityNode::from(node));
+    }
+    // Rows are spawned again when the focus moves
+    if !added && !menu_state.is_changed() {
+        return;
+    }
+    let focus = menu_state.menu.focus(&menu_state.selections);
+    let focussed = buttons
+        .iter()
+        .find(|(_, button)| Some(&button.menu_identifier) == focus.as_ref())
+        .map(|(entity, _)| entity);
+    if focussed.is_some() && input_focus.0 != focussed {
+        input_focus.0 = focussed;
     }
 }
 
+/// Publish where the menu and its focussed item were laid out, see `MenuBounds`
+pub fn menu_bounds_system<S>(
+    menu_state: Option<Res<MenuState<S>>>,
+    mut bounds: ResMut<MenuBounds<S>>,
+    children: Query<&Children>,
+    buttons: Query<(&ButtonComponent<S>, &ComputedNode, &UiGlobalTransform)>,
+    nodes: Query<(&ComputedNode, &UiGlobalTransform)>,
+) where
+    S: ScreenTrait + 'static,
+{
+    // Nodes are placed in logical pixels
+    let rect = |computed: &ComputedNode, transform: &UiGlobalTransform| {
+        let scale = computed.inverse_scale_factor();
+        Some(Rect::from_center_size(
+            transform.translation * scale,
+            computed.size() * scale,
+        ))
+        // Nodes that were just spawned have no layout yet
+        .filter(|rect| !rect.is_empty())
+    };
+    let shown = menu_state
+        .as_deref()
+        .filter(|state| state.visible)
+        .and_then(|state| Some((state, state.root?)));
+    let Some((state, root)) = shown else {
+        bounds.set_if_neq(MenuBounds::default());
+        return;
+    };
+    let menu = nodes
+        .get(root)
+        .ok()
+        .and_then(|(computed, transform)| rect(computed, transform));
+    let focussed = state.menu.focus(&state.selections).and_then(|focus| {
+        buttons
+            .iter_many(children.iter_descendants(root))
+            .find(|(button, _, _)| button.menu_identifier == focus)
+            .and_then(|(_, computed, transform)| rect(computed, transform))
+    });
+    bounds.set_if_neq(MenuBounds::new(menu, focussed));
+}
+
+/// Slide the frame of `MenuOptions::highlight_duration` to the focussed item.
+/// It is spawned once and kept when the menu is re-rendered
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn highlight_system<S>(
+    mut commands: Commands,
+    mut menu_state: ResMut<MenuState<S>>,
+    options: Res<MenuOptions>,
+    time: Res<Time>,
+    children: Query<&Children>,
+    buttons: Query<(&ButtonComponent<S>, &ComputedNode, &UiGlobalTransform)>,
+    mut highlights: Query<(&mut Node, &mut MenuHighlight, &mut Visibility)>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if options.highlight_duration.is_zero() {
+        return;
+    }
+    let state = &mut *menu_state;
+    let Some(highlight) = state.highlight else {
+        let highlight = commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BorderColor::all(state.menu.stylesheet.button.selected.fg),
+                // Above the menu, without taking clicks from it
+                GlobalZIndex(i32::MAX),
+                FocusPolicy::Pass,
+                Visibility::Hidden,
+                MenuHighlight::new(options.highlight_duration),
+                MenuEntity::<S>::default(),
+            ))
+            .id();
+        if let Some(camera) = state.menu.stylesheet.target_camera {
+            commands.entity(highlight).insert(UiTargetCamera(camera));
+        }
+        state.highlight = Some(highlight);
+        return;
+    };
+    let Ok((mut node, mut frame, mut visibility)) = highlights.get_mut(highlight) else {
+        return;
+    };
+    let target = state
+        .root
+        .zip(state.menu.focus(&state.selections))
+        .and_then(|(root, focus)| {
+            buttons
+                .iter_many(children.iter_descendants(root))
+                .find(|(button, _, _)| button.menu_identifier == focus)
+        })
+        .map(|(_, computed, transform)| {
+            // Nodes are placed in logical pixels
+            let scale = computed.inverse_scale_factor();
+            Rect::from_center_size(transform.translation * scale, computed.size() * scale)
+        })
+        // Buttons that were just spawned have no layout yet
+        .filter(|rect| !rect.is_empty());
+    let (Some(target), true) = (target, state.visible && state.fade.is_none()) else {
+        frame.hide();
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    frame.slide_to(target);
+    frame.tick(time.delta());
+    let rect = frame.current();
+    node.left = Val::Px(rect.min.x);
+    node.top = Val::Px(rect.min.y);
+    node.width = Val::Px(rect.width());
+    node.height = Val::Px(rect.height());
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+/// Show the list of an open dropdown, below its row or above it if it would extend
+/// past the bottom of the screen
+#[allow(clippy::type_complexity)]
+pub fn dropdown_placement_system(
+    mut overlays: Query<(
+        &mut Node,
+        &mut Visibility,
+        &mut DropdownOverlay,
+        &ComputedNode,
+        &UiGlobalTransform,
+        &ComputedUiRenderTargetInfo,
+    )>,
+) {
+    for (mut node, mut visibility, mut overlay, computed, transform, target) in &mut overlays {
+        // Lists that were just spawned have no layout yet
+        if overlay.placed || computed.size() == Vec2::ZERO {
+            continue;
+        }
+        // Both in physical pixels
+        let bottom = transform.translation.y + computed.size().y / 2.0;
+        let height = target.physical_size().y as f32;
+        if height > 0.0 && bottom > height {
+            node.top = Val::Auto;
+            node.bottom = Val::Percent(100.0);
+        }
+        overlay.placed = true;
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Compare the topmost screen with the one from the last frame and call the
+/// `ScreenTrait::on_enter` / `on_exit` hooks
+pub fn screen_changed_system<S>(
+    mut menu_state: ResMut<MenuState<S>>,
+    mut shown: Local<Option<S>>,
+    mut writer: MessageWriter<ScreenChangedEvent<S>>,
+    mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+) where
+    S: ScreenTrait + 'static,
+{
+    // A new menu doesn't count as a change, but its first screen is entered
+    if menu_state.is_added() {
+        *shown = None;
+    }
+    let Some(current) = menu_state.screens().last().copied() else {
+        return;
+    };
+    match shown.replace(current) {
+        Some(previous) if previous != current => {
+            writer.write(ScreenChangedEvent { previous, current });
+            previous.on_exit(menu_state.state_mut(), &mut event_writer);
+            current.on_enter(menu_state.state_mut(), &mut event_writer);
+        }
+        Some(_) => (),
+        None => current.on_enter(menu_state.state_mut(), &mut event_writer),
+    }
+}
+
+/// Compare the focussed item with the one from the last change of the `MenuState`
+pub fn focus_changed_system<S>(
+    menu_state: Res<MenuState<S>>,
+    mut shown: Local<Option<Option<(WidgetId, usize)>>>,
+    mut writer: MessageWriter<FocusChangedEvent<S>>,
+) where
+    S: ScreenTrait + 'static,
+{
+    // A new menu doesn't count as a change
+    if menu_state.is_added() {
+        *shown = None;
+    } else if !menu_state.is_changed() {
+        return;
+    }
+    let Some(screen) = menu_state.screens().last().copied() else {
+        return;
+    };
+    let focus = menu_state.menu.focus(&menu_state.selections);
+    match shown.replace(focus.clone()) {
+        Some(from) if from != focus => {
+            writer.write(FocusChangedEvent {
+                screen,
+                from,
+                to: focus,
+            });
+        }
+        _ => (),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn input_system<S>(
     mut reader: MessageReader<NavigationEvent>,
+    mut gamepad_reader: MessageReader<GamepadNavigationEvent>,
     mut menu_state: ResMut<MenuState<S>>,
     mut redraw_writer: MessageWriter<RedrawEvent>,
-    mut selections: ResMut<Selections>,
+    mut refocus_writer: MessageWriter<RefocusEvent>,
     mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut sounds: ResMut<NavigationSounds>,
+    mut activated_writer: MessageWriter<ItemActivatedEvent<S>>,
+    mut unhandled_writer: MessageWriter<UnhandledNavigationEvent<S>>,
+    config: Res<NavigationConfig>,
+    time: Res<Time>,
+    mut captured_input: ResMut<CapturedInput>,
+    context: Res<InputContext>,
+    opened: Res<OpenedMenus>,
 ) where
     S: ScreenTrait + 'static,
 {
-    if let Some(event) = reader.read().next() {
-        if let Some(selection) = menu_state.menu.apply_event(event, &mut selections) {
-            menu_state
+    // Hiding the menu ends editing like a click elsewhere, so that the keyboard
+    // isn't captured by a menu that can't be seen
+    if !menu_state.is_visible() {
+        end_editing(
+            &mut menu_state,
+            &mut captured_input,
+            &mut event_writer,
+            &mut redraw_writer,
+        );
+    }
+    // Hidden menus and menus without the input context leave the events to others
+    if !menu_state.is_visible() || !context.is_active::<S>(&opened) {
+        reader.clear();
+        gamepad_reader.clear();
+        return;
+    }
+    let inputs = menu_state.inputs;
+    let keyboard = reader.read().find(|_| inputs.accepts_keyboard()).copied();
+    let from_gamepad = if keyboard.is_none() {
+        gamepad_reader
+            .read()
+            .find(|e| inputs.accepts_gamepad(e.gamepad))
+            .copied()
+    } else {
+        None
+    };
+    let event = keyboard
+        .or(from_gamepad.map(|e| e.event))
+        .or_else(|| menu_state.queued.take());
+    // Haptics go to the gamepad the event came from
+    let gamepad = from_gamepad.map(|e| e.gamepad);
+    if let Some(event) = &event {
+        // `binding_capture_system` waits for input
+        if menu_state.menu.is_capturing() {
+            return;
+        }
+        if !menu_state.cooled_down(*event, time.elapsed(), config.cooldown) {
+            return;
+        }
+        if menu_state.menu.is_editing() {
+            // Keys are handled by `text_input_system`, gamepads can only stop editing
+            if keyboard.is_none()
+                && matches!(
+                    event,
+                    NavigationEvent::Select | NavigationEvent::Back | NavigationEvent::Close
+                )
+            {
+                // Going back keeps the value of a slider or stepper
+                if *event != NavigationEvent::Select {
+                    menu_state.menu.cancel_number_input();
+                }
+                end_text_input(
+                    &mut menu_state,
+                    &mut captured_input,
+                    &mut event_writer,
+                    &mut redraw_writer,
+                );
+                sounds.0.push(MenuSoundEvent {
+                    kind: if *event == NavigationEvent::Select {
+                        MenuSound::Select
+                    } else {
+                        MenuSound::Back
+                    },
+                    gamepad,
+                });
+            }
+            return;
+        }
+        if *event == NavigationEvent::Select {
+            let state = &mut *menu_state;
+            if let (Some(&screen), Some((menu, index))) = (
+                state.menu.screens().last(),
+                state.menu.focus(&state.selections),
+            ) {
+                activated_writer.write(ItemActivatedEvent {
+                    screen,
+                    menu,
+                    index,
+                });
+            }
+            if state.menu.begin_text_input(&mut state.selections) {
+                captured_input.set::<S>(true, false);
+                redraw_writer.write(RedrawEvent);
+                sounds.0.push(MenuSoundEvent {
+                    kind: MenuSound::Select,
+                    gamepad,
+                });
+                return;
+            }
+            if state.menu.begin_capture(&state.selections) {
+                captured_input.set::<S>(true, true);
+                redraw_writer.write(RedrawEvent);
+                sounds.0.push(MenuSoundEvent {
+                    kind: MenuSound::Select,
+                    gamepad,
+                });
+                return;
+            }
+        }
+        if *event == NavigationEvent::TypeNumber {
+            let state = &mut *menu_state;
+            let kind = if state.menu.begin_number_input(&state.selections) {
+                captured_input.set::<S>(true, false);
+                redraw_writer.write(RedrawEvent);
+                MenuSound::Select
+            } else {
+                if let Some(&screen) = state.menu.screens().last() {
+                    unhandled_writer.write(UnhandledNavigationEvent {
+                        screen,
+                        event: *event,
+                    });
+                }
+                MenuSound::Invalid
+            };
+            sounds.0.push(MenuSoundEvent { kind, gamepad });
+            return;
+        }
+        if menu_state.closes_with(event) {
+            menu_state.close();
+            sounds.0.push(MenuSoundEvent {
+                kind: MenuSound::Back,
+                gamepad,
+            });
+            return;
+        }
+        let state = &mut *menu_state;
+        let screens = state.menu.screens().to_vec();
+        let focus = state.menu.focus(&state.selections);
+        let scroll = state.menu.scroll();
+        let dropdown = state.menu.dropdown();
+        let (selection, changed) = state.menu.apply_event(event, &mut state.selections);
+        // Closing the list of a dropdown sounds like going back, switching tabs like moving
+        let closed = dropdown.is_some() && state.menu.dropdown().is_none() && selection.is_none();
+        let tabbed = matches!(
+            event,
+            NavigationEvent::PreviousTab | NavigationEvent::NextTab
+        );
+        let popped = (screens != state.menu.screens() && !tabbed) || closed;
+        let moved = (screens != state.menu.screens() && tabbed)
+            || scroll != state.menu.scroll()
+            || focus != state.menu.focus(&state.selections)
+            || (dropdown.is_some() && state.menu.dropdown() != dropdown && !closed);
+        let kind = sound_for(event, selection.as_ref(), popped, moved);
+        sounds.0.push(MenuSoundEvent { kind, gamepad });
+        if let (MenuSound::Invalid, false, Some(&screen)) =
+            (kind, changed, state.menu.screens().last())
+        {
+            unhandled_writer.write(UnhandledNavigationEvent {
+                screen,
+                event: *event,
+            });
+        }
+        let handled = selection.is_some_and(|selection| {
+            state
                 .menu
-                .handle_selection(&selection, &mut event_writer);
+                .handle_selection(&selection, &state.selections, &mut event_writer)
+        });
+        // E.g. `Back` on the root screen changes nothing
+        if changed || handled {
+            redraw_writer.write(RedrawEvent);
+        } else if let (Some((menu, previous)), Some((_, current)), Some(root)) =
+            (focus, state.menu.focus(&state.selections), state.root)
+        {
+            // Only the focus moved, if anything
+            if previous != current {
+                refocus_writer.write(RefocusEvent {
+                    root,
+                    menu,
+                    previous,
+                    current,
+                });
+            }
         }
-        redraw_writer.write(RedrawEvent);
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn mouse_system<S>(
     mut menu_state: ResMut<MenuState<S>>,
     mut interaction_query: Query<
         (
-            &Interaction,
+            Entity,
+            Ref<Interaction>,
             &types::ButtonComponent<S>,
             &mut BackgroundColor,
+            Option<&mut ImageNode>,
         ),
         Changed<Interaction>,
     >,
+    backdrops: Query<&Interaction, (Changed<Interaction>, With<MenuBackdrop>)>,
+    children: Query<&Children>,
+    mut texts: Query<(&mut TextFont, &mut TextColor)>,
+    assets: Res<MenuAssets>,
     mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
-    mut selections: ResMut<Selections>,
     mut redraw_writer: MessageWriter<RedrawEvent>,
+    mut refocus_writer: MessageWriter<RefocusEvent>,
+    mut sound_writer: MessageWriter<MenuSoundEvent>,
+    mut activated_writer: MessageWriter<ItemActivatedEvent<S>>,
+    config: Res<NavigationConfig>,
+    time: Res<Time>,
+    mut captured_input: ResMut<CapturedInput>,
 ) where
     S: ScreenTrait + 'static,
 {
+    if !menu_state.is_visible() {
+        return;
+    }
+    if let Some(Ok(Interaction::Pressed)) = menu_state.root.map(|root| backdrops.get(root)) {
+        menu_state.queued = menu_state
+            .menu
+            .stylesheet
+            .backdrop
+            .and_then(|backdrop| backdrop.on_click);
+    }
     for (
+        entity,
         interaction,
         ButtonComponent {
             selection,
             style,
             menu_identifier,
             selected,
+            disabled,
         },
         mut background_color,
+        image,
     ) in &mut interaction_query
     {
+        if *disabled {
+            if *interaction == Interaction::Pressed {
+                sound_writer.write(MenuSoundEvent {
+                    kind: MenuSound::Invalid,
+                    gamepad: None,
+                });
+            }
+            continue;
+        }
+        // Spawned rows start without interaction, only leaving a row ends its hover
+        if *interaction == Interaction::None
+            && !interaction.is_added()
+            && menu_state.hovered.as_ref() == Some(menu_identifier)
+        {
+            menu_state.hovered = None;
+        }
         match *interaction {
-            Interaction::Pressed => {
-                // pop to the chosen selection stack entry
-                menu_state.menu.pop_to_selection(selection);
-
-                // pre-select the correct row
-                selections
-                    .0
-                    .insert(menu_identifier.0.clone(), menu_identifier.1);
-                if let Some(current) = menu_state
-                    .menu
-                    .apply_event(&NavigationEvent::Select, &mut selections)
+            Interaction::Pressed
+                if menu_state.cooled_down(
+                    NavigationEvent::Select,
+                    time.elapsed(),
+                    config.cooldown,
+                ) =>
+            {
+                let kind = press_button(
+                    &mut menu_state,
+                    selection,
+                    menu_identifier,
+                    &mut event_writer,
+                    &mut redraw_writer,
+                    &mut activated_writer,
+                    &mut captured_input,
+                );
+                sound_writer.write(MenuSoundEvent {
+                    kind,
+                    gamepad: None,
+                });
+            }
+            Interaction::Pressed => (),
+            // The focussed item keeps its `selected` style while hovered
+            Interaction::Hovered | Interaction::None if *selected => (),
+            // The rebuilt row is `selected`, so that its `Changed<Interaction>`
+            // doesn't move the focus again. Rows that lost the focus to the keyboard
+            // are rebuilt below the cursor as well, they only show the hover
+            Interaction::Hovered
+                if config.hover_selects && menu_state.hovered.as_ref() != Some(menu_identifier) =>
+            {
+                let state = &mut *menu_state;
+                if state.menu.is_editing()
+                    || state.menu.is_capturing()
+                    || state.menu.dropdown().is_some()
                 {
-                    menu_state
-                        .menu
-                        .handle_selection(&current, &mut event_writer);
-                    redraw_writer.write(RedrawEvent);
+                    continue;
+                }
+                state.hovered = Some(menu_identifier.clone());
+                let (menu, current) = menu_identifier;
+                if let (Some((focussed, previous)), Some(root)) =
+                    (state.menu.focus(&state.selections), state.root)
+                {
+                    if focussed == *menu && previous != *current {
+                        state.selections.set_row(menu.clone(), *current);
+                        refocus_writer.write(RefocusEvent {
+                            root,
+                            menu: menu.clone(),
+                            previous,
+                            current: *current,
+                        });
+                        sound_writer.write(MenuSoundEvent {
+                            kind: MenuSound::Move,
+                            gamepad: None,
+                        });
+                    }
                 }
             }
             Interaction::Hovered => {
-                if !selected {
-                    background_color.0 = style.hover.bg;
+                background_color.0 = style.hover.bg;
+                if let (Some(mut image), Some(images)) = (image, &style.images) {
+                    *image = images.hovered().clone();
                 }
+                let font = (!style.hover_font.is_empty())
+                    .then(|| style.text_font(&assets, Some(&style.hover_font)));
+                set_texts(entity, style.hover.fg, font, &children, &mut texts);
             }
             Interaction::None => {
-                if !selected {
-                    background_color.0 = style.normal.bg;
+                background_color.0 = style.normal.bg;
+                if let (Some(mut image), Some(images)) = (image, &style.images) {
+                    *image = images.normal.clone();
+                }
+                let font = (!style.hover_font.is_empty()).then(|| style.text_font(&assets, None));
+                set_texts(entity, style.normal.fg, font, &children, &mut texts);
+            }
+        }
+    }
+}
+
+/// The cue for `event`, given the `selection` it made and whether it `popped`
+/// a screen or `moved` the focus
+fn sound_for<S>(
+    event: &NavigationEvent,
+    selection: Option<&MenuSelection<S>>,
+    popped: bool,
+    moved: bool,
+) -> MenuSound
+where
+    S: ScreenTrait,
+{
+    match selection {
+        Some(MenuSelection::Back(_)) => MenuSound::Back,
+        // Toggles and steppers also change when selected
+        Some(MenuSelection::Value(_, _)) if *event != NavigationEvent::Select => MenuSound::Move,
+        // E.g. a collapsible that was expanded
+        Some(MenuSelection::None) => MenuSound::Select,
+        Some(_) => MenuSound::Select,
+        None if popped => MenuSound::Back,
+        None if moved => MenuSound::Move,
+        None => MenuSound::Invalid,
+    }
+}
+
+/// Replace the text colors and, if given, the fonts of the texts of a button
+fn set_texts(
+    button: Entity,
+    color: Color,
+    font: Option<TextFont>,
+    children: &Query<&Children>,
+    texts: &mut Query<(&mut TextFont, &mut TextColor)>,
+) {
+    let mut iter = texts.iter_many_mut(children.iter_descendants(button));
+    while let Some((mut text_font, mut text_color)) = iter.fetch_next() {
+        text_color.0 = color;
+        if let Some(font) = &font {
+            *text_font = font.clone();
+        }
+    }
+}
+
+/// Show the screen of a clicked tab and update the colors of hovered ones
+#[allow(clippy::too_many_arguments)]
+pub fn tab_system<S>(
+    mut menu_state: ResMut<MenuState<S>>,
+    mut tabs: Query<(&Interaction, &TabComponent<S>, &Children), Changed<Interaction>>,
+    mut colors: Query<&mut TextColor>,
+    mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut redraw_writer: MessageWriter<RedrawEvent>,
+    mut sound_writer: MessageWriter<MenuSoundEvent>,
+    mut captured_input: ResMut<CapturedInput>,
+    config: Res<NavigationConfig>,
+    time: Res<Time>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if !menu_state.is_visible() {
+        return;
+    }
+    for (interaction, TabComponent { screen }, children) in &mut tabs {
+        let style = &menu_state.menu.stylesheet.tab;
+        let fg = match interaction {
+            Interaction::Hovered => style.hover.fg,
+            _ => style.normal.fg,
+        };
+        for child in children {
+            if let Ok(mut color) = colors.get_mut(*child) {
+                color.0 = fg;
+            }
+        }
+        if *interaction != Interaction::Pressed
+            || !menu_state.cooled_down(NavigationEvent::NextTab, time.elapsed(), config.cooldown)
+        {
+            continue;
+        }
+        end_editing(
+            &mut menu_state,
+            &mut captured_input,
+            &mut event_writer,
+            &mut redraw_writer,
+        );
+        let state = &mut *menu_state;
+        if state.menu.show_tab(*screen, &state.selections) {
+            redraw_writer.write(RedrawEvent);
+            sound_writer.write(MenuSoundEvent {
+                kind: MenuSound::Move,
+                gamepad: None,
+            });
+        }
+    }
+}
+
+/// Like a click on an item, clicks on tabs and breadcrumbs end editing, capturing
+/// and the list of a dropdown
+fn end_editing<S>(
+    menu_state: &mut MenuState<S>,
+    captured_input: &mut CapturedInput,
+    event_writer: &mut MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    redraw_writer: &mut MessageWriter<RedrawEvent>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if menu_state.menu.is_editing() {
+        end_text_input(menu_state, captured_input, event_writer, redraw_writer);
+    }
+    if menu_state.menu.is_capturing() {
+        menu_state
+            .menu
+            .end_capture(None, &mut menu_state.selections);
+        captured_input.set::<S>(false, false);
+    }
+    menu_state.menu.close_dropdown();
+}
+
+/// Go back to the screen of a clicked breadcrumb and update the colors of hovered ones
+#[allow(clippy::too_many_arguments)]
+pub fn breadcrumb_system<S>(
+    mut menu_state: ResMut<MenuState<S>>,
+    mut crumbs: Query<(&Interaction, &BreadcrumbComponent<S>, &Children), Changed<Interaction>>,
+    mut colors: Query<&mut TextColor>,
+    mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut redraw_writer: MessageWriter<RedrawEvent>,
+    mut sound_writer: MessageWriter<MenuSoundEvent>,
+    mut captured_input: ResMut<CapturedInput>,
+    config: Res<NavigationConfig>,
+    time: Res<Time>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if !menu_state.is_visible() {
+        return;
+    }
+    for (interaction, BreadcrumbComponent { depth, screen }, children) in &mut crumbs {
+        let style = &menu_state.menu.stylesheet.breadcrumb;
+        let fg = match interaction {
+            Interaction::Hovered => style.hover.fg,
+            _ => style.normal.fg,
+        };
+        for child in children {
+            if let Ok(mut color) = colors.get_mut(*child) {
+                color.0 = fg;
+            }
+        }
+        // The screens may have changed since the bar was rendered
+        if *interaction != Interaction::Pressed
+            || menu_state.screens().get(*depth) != Some(screen)
+            || !menu_state.cooled_down(NavigationEvent::Back, time.elapsed(), config.cooldown)
+        {
+            continue;
+        }
+        end_editing(
+            &mut menu_state,
+            &mut captured_input,
+            &mut event_writer,
+            &mut redraw_writer,
+        );
+        if menu_state.menu.pop_to_depth(*depth) {
+            redraw_writer.write(RedrawEvent);
+            sound_writer.write(MenuSoundEvent {
+                kind: MenuSound::Back,
+                gamepad: None,
+            });
+        }
+    }
+}
+
+/// Select the item of a button that was clicked or tapped, returns the cue to play
+fn press_button<S>(
+    menu_state: &mut MenuState<S>,
+    selection: &MenuSelection<S>,
+    menu_identifier: &(WidgetId, usize),
+    event_writer: &mut MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    redraw_writer: &mut MessageWriter<RedrawEvent>,
+    activated_writer: &mut MessageWriter<ItemActivatedEvent<S>>,
+    captured_input: &mut CapturedInput,
+) -> MenuSound
+where
+    S: ScreenTrait + 'static,
+{
+    // Clicking anywhere ends editing and cancels capturing a binding
+    if menu_state.menu.is_editing() {
+        end_text_input(menu_state, captured_input, event_writer, redraw_writer);
+    }
+    if menu_state.menu.is_capturing() {
+        menu_state
+            .menu
+            .end_capture(None, &mut menu_state.selections);
+        captured_input.set::<S>(false, false);
+        redraw_writer.write(RedrawEvent);
+    }
+
+    // An open dropdown takes the click: an option of its list is chosen, anything
+    // else closes the list
+    let mut option = None;
+    if let Some((key, _)) = menu_state.menu.dropdown() {
+        match selection {
+            MenuSelection::Value(_, MenuValue::Index(index)) if key == *menu_identifier => {
+                option = Some(*index)
+            }
+            _ => {
+                menu_state.menu.close_dropdown();
+                redraw_writer.write(RedrawEvent);
+                // Clicking the dropdown itself only closes the list
+                if key == *menu_identifier {
+                    return MenuSound::Back;
                 }
             }
         }
     }
+    if let Some(option) = option {
+        menu_state.menu.highlight_option(option);
+    }
+
+    // pop to the chosen selection stack entry, repeatable actions stay where they are
+    let focus = menu_state.menu.focus(&menu_state.selections);
+    let repeatable = option.is_none()
+        && menu_state
+            .menu
+            .is_repeatable(menu_identifier, &menu_state.selections);
+    let popped = option.is_none() && !repeatable && menu_state.menu.pop_to_selection(selection);
+
+    // pre-select the correct row
+    menu_state
+        .selections
+        .set_row(menu_identifier.0.clone(), menu_identifier.1);
+    if let Some(&screen) = menu_state.menu.screens().last() {
+        activated_writer.write(ItemActivatedEvent {
+            screen,
+            menu: menu_identifier.0.clone(),
+            index: menu_identifier.1,
+        });
+    }
+    // `Select` only reaches the topmost screen, a repeatable action may be below it
+    if repeatable {
+        menu_state
+            .menu
+            .handle_selection(selection, &menu_state.selections, event_writer);
+        redraw_writer.write(RedrawEvent);
+        return sound_for(&NavigationEvent::Select, Some(selection), false, false);
+    }
+    if menu_state.menu.begin_text_input(&mut menu_state.selections) {
+        captured_input.set::<S>(true, false);
+        redraw_writer.write(RedrawEvent);
+        return MenuSound::Select;
+    }
+    if menu_state.menu.begin_capture(&menu_state.selections) {
+        captured_input.set::<S>(true, true);
+        redraw_writer.write(RedrawEvent);
+        return MenuSound::Select;
+    }
+    let (current, changed) = menu_state
+        .menu
+        .apply_event(&NavigationEvent::Select, &mut menu_state.selections);
+    let kind = sound_for(&NavigationEvent::Select, current.as_ref(), false, false);
+    let handled = current.is_some_and(|current| {
+        menu_state
+            .menu
+            .handle_selection(&current, &menu_state.selections, event_writer)
+    });
+    let moved = focus != menu_state.menu.focus(&menu_state.selections);
+    if popped || changed || handled || moved {
+        redraw_writer.write(RedrawEvent);
+    }
+    kind
+}
+
+/// Type into the text input that is being edited. `Enter` and `Escape` stop editing.
+/// Sliders and steppers only take numbers, which `Enter` sets if they fit the item
+/// and `Escape` discards
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_system<S>(
+    mut keyboard_input: MessageReader<KeyboardInput>,
+    mut menu_state: ResMut<MenuState<S>>,
+    mut captured_input: ResMut<CapturedInput>,
+    mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut redraw_writer: MessageWriter<RedrawEvent>,
+    mut refocus_writer: MessageWriter<RefocusEvent>,
+    mut sound_writer: MessageWriter<MenuSoundEvent>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if !menu_state.menu.is_editing() || !menu_state.is_visible() {
+        keyboard_input.clear();
+        return;
+    }
+    let state = &mut *menu_state;
+    let number = state.menu.is_typing_number();
+    let mut changed = false;
+    for event in keyboard_input.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let sound = |kind| MenuSoundEvent {
+            kind,
+            gamepad: None,
+        };
+        match &event.logical_key {
+            Key::Enter if number && state.menu.typed_value(&state.selections).is_none() => {
+                sound_writer.write(sound(MenuSound::Invalid));
+                continue;
+            }
+            Key::Escape if number => {
+                state.menu.cancel_number_input();
+                captured_input.set::<S>(false, false);
+                redraw_writer.write(RedrawEvent);
+                sound_writer.write(sound(MenuSound::Back));
+                return;
+            }
+            Key::Character(characters)
+                if number
+                    && !characters
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || c == '.' || c == '-') =>
+            {
+                sound_writer.write(sound(MenuSound::Invalid));
+                continue;
+            }
+            Key::Space if number => {
+                sound_writer.write(sound(MenuSound::Invalid));
+                continue;
+            }
+            Key::Enter | Key::Escape => {
+                end_text_input(
+                    state,
+                    &mut captured_input,
+                    &mut event_writer,
+                    &mut redraw_writer,
+                );
+                return;
+            }
+            Key::Backspace => {
+                state.menu.edit_text(&mut state.selections, |text| {
+                    text.pop();
+                });
+            }
+            Key::Space => state
+                .menu
+                .edit_text(&mut state.selections, |text| text.push(' ')),
+            Key::Character(characters) if !characters.chars().any(char::is_control) => state
+                .menu
+                .edit_text(&mut state.selections, |text| text.push_str(characters)),
+            _ => continue,
+        }
+        changed = true;
+    }
+    // Only the text input has to be rebuilt
+    if let (true, Some(root), Some((menu, index))) = (changed, state.root, state.menu.editing_key())
+    {
+        refocus_writer.write(RefocusEvent {
+            root,
+            menu,
+            previous: index,
+            current: index,
+        });
+    }
+}
+
+/// Jump to the item whose label starts with the typed letters, see
+/// `MenuState::with_type_to_search`
+#[allow(clippy::too_many_arguments)]
+pub fn type_to_search_system<S>(
+    mut keyboard_input: MessageReader<KeyboardInput>,
+    mut menu_state: ResMut<MenuState<S>>,
+    captured_input: Res<CapturedInput>,
+    key_bindings: Res<KeyBindings>,
+    assets: Res<MenuAssets>,
+    time: Res<Time>,
+    mut redraw_writer: MessageWriter<RedrawEvent>,
+    mut refocus_writer: MessageWriter<RefocusEvent>,
+    mut sound_writer: MessageWriter<MenuSoundEvent>,
+    context: Res<InputContext>,
+    opened: Res<OpenedMenus>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let state = &mut *menu_state;
+    // Letters go to the text input that is being edited, in this menu or another one
+    if state.search.is_none()
+        || !state.is_visible()
+        || !context.is_active::<S>(&opened)
+        || !state.inputs.accepts_keyboard()
+        || captured_input.keyboard()
+        || state.menu.is_capturing()
+        || state.menu.dropdown().is_some()
+    {
+        keyboard_input.clear();
+        return;
+    }
+    for event in keyboard_input.read() {
+        if event.state != ButtonState::Pressed
+            || event.repeat
+            || key_bindings.event_for(event.key_code).is_some()
+        {
+            continue;
+        }
+        let Key::Character(characters) = &event.logical_key else {
+            continue;
+        };
+        if !characters.chars().all(char::is_alphanumeric) {
+            continue;
+        }
+        let Some(search) = state.search.as_mut() else {
+            return;
+        };
+        let now = time.elapsed();
+        if now.saturating_sub(search.typed_at) > search.reset_after {
+            search.typed.clear();
+        }
+        search.typed.push_str(characters);
+        search.typed_at = now;
+        let typed = search.typed.clone();
+
+        let focus = state.menu.focus(&state.selections);
+        let scroll = state.menu.scroll();
+        // A single letter moves on to the next match, as does typing it again
+        let mut letters = typed.chars();
+        let first = letters.next().map(String::from).unwrap_or_default();
+        let repeated = letters.all(|letter| first.starts_with(letter));
+        let moved = state.menu.jump_to_prefix(
+            &typed,
+            typed.len() == first.len(),
+            &assets,
+            &mut state.selections,
+        ) || (repeated
+            && state
+                .menu
+                .jump_to_prefix(&first, true, &assets, &mut state.selections));
+        if !moved {
+            continue;
+        }
+        sound_writer.write(MenuSoundEvent {
+            kind: MenuSound::Move,
+            gamepad: None,
+        });
+        if scroll != state.menu.scroll() {
+            redraw_writer.write(RedrawEvent);
+        } else if let (Some((menu, previous)), Some((_, current)), Some(root)) =
+            (focus, state.menu.focus(&state.selections), state.root)
+        {
+            refocus_writer.write(RefocusEvent {
+                root,
+                menu,
+                previous,
+                current,
+            });
+        }
+    }
 }
 
-/// If the `CleanUpUI` `Resource` is available, remove the menu and then the resource.
+/// Capture the next key or gamepad button for a `MenuItem::BindingCapture`.
+/// `Escape` cancels
+pub fn binding_capture_system<S>(
+    mut keyboard_input: MessageReader<KeyboardInput>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut menu_state: ResMut<MenuState<S>>,
+    mut captured_input: ResMut<CapturedInput>,
+    mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut redraw_writer: MessageWriter<RedrawEvent>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if !menu_state.menu.is_capturing() || !menu_state.is_visible() {
+        keyboard_input.clear();
+        return;
+    }
+    let inputs = menu_state.inputs;
+    let key = keyboard_input
+        .read()
+        .filter(|_| inputs.accepts_keyboard())
+        .find(|event| event.state == ButtonState::Pressed && !event.repeat)
+        .map(|event| event.key_code);
+    let captured = match key {
+        Some(KeyCode::Escape) => None,
+        Some(key) => Some(Binding::Key(key)),
+        None => {
+            let Some(button) = gamepads
+                .iter()
+                .filter(|(entity, _)| inputs.accepts_gamepad(*entity))
+                .find_map(|(_, gamepad)| gamepad.get_just_pressed().next().copied())
+            else {
+                return;
+            };
+            Some(Binding::Gamepad(button))
+        }
+    };
+    let state = &mut *menu_state;
+    if let Some(selection) = state.menu.end_capture(captured, &mut state.selections) {
+        state
+            .menu
+            .handle_selection(&selection, &state.selections, &mut event_writer);
+    }
+    captured_input.set::<S>(false, false);
+    redraw_writer.write(RedrawEvent);
+}
+
+/// Stop editing text and hand the text to the action
+fn end_text_input<S>(
+    menu_state: &mut MenuState<S>,
+    captured_input: &mut CapturedInput,
+    event_writer: &mut MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    redraw_writer: &mut MessageWriter<RedrawEvent>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if let Some(selection) = menu_state.menu.commit_text(&mut menu_state.selections) {
+        menu_state
+            .menu
+            .handle_selection(&selection, &menu_state.selections, event_writer);
+    }
+    captured_input.set::<S>(false, false);
+    redraw_writer.write(RedrawEvent);
+}
+
+/// If the `CleanUpUI` `Resource` is available or `MenuState::close` was called,
+/// remove the menu. `cleanup_done_system` removes the resource afterwards.
 /// This is used to close the menu when it is not needed anymore.
+#[allow(clippy::too_many_arguments)]
 pub fn cleanup_system<S>(
     mut commands: Commands,
-    existing: Query<Entity, With<types::QuickMenuComponent>>,
-    children: Query<&Children>,
-    texts: Query<(Entity, &Text)>,
+    mut menu_state: Option<ResMut<MenuState<S>>>,
+    menu_entities: Query<Entity, With<MenuEntity<S>>>,
+    options: Res<MenuOptions>,
+    mut closing: ResMut<ClosingMenus>,
+    mut event_writer: MessageWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut redraw_writer: MessageWriter<RedrawEvent>,
+    mut captured_input: ResMut<CapturedInput>,
 ) where
     S: ScreenTrait + 'static,
 {
-    let mut deleted_children: HashSet<Entity> = HashSet::new();
-
-    info!("In cleanup_system");
-    // Remove all menu elements
-    for item in existing.iter() {
-        for child in children.iter_descendants(item) {
-            deleted_children.insert(child);
-            if let Ok((_, text)) = texts.get(child) {
-                info!("Deleting {}", text.0);
+    let id = std::any::TypeId::of::<S>();
+    // Fade out first, the menu is removed once that is done
+    let duration = options.transition_duration;
+    if let Some(state) = menu_state.as_deref_mut() {
+        if state.root.is_some() && state.visible && !duration.is_zero() {
+            match &state.fade {
+                Some(Fade {
+                    closing: true,
+                    timer,
+                }) if timer.is_finished() => {}
+                Some(Fade { closing: true, .. }) => return,
+                _ => {
+                    state.fade = Some(Fade::new(duration, true));
+                    closing.0.insert(id);
+                    return;
+                }
             }
         }
-        info!("Removing: {:#?}", item);
-        commands.entity(item).despawn();
     }
-    for (entity, text) in texts {
-        if !deleted_children.contains(&entity) {
-            info!("Did not delete {}", text.0);
-        }
+    closing.0.remove(&id);
+
+    // Also removes what is left of a menu whose `MenuState` was removed by hand
+    for entity in &menu_entities {
+        commands.entity(entity).try_despawn();
+    }
+    // Nothing else to clean, e.g. `cleanup` was called without an open menu
+    let Some(mut menu_state) = menu_state else {
+        return;
+    };
+    // A text that is being edited is handed to its action before the screen is left
+    end_editing(
+        &mut menu_state,
+        &mut captured_input,
+        &mut event_writer,
+        &mut redraw_writer,
+    );
+    // Closing the menu leaves its topmost screen
+    if let Some(screen) = menu_state.screens().last().copied() {
+        screen.on_exit(menu_state.state_mut(), &mut event_writer);
+    }
+
+    // Remove all elements of this menu
+    if let Some(item) = menu_state.root {
+        commands.entity(item).try_despawn();
+    }
+    if let Some(highlight) = menu_state.highlight {
+        commands.entity(highlight).try_despawn();
     }
-    // Remove the resource again
-    info!("It let me finish the function");
-    commands.remove_resource::<CleanUpUI>();
     // Remove the state
     commands.remove_resource::<MenuState<S>>();
 }
+
+/// Remove `CleanUpUI` once the `cleanup_system` of every menu is done, so that one menu
+/// doesn't end the cleanup while another one is still fading out
+pub fn cleanup_done_system(mut commands: Commands, closing: Res<ClosingMenus>) {
+    if closing.0.is_empty() {
+        commands.remove_resource::<CleanUpUI>();
+    }
+}
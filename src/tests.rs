@@ -0,0 +1,2304 @@
+//! Tests that drive menus with `MenuState::simulate` or an `App` without a window
+use std::time::Duration;
+
+use bevy::camera::RenderTargetInfo;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::gamepad::{
+    GamepadAxis, GamepadAxisChangedEvent, GamepadButton, GamepadButtonChangedEvent,
+    GamepadConnection, GamepadConnectionEvent, RawGamepadButtonChangedEvent, RawGamepadEvent,
+};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::mouse::MouseMotion;
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::input::ButtonState;
+use bevy::math::{Affine2, Rect, UVec2};
+use bevy::prelude::{
+    App, AssetPlugin, Assets, BackgroundColor, BorderRect, Camera, Camera2d, ChildOf, Children,
+    Color, Entity, FlexDirection, GlobalZIndex, Handle, Image, ImageNode, ImagePlugin, Interaction,
+    KeyCode, LineBreak, Message, MessageWriter, Messages, MinimalPlugins, Node, NodeImageMode,
+    PositionType, Query, Res, TextColor, TextFont, TextLayout, TextureSlicer, Val, Vec2,
+    Visibility, With,
+};
+use bevy::time::TimeUpdateStrategy;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+
+use crate::style::{
+    Backdrop, ControlImages, ControlState, MenuAnchor, MenuLayout, RowStyle, StyleOverride,
+    Stylesheet,
+};
+use crate::types::{
+    BreadcrumbComponent, CapturedInput, CleanUpUI, GamepadActivation, MenuBackdrop,
+    NavigationRepeat, QuickMenuComponent,
+};
+use crate::{
+    ActionTrait, ButtonComponent, GamepadBindings, GamepadLostEvent, GamepadNavigationEvent,
+    InputContext, LastInputDevice, Menu, MenuBounds, MenuInputs, MenuItem, MenuOptions, MenuSound,
+    MenuSoundEvent, MenuState, MenuValue, NavigationConfig, NavigationEvent, QuickMenuPlugin,
+    RedrawEvent, ScreenTrait, StickAxes, UnhandledNavigationEvent, VerticalMenuComponent,
+};
+
+use MenuValue::*;
+use NavigationEvent::*;
+use TestAction::*;
+use TestEvent::*;
+
+#[derive(Debug, Message, PartialEq)]
+enum TestEvent {
+    Action(TestAction),
+    Value(TestAction, MenuValue),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum TestAction {
+    Play,
+}
+
+impl ActionTrait for TestAction {
+    type State = TestState;
+    type Event = TestEvent;
+    fn handle(&self, _state: &mut TestState, event_writer: &mut MessageWriter<TestEvent>) {
+        event_writer.write(Action(*self));
+    }
+
+    fn handle_value(
+        &self,
+        _state: &mut TestState,
+        value: &MenuValue,
+        event_writer: &mut MessageWriter<TestEvent>,
+    ) {
+        event_writer.write(Value(*self, value.clone()));
+    }
+}
+
+/// Each test builds its screens with its own function
+struct TestState {
+    screens: fn(TestScreen, &TestState) -> Menu<TestScreen>,
+    /// The rows of lists
+    names: Vec<&'static str>,
+    /// The value of sliders
+    number: f32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum TestScreen {
+    Root,
+    Sub,
+}
+
+impl ScreenTrait for TestScreen {
+    type Action = TestAction;
+    type State = TestState;
+    fn resolve(&self, state: &TestState) -> Menu<TestScreen> {
+        (state.screens)(*self, state)
+    }
+}
+
+fn menu(screens: fn(TestScreen, &TestState) -> Menu<TestScreen>) -> MenuState<TestScreen> {
+    let state = TestState {
+        screens,
+        names: Vec::new(),
+        number: 0.0,
+    };
+    MenuState::new(state, TestScreen::Root, None)
+}
+
+/// An app without a window that shows `menu`
+fn app(menu: MenuState<TestScreen>) -> App {
+    with_menu(App::new(), menu)
+}
+
+/// Add the plugins to `app` and show `menu`
+fn with_menu(mut app: App, menu: MenuState<TestScreen>) -> App {
+    with_plugins(&mut app, QuickMenuPlugin::new()).insert_resource(menu);
+    app.update();
+    app.update();
+    app
+}
+
+/// Add the plugins the menu needs to `app`, without a window
+fn with_plugins(app: &mut App, plugin: QuickMenuPlugin<TestScreen>) -> &mut App {
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        bevy::input::InputPlugin,
+        ImagePlugin::default(),
+        bevy::text::TextPlugin,
+    ))
+    .add_message::<TestEvent>()
+    .add_plugins(plugin)
+}
+
+/// `read` from each shown button, ordered by their index
+fn buttons<T>(app: &mut App, read: impl Fn(&ButtonComponent<TestScreen>) -> T) -> Vec<T> {
+    let world = app.world_mut();
+    let mut buttons: Vec<_> = world
+        .query::<&ButtonComponent<TestScreen>>()
+        .iter(world)
+        .map(|button| (button.menu_identifier.1, read(button)))
+        .collect();
+    buttons.sort_by_key(|(index, _)| *index);
+    buttons.into_iter().map(|(_, read)| read).collect()
+}
+
+/// The index of the focussed item of the topmost screen
+fn focus<S: ScreenTrait>(menu: &MenuState<S>) -> Option<usize> {
+    menu.current_selection().map(|selection| selection.index)
+}
+
+#[test]
+fn held_key_repeats_at_the_configured_rate() {
+    let config = NavigationConfig::default();
+    let mut repeat = NavigationRepeat::default();
+    // The press itself, then 800ms at 60 fps
+    repeat.hold(Down);
+    let mut events = vec![Down];
+    for _ in 0..50 {
+        events.extend(repeat.tick(Duration::from_millis(16), &config));
+    }
+    // Repeats after 400ms, 520ms, 640ms and 760ms
+    assert_eq!(events, vec![Down; 5]);
+
+    repeat.release();
+    assert!(repeat.tick(Duration::from_secs(1), &config).is_empty());
+
+    let mut menu = menu(|_, _| {
+        Menu::new(
+            "root",
+            (0..8)
+                .map(|row| MenuItem::action(format!("{row}"), Play))
+                .collect(),
+        )
+    });
+    menu.simulate(&events);
+    assert_eq!(focus(&menu), Some(5));
+}
+
+#[test]
+fn slider_steps_within_its_range() {
+    let mut menu = menu(|_, state| {
+        Menu::new(
+            "root",
+            vec![MenuItem::slider(
+                "Volume",
+                Play,
+                state.number,
+                0.0..=1.0,
+                0.25,
+            )],
+        )
+    });
+    menu.state_mut().number = 0.5;
+    let events = menu.simulate(&[Right, Right, Right, Left]);
+    assert_eq!(
+        events,
+        vec![
+            Value(Play, Float(0.75)),
+            Value(Play, Float(1.0)),
+            Value(Play, Float(0.75)),
+        ]
+    );
+    assert_eq!(menu.get_selection(&("root".into(), 0)), Some(Float(0.75)));
+}
+
+#[test]
+fn slider_value_stays_with_its_item_until_the_state_changes() {
+    let mut menu = menu(|_, state| {
+        let mut menu = Menu::new("root", Vec::new()).with_list(
+            &state.names,
+            |name| *name,
+            |name| MenuItem::action(*name, Play),
+        );
+        let slider = MenuItem::slider("Volume", Play, state.number, 0.0..=1.0, 0.25);
+        menu.entries.push(slider);
+        menu
+    });
+    menu.state_mut().names = vec!["a", "b"];
+    menu.simulate(&[Last, Right]);
+    assert_eq!(menu.get_selection(&("root".into(), 2)), Some(Float(0.25)));
+
+    // Another row moves the slider down, its value moves along
+    menu.state_mut().names.insert(0, "c");
+    menu.simulate(&[]);
+    assert_eq!(menu.get_selection(&("root".into(), 3)), Some(Float(0.25)));
+    assert_eq!(
+        menu.selections().value(&"root".into(), "Volume"),
+        Some(&Float(0.25))
+    );
+
+    // A new value from the state wins over the edited one
+    menu.state_mut().number = 0.75;
+    menu.simulate(&[]);
+    assert_eq!(menu.get_selection(&("root".into(), 3)), Some(Float(0.75)));
+    assert_eq!(menu.selections().value(&"root".into(), "Volume"), None);
+}
+
+#[test]
+fn stepper_stops_at_both_ends_unless_it_wraps() {
+    let mut menu = menu(|_, _| {
+        let options = ["Easy", "Normal", "Hard"];
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::stepper("Difficulty", Play, options, 1),
+                MenuItem::stepper("Wrapping", Play, options, 0).with_wrap(),
+            ],
+        )
+    });
+    let events = menu.simulate(&[Right, Right, Left, Left, Left]);
+    assert_eq!(
+        events,
+        vec![
+            Value(Play, Index(2)),
+            Value(Play, Index(1)),
+            Value(Play, Index(0))
+        ]
+    );
+
+    let events = menu.simulate(&[Down, Left, Right]);
+    assert_eq!(events, vec![Value(Play, Index(2)), Value(Play, Index(0))]);
+    assert_eq!(menu.get_selection(&("root".into(), 0)), Some(Index(0)));
+}
+
+#[test]
+fn close_goes_back_and_then_closes_the_menu() {
+    let mut menu = menu(|screen, _| match screen {
+        TestScreen::Root => Menu::new("root", vec![MenuItem::screen("Sub", TestScreen::Sub)]),
+        TestScreen::Sub => Menu::new("sub", vec![MenuItem::action("Play", Play)]),
+    });
+    menu.simulate(&[Select]);
+    assert_eq!(menu.screens(), &[TestScreen::Root, TestScreen::Sub]);
+
+    menu.simulate(&[Close]);
+    assert_eq!(menu.screens(), &[TestScreen::Root]);
+    assert!(!menu.is_closing());
+
+    // Nothing is selected once the menu closes
+    let events = menu.simulate(&[Close, Select]);
+    assert!(menu.is_closing());
+    assert!(events.is_empty());
+    assert_eq!(menu.screens(), &[TestScreen::Root]);
+}
+
+#[test]
+fn style_override_only_changes_its_item() {
+    let mut app = app(menu(|_, _| {
+        let danger = ControlState::new(Color::srgb(1.0, 0.0, 0.0), Color::NONE);
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::action("Play", Play),
+                MenuItem::action("Delete", Play).styled(StyleOverride {
+                    normal: Some(danger),
+                    hover: Some(danger),
+                    ..Default::default()
+                }),
+                MenuItem::action("Quit", Play),
+            ],
+        )
+    }));
+    let default = Stylesheet::default().button;
+    let colors = buttons(&mut app, |button| {
+        (button.style.normal.fg, button.style.hover.fg)
+    });
+    assert_eq!(
+        colors,
+        vec![
+            (default.normal.fg, default.hover.fg),
+            (Color::srgb(1.0, 0.0, 0.0), Color::srgb(1.0, 0.0, 0.0)),
+            (default.normal.fg, default.hover.fg),
+        ]
+    );
+}
+
+#[test]
+fn tap_activates_its_item_once() {
+    let mut app = app(menu(|_, _| {
+        Menu::new("root", vec![MenuItem::action("Play", Play)])
+    }));
+    let world = app.world_mut();
+    let button = world
+        .query_filtered::<Entity, With<ButtonComponent<TestScreen>>>()
+        .single(world)
+        .unwrap();
+    let window = world.spawn_empty().id();
+    let tap = |app: &mut App, phase| {
+        app.world_mut().write_message(TouchInput {
+            phase,
+            position: Vec2::ZERO,
+            window,
+            force: None,
+            id: 0,
+        });
+    };
+    // bevy_ui turns the tap into an `Interaction` of the button below it
+    tap(&mut app, TouchPhase::Started);
+    app.world_mut()
+        .entity_mut(button)
+        .insert(Interaction::Pressed);
+    app.update();
+    tap(&mut app, TouchPhase::Ended);
+    app.update();
+    app.update();
+    let events: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Messages<TestEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(events, vec![Action(Play)]);
+}
+
+#[test]
+fn select_within_the_cooldown_is_ignored() {
+    let mut app = App::new();
+    app.insert_resource(NavigationConfig {
+        cooldown: Duration::from_secs(10),
+        ..Default::default()
+    });
+    let mut app = with_menu(
+        app,
+        menu(|_, _| {
+            Menu::new(
+                "root",
+                vec![
+                    MenuItem::action("Play", Play),
+                    MenuItem::action("Quit", Play),
+                ],
+            )
+        }),
+    );
+    // E.g. a click and a gamepad in the same frame, `Down` has its own cooldown
+    for event in [Select, Select, Down] {
+        app.world_mut().write_message(event);
+    }
+    for _ in 0..3 {
+        app.update();
+    }
+    let events: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Messages<TestEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(events, vec![Action(Play)]);
+    assert_eq!(
+        focus(app.world().resource::<MenuState<TestScreen>>()),
+        Some(1)
+    );
+}
+
+/// The screens of one player in split-screen, see `players_navigate_their_own_menus`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum PlayerScreen<const PLAYER: usize> {
+    Root,
+}
+
+impl<const PLAYER: usize> ScreenTrait for PlayerScreen<PLAYER> {
+    type Action = TestAction;
+    type State = TestState;
+    fn resolve(&self, _state: &TestState) -> Menu<Self> {
+        let entries = vec![
+            MenuItem::action("Play", Play),
+            MenuItem::action("Quit", Play),
+        ];
+        Menu::new(format!("player {PLAYER}"), entries)
+    }
+}
+
+#[test]
+fn players_navigate_their_own_menus() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        bevy::input::InputPlugin,
+        ImagePlugin::default(),
+        bevy::text::TextPlugin,
+    ))
+    .add_message::<TestEvent>()
+    .add_plugins(QuickMenuPlugin::<PlayerScreen<0>>::new())
+    .add_plugins(QuickMenuPlugin::<PlayerScreen<1>>::new());
+    let player = |inputs| {
+        let state = TestState {
+            screens: |_, _| unreachable!(),
+            names: Vec::new(),
+            number: 0.0,
+        };
+        (state, inputs)
+    };
+    let (state, inputs) = player(MenuInputs::Keyboard);
+    app.insert_resource(MenuState::new(state, PlayerScreen::<0>::Root, None).with_inputs(inputs));
+    let (state, inputs) = player(MenuInputs::Gamepad(Entity::PLACEHOLDER));
+    app.insert_resource(MenuState::new(state, PlayerScreen::<1>::Root, None).with_inputs(inputs));
+    app.update();
+    app.world_mut().write_message(Down);
+    app.update();
+    let world = app.world();
+    assert_eq!(
+        focus(world.resource::<MenuState<PlayerScreen<0>>>()),
+        Some(1)
+    );
+    assert_eq!(
+        focus(world.resource::<MenuState<PlayerScreen<1>>>()),
+        Some(0)
+    );
+}
+
+#[test]
+fn hiding_or_closing_ends_text_input() {
+    let mut app = app(menu(|_, _| {
+        Menu::new("root", vec![MenuItem::text_input("Name", Play, "Ann")])
+    }));
+    let start_editing = |app: &mut App| {
+        app.world_mut().write_message(Select);
+        app.update();
+        assert!(app.world().resource::<CapturedInput>().keyboard());
+    };
+    let ended = |app: &mut App| {
+        let captured = app.world().resource::<CapturedInput>().keyboard();
+        let events: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<TestEvent>>()
+            .drain()
+            .collect();
+        assert!(!captured);
+        assert_eq!(events, vec![Value(Play, Text("Ann".into()))]);
+    };
+
+    start_editing(&mut app);
+    let mut menu = app.world_mut().resource_mut::<MenuState<TestScreen>>();
+    menu.set_visible(false);
+    app.update();
+    assert!(!app
+        .world()
+        .resource::<MenuState<TestScreen>>()
+        .menu
+        .is_editing());
+    ended(&mut app);
+
+    app.world_mut()
+        .resource_mut::<MenuState<TestScreen>>()
+        .set_visible(true);
+    start_editing(&mut app);
+    app.world_mut()
+        .resource_mut::<MenuState<TestScreen>>()
+        .close();
+    app.update();
+    assert!(!app.world().contains_resource::<MenuState<TestScreen>>());
+    ended(&mut app);
+}
+
+#[test]
+fn confirm_dialog_only_performs_the_action_for_yes() {
+    let mut menu = menu(|_, _| {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::action("Reset", Play),
+                MenuItem::action("Delete", Play).with_confirm("Really delete?"),
+            ],
+        )
+    });
+    let dialog = |menu: &MenuState<TestScreen>| {
+        menu.current_selection()
+            .map(|selection| selection.menu.as_str().to_owned())
+    };
+    // "No" and `Back` close the dialog without the action
+    assert!(menu.simulate(&[Down, Select]).is_empty());
+    assert_eq!(dialog(&menu).as_deref(), Some("root confirm 1"));
+    assert!(menu.simulate(&[Down, Select]).is_empty());
+    assert_eq!(dialog(&menu).as_deref(), Some("root"));
+    assert!(menu.simulate(&[Select, Back]).is_empty());
+    assert_eq!(focus(&menu), Some(1));
+
+    // The dialog is closed before the menu
+    menu.simulate(&[Select, Close]);
+    assert!(!menu.is_closing());
+    let events = menu.simulate(&[Select, Up, Select]);
+    assert_eq!(events, vec![Action(Play)]);
+    assert_eq!(dialog(&menu).as_deref(), Some("root"));
+}
+
+#[test]
+fn description_is_found_below_styles_and_collapsibles() {
+    let mut menu = menu(|_, _| {
+        let described = |label| MenuItem::action(label, Play).with_description("Help");
+        Menu::new(
+            "root",
+            vec![
+                described("Play").styled(StyleOverride::default()),
+                MenuItem::collapsible("Video", vec![described("VSync")]),
+                MenuItem::action("Quit", Play),
+            ],
+        )
+    });
+    let description = |menu: &MenuState<TestScreen>| {
+        menu.current_selection()
+            .and_then(|selection| selection.description)
+            .map(|text| text.debug_text())
+    };
+    assert_eq!(description(&menu).as_deref(), Some("Help"));
+    menu.simulate(&[Down, Select, Down]);
+    assert_eq!(description(&menu).as_deref(), Some("Help"));
+    menu.simulate(&[Down]);
+    assert_eq!(description(&menu), None);
+}
+
+#[test]
+fn long_label_wraps_and_stays_navigable() {
+    const LONG: &str = "A very long label that does not fit into a single line of the menu";
+    let mut menu = menu(|_, _| {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::action("Play", Play),
+                MenuItem::action(LONG, Play),
+                MenuItem::action("Quit", Play),
+            ],
+        )
+    });
+    menu.set_stylesheet(Stylesheet::default().with_wrap_width(Val::Px(120.0)));
+    let mut app = app(menu);
+    let world = app.world_mut();
+    let (node, layout) = world
+        .query::<(&bevy::prelude::Text, &Node, &TextLayout)>()
+        .iter(world)
+        .find(|(text, _, _)| text.0 == LONG)
+        .map(|(_, node, layout)| (node.max_width, layout.linebreak))
+        .unwrap();
+    assert_eq!(node, Val::Px(120.0));
+    assert_eq!(layout, LineBreak::WordBoundary);
+
+    // The wrapped row is focussed like any other
+    for event in [Down, Down, Up] {
+        app.world_mut().write_message(event);
+        app.update();
+    }
+    assert_eq!(
+        buttons(&mut app, |button| button.selected),
+        [false, true, false]
+    );
+}
+
+#[test]
+fn headlines_are_skipped_and_not_counted() {
+    let mut menu = menu(|_, _| {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::headline("Settings"),
+                MenuItem::headline("Audio"),
+                MenuItem::action("Volume", Play),
+                MenuItem::headline("Video"),
+                MenuItem::headline("Display"),
+                MenuItem::action("VSync", Play),
+            ],
+        )
+    });
+    let label = |menu: &MenuState<TestScreen>| {
+        menu.current_selection()
+            .and_then(|selection| selection.label)
+            .map(|label| label.debug_text())
+    };
+    // The leading headlines start the focus on the first option
+    assert_eq!(focus(&menu), Some(0));
+    assert_eq!(label(&menu).as_deref(), Some("Volume"));
+
+    menu.simulate(&[Down]);
+    assert_eq!(focus(&menu), Some(1));
+    assert_eq!(label(&menu).as_deref(), Some("VSync"));
+    menu.simulate(&[Up, Up]);
+    assert_eq!(label(&menu).as_deref(), Some("Volume"));
+    assert_eq!(menu.selections().row(&"root".into()), Some(0));
+}
+
+/// The second menu of `one_sound_per_input_for_menus_that_share_it`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct HudScreen;
+
+impl ScreenTrait for HudScreen {
+    type Action = TestAction;
+    type State = TestState;
+    fn resolve(&self, _state: &TestState) -> Menu<Self> {
+        Menu::new("hud", vec![MenuItem::action("Map", Play)])
+    }
+}
+
+#[test]
+fn one_sound_per_input_for_menus_that_share_it() {
+    let mut app = app(menu(|_, _| {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::action("Play", Play),
+                MenuItem::action("Quit", Play),
+            ],
+        )
+    }));
+    app.add_plugins(QuickMenuPlugin::<HudScreen>::new());
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    app.insert_resource(MenuState::new(state, HudScreen, None));
+    app.update();
+    app.world_mut()
+        .resource_mut::<Messages<MenuSoundEvent>>()
+        .clear();
+
+    // Only the main menu can move, the HUD has a single item
+    let mut sounds = |event| {
+        app.world_mut().write_message(event);
+        app.update();
+        app.world_mut()
+            .resource_mut::<Messages<MenuSoundEvent>>()
+            .drain()
+            .map(|sound| sound.kind)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(sounds(Down), [MenuSound::Move]);
+    assert_eq!(sounds(Down), [MenuSound::Invalid]);
+}
+
+#[test]
+fn first_and_last_skip_disabled_items_and_headlines() {
+    let mut menu = menu(|_, _| {
+        let mut entries = vec![
+            MenuItem::headline("Saves"),
+            MenuItem::action("Autosave", Play).disabled(true),
+        ];
+        entries.extend((1..=6).map(|slot| MenuItem::action(format!("Slot {slot}"), Play)));
+        entries.push(MenuItem::action("Cloud", Play).disabled(true));
+        Menu::new("root", entries)
+    });
+    menu.simulate(&[Down, Down, Down]);
+    assert_eq!(focus(&menu), Some(4));
+    menu.simulate(&[First]);
+    assert_eq!(focus(&menu), Some(1));
+    menu.simulate(&[Last]);
+    assert_eq!(focus(&menu), Some(6));
+}
+
+#[test]
+fn pages_move_by_the_visible_rows() {
+    let mut menu = menu(|_, _| {
+        let entries = (0..50)
+            .map(|row| MenuItem::action(format!("{row}"), Play))
+            .collect();
+        Menu::new("root", entries).with_max_rows(10)
+    });
+    menu.simulate(&[PageDown]);
+    assert_eq!(focus(&menu), Some(10));
+    menu.simulate(&[PageDown, PageDown, PageDown, PageDown, PageDown]);
+    assert_eq!(focus(&menu), Some(49));
+    // The focus stays within the shown rows
+    let first = menu.menu.scroll();
+    assert!((first..first + 10).contains(&49));
+    menu.simulate(&[PageUp, PageUp]);
+    assert_eq!(focus(&menu), Some(29));
+    let first = menu.menu.scroll();
+    assert!((first..first + 10).contains(&29));
+    menu.simulate(&[PageUp, PageUp, PageUp, PageUp]);
+    assert_eq!(focus(&menu), Some(0));
+}
+
+#[test]
+fn selecting_a_radio_option_moves_the_active_marker() {
+    let mut menu = menu(|_, _| {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::radio("Low", Play, "quality", 0, true),
+                MenuItem::radio("High", Play, "quality", 1, false),
+                MenuItem::radio("Windowed", Play, "mode", 0, true),
+            ],
+        )
+    });
+    let active = |menu: &MenuState<TestScreen>| {
+        (0..3)
+            .map(|index| menu.get_selection(&("root".into(), index)))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        active(&menu),
+        [Some(Bool(true)), Some(Bool(false)), Some(Bool(true))]
+    );
+
+    let events = menu.simulate(&[Down, Select]);
+    assert_eq!(events, vec![Value(Play, Radio("quality".into(), 1))]);
+    // Other groups keep their option
+    assert_eq!(
+        active(&menu),
+        [Some(Bool(false)), Some(Bool(true)), Some(Bool(true))]
+    );
+}
+
+#[test]
+fn quick_menu_declares_nested_screens_in_place() {
+    let mut menu = menu(|screen, _| {
+        crate::quick_menu! {
+            match screen {
+                TestScreen::Root => "root" [
+                    headline("Test"),
+                    screen("Sub", TestScreen::Sub) => "sub" [
+                        action("Play", Play),
+                        back("Back"),
+                    ].wrapping(),
+                ],
+            }
+        }
+    });
+    assert!(menu.simulate(&[Select]).is_empty());
+    assert_eq!(menu.screens(), &[TestScreen::Root, TestScreen::Sub]);
+    // `wrapping` belongs to the nested menu, so Up twice goes round to Play
+    assert_eq!(menu.simulate(&[Up, Up, Select]), vec![Action(Play)]);
+    menu.simulate(&[Down, Select]);
+    assert_eq!(menu.screens(), &[TestScreen::Root]);
+}
+
+#[test]
+fn keyboard_moves_on_from_the_hovered_item() {
+    let mut app = App::new();
+    app.insert_resource(NavigationConfig {
+        hover_selects: true,
+        ..Default::default()
+    });
+    let mut app = with_menu(
+        app,
+        menu(|_, _| {
+            Menu::new(
+                "root",
+                (0..5)
+                    .map(|index| MenuItem::action(format!("Item {index}"), Play))
+                    .collect(),
+            )
+        }),
+    );
+    // What bevy_ui does for the row below the cursor, also after it was rebuilt
+    let hover = |app: &mut App, index: usize| {
+        let world = app.world_mut();
+        let row = world
+            .query::<(Entity, &ButtonComponent<TestScreen>)>()
+            .iter(world)
+            .find(|(_, button)| button.menu_identifier.1 == index)
+            .map(|(entity, _)| entity)
+            .unwrap();
+        world.entity_mut(row).insert(Interaction::Hovered);
+        app.update();
+        app.update();
+    };
+    let focussed = |app: &App| focus(app.world().resource::<MenuState<TestScreen>>());
+
+    hover(&mut app, 3);
+    assert_eq!(focussed(&app), Some(3));
+    app.world_mut().write_message(Down);
+    app.update();
+    app.update();
+    assert_eq!(focussed(&app), Some(4));
+    // The rebuilt row 3 is still below the cursor
+    hover(&mut app, 3);
+    assert_eq!(focussed(&app), Some(4));
+    app.world_mut().write_message(Up);
+    app.update();
+    assert_eq!(focussed(&app), Some(3));
+}
+
+#[test]
+fn cleanup_removes_the_backdrop_with_the_menu() {
+    let mut menu = menu(|_, _| Menu::new("root", vec![MenuItem::action("Play", Play)]));
+    menu.set_stylesheet(Stylesheet::default().with_backdrop(Backdrop::default()));
+    let mut app = app(menu);
+    let count = |app: &mut App| {
+        let world = app.world_mut();
+        let backdrops = world
+            .query_filtered::<(), With<MenuBackdrop>>()
+            .iter(world)
+            .count();
+        let nodes = world
+            .query_filtered::<(), With<QuickMenuComponent>>()
+            .iter(world)
+            .count();
+        (backdrops, nodes)
+    };
+    let (backdrops, nodes) = count(&mut app);
+    assert_eq!(backdrops, 1);
+    assert!(nodes > 1);
+
+    crate::cleanup(&mut app.world_mut().commands());
+    app.world_mut().flush();
+    app.update();
+    app.update();
+    assert_eq!(count(&mut app), (0, 0));
+}
+
+#[test]
+fn cleanup_waits_for_every_menu_to_fade_out() {
+    let mut app = app(menu(|_, _| {
+        Menu::new("root", vec![MenuItem::action("Play", Play)])
+    }));
+    // A hidden menu doesn't fade, so it is removed right away
+    let step = Duration::from_millis(100);
+    app.add_plugins(QuickMenuPlugin::<HudScreen>::with_options(MenuOptions {
+        transition_duration: step * 5,
+        ..Default::default()
+    }))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(step));
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    let mut hud = MenuState::new(state, HudScreen, None);
+    hud.set_visible(false);
+    app.insert_resource(hud);
+    for _ in 0..10 {
+        app.update();
+    }
+
+    crate::cleanup(&mut app.world_mut().commands());
+    app.world_mut().flush();
+    app.update();
+    app.update();
+    let world = app.world();
+    assert!(!world.contains_resource::<MenuState<HudScreen>>());
+    assert!(world.contains_resource::<MenuState<TestScreen>>());
+    assert!(world.contains_resource::<CleanUpUI>());
+
+    for _ in 0..10 {
+        app.update();
+    }
+    let world = app.world_mut();
+    assert!(!world.contains_resource::<MenuState<TestScreen>>());
+    assert!(!world.contains_resource::<CleanUpUI>());
+    let nodes = world
+        .query_filtered::<(), With<QuickMenuComponent>>()
+        .iter(world)
+        .count();
+    assert_eq!(nodes, 0);
+
+    // Without any menu there is nothing to remove
+    crate::cleanup(&mut world.commands());
+    world.flush();
+    app.update();
+    assert!(!app.world().contains_resource::<CleanUpUI>());
+}
+
+/// A menu with an action for each of the `names` of the state, up to 8 of them
+fn list(count: usize) -> MenuState<TestScreen> {
+    let mut menu = menu(|_, state| {
+        Menu::new(
+            "root",
+            state
+                .names
+                .iter()
+                .map(|name| MenuItem::action(*name, Play))
+                .collect(),
+        )
+    });
+    menu.state_mut().names = [
+        "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight",
+    ][..count]
+        .to_vec();
+    menu
+}
+
+#[test]
+fn noisy_stick_at_the_threshold_moves_once() {
+    let mut app = app(list(5));
+    let gamepad = app.world_mut().spawn(GamepadActivation::new()).id();
+    let mut stick = |values: &[f32]| {
+        for value in values {
+            app.world_mut().write_message(GamepadAxisChangedEvent::new(
+                gamepad,
+                GamepadAxis::LeftStickY,
+                *value,
+            ));
+            app.update();
+        }
+        focus(app.world().resource::<MenuState<TestScreen>>())
+    };
+    // Dipping below the activate threshold but not the release threshold doesn't re-arm
+    assert_eq!(stick(&[-0.11, -0.09, -0.11, -0.08, -0.12]), Some(1));
+    assert_eq!(stick(&[-0.02, -0.11]), Some(2));
+}
+
+#[test]
+fn disabled_stick_axis_doesnt_navigate() {
+    let mut app = App::new();
+    app.insert_resource(NavigationConfig {
+        stick_axes: StickAxes {
+            right_y: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let mut app = with_menu(app, list(3));
+    let gamepad = app.world_mut().spawn(GamepadActivation::new()).id();
+    let mut stick = |axis, values: &[f32]| {
+        let mut events = Vec::new();
+        for value in values {
+            app.world_mut()
+                .write_message(GamepadAxisChangedEvent::new(gamepad, axis, *value));
+            app.update();
+            events.extend(
+                app.world_mut()
+                    .resource_mut::<Messages<GamepadNavigationEvent>>()
+                    .drain()
+                    .map(|navigation| navigation.event),
+            );
+        }
+        (
+            events,
+            focus(app.world().resource::<MenuState<TestScreen>>()),
+        )
+    };
+    assert_eq!(
+        stick(GamepadAxis::RightStickY, &[-0.8, 0.0]),
+        (vec![], Some(0))
+    );
+    assert_eq!(
+        stick(GamepadAxis::LeftStickY, &[-0.8, 0.0]),
+        (vec![Down], Some(1))
+    );
+}
+
+#[test]
+fn held_dpad_repeats_until_released() {
+    let config = NavigationConfig::default();
+    let mut activation = GamepadActivation::new();
+    let hold = |activation: &mut GamepadActivation, frames| {
+        (0..frames)
+            .flat_map(|_| activation.tick(Duration::from_millis(16), &config))
+            .collect::<Vec<_>>()
+    };
+    activation.hold_button(GamepadButton::DPadDown, Down);
+    // 800ms: the initial delay of 400ms, then a repeat every 120ms
+    assert_eq!(hold(&mut activation, 50), [Down, Down, Down, Down]);
+    // Still pressed
+    activation.release_button(|button| button != GamepadButton::DPadDown);
+    assert_eq!(hold(&mut activation, 10), [Down]);
+    activation.release_button(|button| button == GamepadButton::DPadDown);
+    assert!(hold(&mut activation, 50).is_empty());
+}
+
+#[test]
+fn analog_triggers_page_through_the_menu() {
+    let mut app = App::new();
+    app.insert_resource(NavigationConfig {
+        analog_triggers: true,
+        ..Default::default()
+    });
+    let mut app = with_menu(
+        app,
+        menu(|_, _| {
+            let entries = (0..8)
+                .map(|row| MenuItem::action(format!("{row}"), Play))
+                .collect();
+            Menu::new("root", entries).with_max_rows(3)
+        }),
+    );
+    let gamepad = app.world_mut().spawn(GamepadActivation::new()).id();
+    let mut trigger = |button, values: &[f32]| {
+        for value in values {
+            let state = if *value > 0.0 {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Released
+            };
+            app.world_mut()
+                .write_message(GamepadButtonChangedEvent::new(
+                    gamepad, button, state, *value,
+                ));
+            app.update();
+        }
+        focus(app.world().resource::<MenuState<TestScreen>>())
+    };
+    // A trigger pages once, until it is let go past the release threshold
+    let right = GamepadButton::RightTrigger2;
+    assert_eq!(trigger(right, &[0.3, 0.6, 0.4, 0.7]), Some(3));
+    assert_eq!(trigger(right, &[0.2, 0.6]), Some(6));
+    assert_eq!(trigger(GamepadButton::LeftTrigger2, &[0.9]), Some(3));
+}
+
+#[test]
+fn back_on_the_root_screen_doesnt_redraw() {
+    let mut app = app(menu(|screen, _| match screen {
+        TestScreen::Root => Menu::new("root", vec![MenuItem::screen("Sub", TestScreen::Sub)]),
+        TestScreen::Sub => Menu::new("sub", vec![MenuItem::back("Back")]),
+    }));
+    let mut redraws = |event| {
+        app.world_mut()
+            .resource_mut::<Messages<RedrawEvent>>()
+            .clear();
+        app.world_mut().write_message(event);
+        app.update();
+        app.world_mut()
+            .resource_mut::<Messages<RedrawEvent>>()
+            .drain()
+            .count()
+    };
+    assert_eq!(redraws(Back), 0);
+    assert_eq!(redraws(Select), 1);
+    assert_eq!(redraws(Back), 1);
+}
+
+#[test]
+fn initial_focus_applies_until_the_focus_moved() {
+    let mut menu = menu(|screen, _| match screen {
+        TestScreen::Root => Menu::new("root", vec![MenuItem::screen("Sub", TestScreen::Sub)]),
+        TestScreen::Sub => Menu::new(
+            "sub",
+            vec![
+                MenuItem::headline("Game"),
+                MenuItem::action("New", Play),
+                MenuItem::action("Resume", Play),
+                MenuItem::back("Back"),
+            ],
+        )
+        .with_initial_focus(1),
+    });
+    menu.simulate(&[Select]);
+    assert_eq!(focus(&menu), Some(1));
+    menu.simulate(&[Down, Select]);
+    assert_eq!(menu.screens(), &[TestScreen::Root]);
+    // The focus the user chose is kept over the initial one
+    menu.simulate(&[Select]);
+    assert_eq!(focus(&menu), Some(2));
+}
+
+#[test]
+fn right_to_left_reverses_the_row_and_its_navigation() {
+    let row = |sheet: Stylesheet| {
+        let mut menu = menu(|_, _| {
+            Menu::new(
+                "root",
+                vec![MenuItem::action("One", Play), MenuItem::action("Two", Play)],
+            )
+            .horizontal()
+        });
+        menu.set_stylesheet(sheet);
+        let mut app = app(menu);
+        let world = app.world_mut();
+        let direction = world
+            .query_filtered::<&Node, With<VerticalMenuComponent>>()
+            .single(world)
+            .unwrap()
+            .flex_direction;
+        // "Two" is on the left in right-to-left layouts
+        app.world_mut().write_message(Left);
+        app.update();
+        let focus = focus(app.world().resource::<MenuState<TestScreen>>());
+        (direction, focus)
+    };
+    assert_eq!(row(Stylesheet::default()), (FlexDirection::Row, Some(0)));
+    assert_eq!(
+        row(Stylesheet::default().with_rtl()),
+        (FlexDirection::RowReverse, Some(1))
+    );
+}
+
+#[test]
+fn set_selection_changes_the_shown_stepper_option() {
+    let mut app = app(menu(|_, _| {
+        Menu::new(
+            "root",
+            vec![MenuItem::stepper(
+                "Quality",
+                Play,
+                ["Low", "Medium", "High"],
+                0,
+            )],
+        )
+    }));
+    let texts = |app: &mut App| {
+        let world = app.world_mut();
+        world
+            .query::<&bevy::prelude::Text>()
+            .iter(world)
+            .map(|text| text.0.clone())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    assert!(texts(&mut app).contains("Low"));
+
+    let mut menu = app.world_mut().resource_mut::<MenuState<TestScreen>>();
+    let key = ("root".into(), 0);
+    assert!(!menu.set_selection(key.clone(), Bool(true)));
+    assert!(!menu.set_selection(("root".into(), 1), Index(1)));
+    // Past the last option
+    assert!(menu.set_selection(key.clone(), Index(5)));
+    assert_eq!(menu.get_selection(&key), Some(Index(2)));
+    app.update();
+    let shown = texts(&mut app);
+    assert!(shown.contains("High") && !shown.contains("Low"));
+}
+
+#[test]
+fn last_input_device_follows_gamepad_and_mouse() {
+    fn after(app: &mut App, message: impl Message) -> LastInputDevice {
+        app.world_mut().write_message(message);
+        app.update();
+        *app.world().resource::<LastInputDevice>()
+    }
+    let mut app = app(list(2));
+    let gamepad = app.world_mut().spawn(GamepadActivation::new()).id();
+    let press =
+        GamepadButtonChangedEvent::new(gamepad, GamepadButton::South, ButtonState::Pressed, 1.0);
+    assert_eq!(after(&mut app, press), LastInputDevice::Gamepad);
+    let motion = MouseMotion {
+        delta: Vec2::new(3.0, 1.0),
+    };
+    assert_eq!(after(&mut app, motion), LastInputDevice::Mouse);
+    // A stick resting slightly off center isn't a use of the gamepad
+    let drift = GamepadAxisChangedEvent::new(gamepad, GamepadAxis::LeftStickX, 0.02);
+    assert_eq!(after(&mut app, drift), LastInputDevice::Mouse);
+}
+
+#[test]
+fn grid_moves_within_rows_and_columns() {
+    // 0 1 2
+    // 3 4 5
+    // 6 7
+    let mut menu = menu(|_, _| {
+        let entries = (0..8)
+            .map(|cell| MenuItem::action(format!("{cell}"), Play))
+            .collect();
+        Menu::new("root", entries).with_columns(3)
+    });
+    let mut steps = |events: &[NavigationEvent]| {
+        events
+            .iter()
+            .map(|event| {
+                menu.simulate(&[*event]);
+                focus(&menu).unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    // The focus stops at the edges
+    assert_eq!(steps(&[Left, Up, Right, Right, Right]), [0, 0, 1, 2, 2]);
+    assert_eq!(steps(&[Down, Down, Left, Down, Down]), [5, 5, 4, 7, 7]);
+    assert_eq!(steps(&[Right, Up, Up, Up]), [7, 4, 1, 1]);
+}
+
+#[test]
+fn doubling_the_scale_doubles_the_font_size() {
+    let mut app = app(list(2));
+    let sizes = |app: &mut App| {
+        let world = app.world_mut();
+        let mut sizes: Vec<_> = world
+            .query_filtered::<&TextFont, With<bevy::prelude::Text>>()
+            .iter(world)
+            .map(|font| font.font_size)
+            .collect();
+        sizes.sort_by(f32::total_cmp);
+        sizes
+    };
+    let normal = sizes(&mut app);
+    assert!(!normal.is_empty());
+    app.world_mut()
+        .resource_mut::<MenuState<TestScreen>>()
+        .set_scale(2.0);
+    app.update();
+    let doubled: Vec<_> = normal.iter().map(|size| size * 2.0).collect();
+    assert_eq!(sizes(&mut app), doubled);
+}
+
+/// A chain of screens that each open the next one, see `root_returns_from_deep_screens`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct LevelScreen(usize);
+
+impl ScreenTrait for LevelScreen {
+    type Action = TestAction;
+    type State = TestState;
+    fn resolve(&self, _state: &TestState) -> Menu<Self> {
+        Menu::new(
+            format!("level {}", self.0),
+            vec![
+                MenuItem::action("Play", Play),
+                MenuItem::screen("Deeper", LevelScreen(self.0 + 1)),
+            ],
+        )
+    }
+}
+
+#[test]
+fn root_returns_from_deep_screens() {
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    let mut menu = MenuState::new(state, LevelScreen(0), None);
+    menu.simulate(&[Down, Select, Down, Select, Down, Select]);
+    assert_eq!(menu.screens().len(), 4);
+    menu.simulate(&[Root]);
+    assert_eq!(menu.screens(), &[LevelScreen(0)]);
+    // The screens in between keep their focus
+    menu.simulate(&[Select]);
+    assert_eq!(menu.screens(), &[LevelScreen(0), LevelScreen(1)]);
+    assert_eq!(focus(&menu), Some(1));
+}
+
+#[test]
+fn repeatable_action_keeps_the_focus_and_screen() {
+    let screens = |screen, _: &TestState| match screen {
+        TestScreen::Root => Menu::new(
+            "root",
+            vec![
+                MenuItem::action("+1 Gold", Play).repeatable(true),
+                MenuItem::screen("Sub", TestScreen::Sub),
+            ],
+        ),
+        TestScreen::Sub => Menu::new("sub", vec![MenuItem::back("Back")]),
+    };
+    let mut state = menu(screens);
+    let events = state.simulate(&[Select; 10]);
+    assert_eq!(events.len(), 10);
+    assert!(events.iter().all(|event| *event == Action(Play)));
+    assert_eq!(focus(&state), Some(0));
+    assert_eq!(state.screens(), &[TestScreen::Root]);
+
+    // Clicking it on a screen below the topmost one doesn't go back either
+    let mut app = app(menu(screens));
+    for event in [Down, Select] {
+        app.world_mut().write_message(event);
+        app.update();
+    }
+    app.update();
+    let world = app.world_mut();
+    let button = world
+        .query::<(Entity, &ButtonComponent<TestScreen>)>()
+        .iter(world)
+        .find(|(_, button)| button.menu_identifier == ("root".into(), 0))
+        .map(|(entity, _)| entity)
+        .unwrap();
+    world.entity_mut(button).insert(Interaction::Pressed);
+    world.resource_mut::<Messages<TestEvent>>().clear();
+    app.update();
+    let events: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Messages<TestEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(events, vec![Action(Play)]);
+    let menu = app.world().resource::<MenuState<TestScreen>>();
+    assert_eq!(menu.screens(), &[TestScreen::Root, TestScreen::Sub]);
+}
+
+#[test]
+fn screens_follow_input_and_programmatic_navigation() {
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    let mut menu = MenuState::new(state, LevelScreen(0), None);
+    menu.push_screen(LevelScreen(1));
+    menu.simulate(&[Down, Select]);
+    assert_eq!(
+        menu.screens(),
+        &[LevelScreen(0), LevelScreen(1), LevelScreen(2)]
+    );
+    menu.navigate_to(LevelScreen(1));
+    assert_eq!(menu.screens(), &[LevelScreen(0), LevelScreen(1)]);
+    assert_eq!(menu.pop_screen(), Some(LevelScreen(1)));
+    assert_eq!(menu.screens(), &[LevelScreen(0)]);
+}
+
+#[test]
+fn clicking_a_breadcrumb_goes_back_to_its_screen() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        bevy::input::InputPlugin,
+        ImagePlugin::default(),
+        bevy::text::TextPlugin,
+    ))
+    .add_message::<TestEvent>()
+    .add_plugins(QuickMenuPlugin::<LevelScreen>::new());
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    let menu = MenuState::new(state, LevelScreen(0), None)
+        .with_breadcrumbs(|screen| format!("Level {}", screen.0));
+    app.insert_resource(menu);
+    app.update();
+    let crumbs = |app: &mut App| {
+        let world = app.world_mut();
+        let mut crumbs: Vec<_> = world
+            .query::<(Entity, &BreadcrumbComponent<LevelScreen>)>()
+            .iter(world)
+            .map(|(entity, crumb)| (crumb.depth, entity))
+            .collect();
+        crumbs.sort();
+        crumbs
+    };
+    // Hidden on the root screen
+    assert!(crumbs(&mut app).is_empty());
+
+    for event in [Down, Select, Down, Select] {
+        app.world_mut().write_message(event);
+        app.update();
+    }
+    app.update();
+    // The topmost screen is shown already and can't be clicked
+    let shown = crumbs(&mut app);
+    assert_eq!(shown.len(), 2);
+    app.world_mut()
+        .entity_mut(shown[1].1)
+        .insert(Interaction::Pressed);
+    app.update();
+    let menu = app.world().resource::<MenuState<LevelScreen>>();
+    assert_eq!(menu.screens(), &[LevelScreen(0), LevelScreen(1)]);
+}
+
+/// A root screen that opens a group of tabs, see `next_tab_cycles_through_the_group`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum TabScreen {
+    Root,
+    Video,
+    Audio,
+    Controls,
+}
+
+impl ScreenTrait for TabScreen {
+    type Action = TestAction;
+    type State = TestState;
+    fn resolve(&self, _state: &TestState) -> Menu<Self> {
+        const TABS: [(&str, TabScreen); 3] = [
+            ("Video", TabScreen::Video),
+            ("Audio", TabScreen::Audio),
+            ("Controls", TabScreen::Controls),
+        ];
+        let tab = |id| {
+            Menu::new(
+                id,
+                vec![MenuItem::action("One", Play), MenuItem::action("Two", Play)],
+            )
+            .with_tabs(TABS)
+        };
+        match self {
+            TabScreen::Root => {
+                Menu::new("root", vec![MenuItem::screen("Settings", TabScreen::Video)])
+            }
+            TabScreen::Video => tab("video"),
+            TabScreen::Audio => tab("audio"),
+            TabScreen::Controls => tab("controls"),
+        }
+    }
+}
+
+#[test]
+fn next_tab_cycles_through_the_group() {
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    let mut menu = MenuState::new(state, TabScreen::Root, None);
+    let mut shown = |events: &[NavigationEvent]| {
+        menu.simulate(events);
+        (*menu.screens().last().unwrap(), focus(&menu))
+    };
+    assert_eq!(shown(&[Select]), (TabScreen::Video, Some(0)));
+    assert_eq!(shown(&[NextTab]), (TabScreen::Audio, Some(0)));
+    assert_eq!(shown(&[Down, NextTab]), (TabScreen::Controls, Some(0)));
+    assert_eq!(shown(&[NextTab]), (TabScreen::Video, Some(0)));
+    // Each tab keeps its focus
+    assert_eq!(
+        shown(&[PreviousTab, PreviousTab]),
+        (TabScreen::Audio, Some(1))
+    );
+    // The tabs replace each other, so `Back` leaves the group
+    assert_eq!(shown(&[Back]), (TabScreen::Root, Some(0)));
+}
+
+#[test]
+fn shoulders_switch_tabs_or_jump_to_the_first_and_last_item() {
+    let bindings = GamepadBindings::default();
+    assert_eq!(
+        bindings.event_for(GamepadButton::LeftTrigger),
+        Some(PreviousTab)
+    );
+    assert_eq!(
+        bindings.event_for(GamepadButton::RightTrigger),
+        Some(NextTab)
+    );
+    // Screens without tabs
+    let mut menu = list(3);
+    menu.simulate(&[NextTab]);
+    assert_eq!(focus(&menu), Some(2));
+    menu.simulate(&[PreviousTab]);
+    assert_eq!(focus(&menu), Some(0));
+}
+
+#[test]
+fn back_focusses_the_row_the_screen_was_left_from() {
+    // Both screens use the same menu id, so they share their row in the `Selections`
+    let mut menu = menu(|screen, _| match screen {
+        TestScreen::Root => Menu::new(
+            "list",
+            vec![
+                MenuItem::action("One", Play),
+                MenuItem::toggle("Two", Play, false),
+                MenuItem::action("Three", Play),
+                MenuItem::screen("Four", TestScreen::Sub),
+            ],
+        ),
+        TestScreen::Sub => Menu::new(
+            "list",
+            vec![MenuItem::action("Play", Play), MenuItem::back("Back")],
+        ),
+    });
+    menu.simulate(&[Down, Select, Down, Down, Select]);
+    assert_eq!(menu.screens(), &[TestScreen::Root, TestScreen::Sub]);
+    // Moves the shared row to 0 and 1
+    menu.simulate(&[Up, Down, Select]);
+    assert_eq!(menu.screens(), &[TestScreen::Root]);
+    assert_eq!(focus(&menu), Some(3));
+}
+
+#[test]
+fn large_menu_is_built_over_frames_without_blanking() {
+    let mut app = app(menu(|screen, _| {
+        let (id, first) = match screen {
+            TestScreen::Root => ("root", MenuItem::screen("Sub", TestScreen::Sub)),
+            TestScreen::Sub => ("sub", MenuItem::back("Back")),
+        };
+        let rows = (1..500).map(|row| MenuItem::action(format!("{row}"), Play));
+        Menu::new(id, std::iter::once(first).chain(rows).collect())
+    }));
+    app.insert_resource(MenuOptions {
+        max_spawns_per_frame: Some(250),
+        ..Default::default()
+    });
+    // The buttons below the shown root, and whether it is being replaced
+    let shown = |app: &mut App| {
+        app.world_mut()
+            .run_system_once(
+                |menu: Res<MenuState<TestScreen>>,
+                 visibility: Query<&Visibility>,
+                 children: Query<&Children>,
+                 buttons: Query<(), With<ButtonComponent<TestScreen>>>| {
+                    let root = menu.root.unwrap();
+                    assert_ne!(visibility.get(root).ok(), Some(&Visibility::Hidden));
+                    let shown = buttons.iter_many(children.iter_descendants(root)).count();
+                    (shown, menu.pending.is_some())
+                },
+            )
+            .unwrap()
+    };
+
+    app.world_mut().write_message(Select);
+    let mut frames = 0;
+    loop {
+        app.update();
+        frames += 1;
+        let (buttons, building) = shown(&mut app);
+        assert!(buttons > 0);
+        if !building {
+            break;
+        }
+        assert!(frames < 5);
+    }
+    // Not all at once
+    assert!(frames > 1);
+    let menu = app.world().resource::<MenuState<TestScreen>>();
+    assert_eq!(menu.screens(), &[TestScreen::Root, TestScreen::Sub]);
+}
+
+/// Press and release `key_code`, which types `logical_key`, and run a frame
+fn press_key(app: &mut App, key_code: KeyCode, logical_key: Key) {
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world_mut().write_message(KeyboardInput {
+            key_code,
+            logical_key: logical_key.clone(),
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+    app.update();
+}
+
+#[test]
+fn typing_jumps_to_the_item_starting_with_the_letters() {
+    let mut menu = list(0).with_type_to_search(Duration::from_secs(1));
+    menu.state_mut().names = vec!["Video", "Accessibility", "Audio", "Controls"];
+    let mut app = app(menu);
+    let type_key = |app: &mut App, key: KeyCode, letter: &str| {
+        press_key(app, key, Key::Character(letter.into()));
+        focus(app.world().resource::<MenuState<TestScreen>>())
+    };
+
+    assert_eq!(type_key(&mut app, KeyCode::KeyA, "a"), Some(1));
+    assert_eq!(type_key(&mut app, KeyCode::KeyU, "u"), Some(2));
+    // "auv" matches nothing, but after a pause the letters start over
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        200,
+    )));
+    for _ in 0..8 {
+        app.update();
+    }
+    assert_eq!(type_key(&mut app, KeyCode::KeyV, "v"), Some(0));
+}
+
+#[test]
+fn presets_have_distinct_states_and_match_their_setters() {
+    for sheet in [Stylesheet::dark(), Stylesheet::light()] {
+        let button = &sheet.button;
+        assert_ne!(button.hover.bg, button.normal.bg);
+        assert_ne!(button.selected.bg, button.normal.bg);
+        assert_ne!(button.selected.bg, button.hover.bg);
+        assert_ne!(button.disabled.fg, button.normal.fg);
+    }
+    let [normal, hover, selected] = [Color::BLACK, Color::WHITE, Color::NONE]
+        .map(|bg| ControlState::new(Color::srgb(0.5, 0.5, 0.5), bg));
+    let built = Stylesheet::default().with_button_states(normal, hover, selected);
+    let mut by_hand = Stylesheet::default();
+    by_hand.button.normal = ControlState {
+        fg: Color::srgb(0.5, 0.5, 0.5),
+        bg: Color::BLACK,
+    };
+    by_hand.button.hover = ControlState {
+        fg: Color::srgb(0.5, 0.5, 0.5),
+        bg: Color::WHITE,
+    };
+    by_hand.button.selected = ControlState {
+        fg: Color::srgb(0.5, 0.5, 0.5),
+        bg: Color::NONE,
+    };
+    for (built, by_hand) in [
+        (built.button.normal, by_hand.button.normal),
+        (built.button.hover, by_hand.button.hover),
+        (built.button.selected, by_hand.button.selected),
+        (built.button.disabled, by_hand.button.disabled),
+    ] {
+        assert_eq!(built, by_hand);
+    }
+}
+
+#[test]
+fn replacing_the_stylesheet_restyles_the_menu_and_keeps_the_focus() {
+    let mut app = app(list(3));
+    app.world_mut().write_message(Down);
+    app.update();
+    // The rendered background of each row, ordered by their index
+    let backgrounds = |app: &mut App| {
+        let world = app.world_mut();
+        let mut rows: Vec<_> = world
+            .query::<(&ButtonComponent<TestScreen>, &BackgroundColor)>()
+            .iter(world)
+            .map(|(button, background)| (button.menu_identifier.1, background.0))
+            .collect();
+        rows.sort_by_key(|(index, _)| *index);
+        rows.into_iter().map(|(_, color)| color).collect::<Vec<_>>()
+    };
+    let default = Stylesheet::default().button;
+    assert_eq!(
+        backgrounds(&mut app),
+        vec![default.normal.bg, default.selected.bg, default.normal.bg]
+    );
+
+    app.insert_resource(Stylesheet::light());
+    app.update();
+    let light = Stylesheet::light().button;
+    assert_eq!(
+        backgrounds(&mut app),
+        vec![light.normal.bg, light.selected.bg, light.normal.bg]
+    );
+    assert_eq!(
+        focus(app.world().resource::<MenuState<TestScreen>>()),
+        Some(1)
+    );
+}
+
+#[test]
+fn typed_number_sets_the_slider_within_its_range() {
+    let mut menu = menu(|_, state| {
+        Menu::new(
+            "root",
+            vec![MenuItem::slider(
+                "Volume",
+                Play,
+                state.number,
+                0.0..=100.0,
+                1.0,
+            )],
+        )
+    });
+    menu.state_mut().number = 50.0;
+    let mut app = app(menu);
+    let type_number = |app: &mut App, number: &str| {
+        app.world_mut().write_message(TypeNumber);
+        app.update();
+        for digit in number.chars() {
+            press_key(
+                app,
+                KeyCode::Digit0,
+                Key::Character(digit.to_string().into()),
+            );
+        }
+        app.world_mut()
+            .resource_mut::<Messages<MenuSoundEvent>>()
+            .clear();
+        press_key(app, KeyCode::Enter, Key::Enter);
+        let sounds: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<MenuSoundEvent>>()
+            .drain()
+            .map(|sound| sound.kind)
+            .collect();
+        let events: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<TestEvent>>()
+            .drain()
+            .collect();
+        (sounds, events)
+    };
+
+    // Outside of the range, the number can be corrected or discarded
+    let (sounds, events) = type_number(&mut app, "150");
+    assert_eq!(sounds, vec![MenuSound::Invalid]);
+    assert_eq!(events, vec![]);
+    press_key(&mut app, KeyCode::Escape, Key::Escape);
+
+    let (_, events) = type_number(&mut app, "75");
+    assert_eq!(events, vec![Value(Play, Float(75.0))]);
+    let menu = app.world().resource::<MenuState<TestScreen>>();
+    assert_eq!(menu.get_selection(&("root".into(), 0)), Some(Float(75.0)));
+}
+
+#[test]
+fn back_on_the_root_screen_is_unhandled_once() {
+    let mut app = app(list(2));
+    let mut unhandled = |event| {
+        app.world_mut().write_message(event);
+        app.update();
+        app.world_mut()
+            .resource_mut::<Messages<UnhandledNavigationEvent<TestScreen>>>()
+            .drain()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(unhandled(Down), vec![]);
+    assert_eq!(
+        unhandled(Back),
+        vec![UnhandledNavigationEvent {
+            screen: TestScreen::Root,
+            event: Back,
+        }]
+    );
+}
+
+#[test]
+fn pointer_is_shown_on_the_focussed_row_only() {
+    let mut app = app(list(3));
+    let pointer = app
+        .world_mut()
+        .resource_mut::<Assets<Image>>()
+        .add(Image::default());
+    app.world_mut()
+        .resource_mut::<MenuState<TestScreen>>()
+        .set_stylesheet(Stylesheet::default().with_pointer(pointer.clone()));
+    app.update();
+    // The rows with a shown pointer
+    let pointed = move |app: &mut App| {
+        let pointer = pointer.clone();
+        app.world_mut()
+            .run_system_once(
+                move |images: Query<(&ImageNode, &Visibility, &ChildOf)>,
+                      children: Query<&Children>,
+                      buttons: Query<&ButtonComponent<TestScreen>>| {
+                    let mut rows: Vec<_> = images
+                        .iter()
+                        .filter(|(image, ..)| image.image == pointer)
+                        .filter(|(_, visibility, _)| **visibility != Visibility::Hidden)
+                        .flat_map(|(.., row)| {
+                            buttons
+                                .iter_many(children.iter_descendants(row.parent()))
+                                .map(|button| button.menu_identifier.1)
+                                .collect::<Vec<_>>()
+                        })
+                        .collect();
+                    rows.sort();
+                    rows
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(pointed(&mut app), vec![0]);
+    app.world_mut().write_message(Down);
+    app.update();
+    assert_eq!(pointed(&mut app), vec![1]);
+}
+
+#[test]
+fn menu_of_a_lost_gamepad_follows_the_other_one() {
+    let mut app = app(list(3));
+    let [first, second] = [(); 2].map(|_| app.world_mut().spawn_empty().id());
+    for gamepad in [first, second] {
+        app.world_mut().write_message(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected {
+                name: "Pad".into(),
+                vendor_id: None,
+                product_id: None,
+            },
+        ));
+    }
+    app.update();
+    app.world_mut()
+        .resource_mut::<MenuState<TestScreen>>()
+        .set_inputs(MenuInputs::Gamepad(first));
+
+    app.world_mut().write_message(GamepadConnectionEvent::new(
+        first,
+        GamepadConnection::Disconnected,
+    ));
+    app.update();
+    let lost: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Messages<GamepadLostEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(lost, vec![GamepadLostEvent { gamepad: first }]);
+
+    // What gilrs reports for the second gamepad
+    for value in [1.0, 0.0] {
+        app.world_mut()
+            .write_message(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                second,
+                GamepadButton::DPadDown,
+                value,
+            )));
+        app.update();
+    }
+    assert_eq!(
+        focus(app.world().resource::<MenuState<TestScreen>>()),
+        Some(1)
+    );
+}
+
+#[test]
+fn focussed_bounds_follow_the_focus() {
+    let mut app = app(list(3));
+    let bounds = |app: &App| *app.world().resource::<MenuBounds<TestScreen>>();
+    // Not laid out yet
+    assert_eq!(bounds(&app), MenuBounds::default());
+
+    // What the layout of bevy_ui computes, rows of 40 pixels below each other
+    let lay_out = |app: &mut App| {
+        let world = app.world_mut();
+        let mut placed: Vec<_> = world
+            .query::<(Entity, &ButtonComponent<TestScreen>)>()
+            .iter(world)
+            .map(|(entity, button)| (entity, 20.0 + 40.0 * button.menu_identifier.1 as f32, 40.0))
+            .collect();
+        placed.push((
+            world.resource::<MenuState<TestScreen>>().root.unwrap(),
+            60.0,
+            120.0,
+        ));
+        for (entity, center, height) in placed {
+            world.entity_mut(entity).insert((
+                ComputedNode {
+                    size: Vec2::new(200.0, height),
+                    ..Default::default()
+                },
+                UiGlobalTransform::from(Affine2::from_translation(Vec2::new(100.0, center))),
+            ));
+        }
+        app.update();
+    };
+    lay_out(&mut app);
+    let row = |index: f32| {
+        Some(Rect::from_center_size(
+            Vec2::new(100.0, 20.0 + 40.0 * index),
+            Vec2::new(200.0, 40.0),
+        ))
+    };
+    assert_eq!(bounds(&app).focussed, row(0.0));
+    assert_eq!(bounds(&app).menu, Some(Rect::new(0.0, 0.0, 200.0, 120.0)));
+
+    app.world_mut().write_message(Down);
+    app.update();
+    lay_out(&mut app);
+    assert_eq!(bounds(&app).focussed, row(1.0));
+}
+
+#[test]
+fn held_select_selects_once_across_screens() {
+    let mut app = app(menu(|screen, _| match screen {
+        TestScreen::Root => Menu::new("root", vec![MenuItem::screen("Sub", TestScreen::Sub)]),
+        TestScreen::Sub => Menu::new("sub", vec![MenuItem::action("Play", Play)]),
+    }));
+    let enter = |app: &mut App, state| {
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::Enter,
+            logical_key: Key::Enter,
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        app.world_mut()
+            .resource_mut::<Messages<TestEvent>>()
+            .drain()
+            .collect::<Vec<_>>()
+    };
+    // Repeats of a held key that the OS doesn't flag
+    for _ in 0..3 {
+        assert_eq!(enter(&mut app, ButtonState::Pressed), vec![]);
+    }
+    let menu = app.world().resource::<MenuState<TestScreen>>();
+    assert_eq!(menu.screens(), &[TestScreen::Root, TestScreen::Sub]);
+
+    enter(&mut app, ButtonState::Released);
+    assert_eq!(enter(&mut app, ButtonState::Pressed), vec![Action(Play)]);
+}
+
+#[test]
+#[should_panic(expected = "expects a MenuState<bevy_quickmenu::tests::TestScreen> after Startup")]
+fn startup_check_explains_a_missing_menu_state() {
+    let mut app = App::new();
+    with_plugins(&mut app, QuickMenuPlugin::new().with_startup_check()).update();
+}
+
+#[test]
+fn body_is_wrapped_and_never_focussed() {
+    const BODY: &str = "Line one\nLine two\nLine three";
+    let mut app = app(menu(|_, _| {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::body(BODY),
+                MenuItem::action("Ok", Play),
+                MenuItem::body("The end"),
+            ],
+        )
+    }));
+    let world = app.world_mut();
+    let (node, layout) = world
+        .query::<(&bevy::prelude::Text, &Node, &TextLayout)>()
+        .iter(world)
+        .find(|(text, _, _)| text.0 == BODY)
+        .map(|(_, node, layout)| (node.max_width, layout.linebreak))
+        .unwrap();
+    assert_eq!(node, Val::Px(480.0));
+    assert_eq!(layout, LineBreak::WordBoundary);
+
+    // Only the action can be focussed
+    for event in [Up, Down, Down] {
+        app.world_mut().write_message(event);
+        app.update();
+    }
+    assert_eq!(buttons(&mut app, |button| button.selected), [true]);
+    app.world_mut().write_message(Select);
+    app.update();
+    let events: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Messages<TestEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(events, vec![Action(Play)]);
+}
+
+#[test]
+fn only_the_most_recent_menu_navigates() {
+    let mut app = app(list(3));
+    app.insert_resource(InputContext::MostRecent)
+        .add_plugins(QuickMenuPlugin::<PlayerScreen<0>>::new());
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    app.insert_resource(MenuState::new(state, PlayerScreen::<0>::Root, None));
+    app.update();
+    // The focus of the main menu and of the one opened after it
+    let navigate = |app: &mut App, event| {
+        app.world_mut().write_message(event);
+        app.update();
+        let world = app.world();
+        (
+            focus(world.resource::<MenuState<TestScreen>>()),
+            focus(world.resource::<MenuState<PlayerScreen<0>>>()),
+        )
+    };
+    assert_eq!(navigate(&mut app, Down), (Some(0), Some(1)));
+    // The main menu takes over once the other one is hidden
+    app.world_mut()
+        .resource_mut::<MenuState<PlayerScreen<0>>>()
+        .set_visible(false);
+    app.update();
+    assert_eq!(navigate(&mut app, Down), (Some(1), Some(1)));
+
+    app.insert_resource(InputContext::menu::<PlayerScreen<0>>());
+    app.world_mut()
+        .resource_mut::<MenuState<PlayerScreen<0>>>()
+        .set_visible(true);
+    app.update();
+    assert_eq!(navigate(&mut app, Up), (Some(1), Some(0)));
+}
+
+/// Lay the menu out like bevy_ui does in a window of 800x600 pixels
+fn with_layout(app: &mut App) {
+    app.add_plugins((
+        bevy::transform::TransformPlugin,
+        bevy::ui::UiPlugin,
+        bevy::image::TextureAtlasPlugin,
+    ));
+    let mut camera = Camera::default();
+    camera.computed.target_info = Some(RenderTargetInfo {
+        physical_size: UVec2::new(800, 600),
+        scale_factor: 1.0,
+    });
+    app.world_mut().spawn((Camera2d, camera));
+    app.update();
+}
+
+#[test]
+fn row_gap_adds_to_the_height_of_the_menu() {
+    let height = |gap| {
+        let mut menu = list(4);
+        menu.set_stylesheet(Stylesheet::default().with_rows(RowStyle {
+            gap,
+            ..Default::default()
+        }));
+        let mut app = app(menu);
+        with_layout(&mut app);
+        app.world()
+            .resource::<MenuBounds<TestScreen>>()
+            .menu
+            .unwrap()
+            .height()
+    };
+    let packed = height(0.0);
+    // Three gaps between the four rows
+    assert_eq!(height(10.0), packed + 30.0);
+    assert_eq!(height(20.0), packed + 60.0);
+}
+
+#[test]
+fn nine_slice_plates_follow_the_state_of_their_buttons() {
+    let mut app = app(list(3));
+    let [normal, hover, selected, panel] = [(); 4].map(|_| {
+        app.world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::default())
+    });
+    let plate = |image: &Handle<Image>| {
+        ImageNode::new(image.clone()).with_mode(NodeImageMode::Sliced(TextureSlicer {
+            border: BorderRect::all(6.0),
+            ..Default::default()
+        }))
+    };
+    let plates = ControlImages::new(plate(&normal))
+        .with_hover(plate(&hover))
+        .with_selected(plate(&selected));
+    app.world_mut()
+        .resource_mut::<MenuState<TestScreen>>()
+        .set_stylesheet(
+            Stylesheet::default()
+                .with_button_images(plates)
+                .with_panel(plate(&panel)),
+        );
+    app.update();
+    // The plate behind each row, ordered by their index
+    let shown = |app: &mut App| {
+        let world = app.world_mut();
+        let mut rows: Vec<_> = world
+            .query::<(&ButtonComponent<TestScreen>, &ImageNode)>()
+            .iter(world)
+            .map(|(button, image)| (button.menu_identifier.1, image.image.clone()))
+            .collect();
+        rows.sort_by_key(|(index, _)| *index);
+        rows.into_iter().map(|(_, image)| image).collect::<Vec<_>>()
+    };
+    assert_eq!(
+        shown(&mut app),
+        [selected.clone(), normal.clone(), normal.clone()]
+    );
+    let world = app.world_mut();
+    let root = world.resource::<MenuState<TestScreen>>().root.unwrap();
+    assert_eq!(world.get::<ImageNode>(root).unwrap().image, panel);
+
+    // What bevy_ui does for the row below the cursor
+    let world = app.world_mut();
+    let row = world
+        .query::<(Entity, &ButtonComponent<TestScreen>)>()
+        .iter(world)
+        .find(|(_, button)| button.menu_identifier.1 == 2)
+        .map(|(entity, _)| entity)
+        .unwrap();
+    world.entity_mut(row).insert(Interaction::Hovered);
+    app.update();
+    assert_eq!(
+        shown(&mut app),
+        [selected.clone(), normal.clone(), hover.clone()]
+    );
+
+    // The cursor stays on the third row
+    app.world_mut().write_message(Down);
+    app.update();
+    assert_eq!(shown(&mut app), [normal, selected, hover]);
+
+    // The corners grow along with the rest of the menu
+    app.world_mut()
+        .resource_mut::<MenuState<TestScreen>>()
+        .set_scale(2.0);
+    app.update();
+    let world = app.world_mut();
+    let corner_scales: Vec<_> = world
+        .query_filtered::<&ImageNode, With<ButtonComponent<TestScreen>>>()
+        .iter(world)
+        .map(|image| match &image.image_mode {
+            NodeImageMode::Sliced(slicer) => slicer.max_corner_scale,
+            _ => 0.0,
+        })
+        .collect();
+    assert_eq!(corner_scales, [2.0; 3]);
+}
+
+#[test]
+fn plugins_without_options_keep_those_of_another_one() {
+    let mut app = App::new();
+    let options = MenuOptions {
+        transition_duration: Duration::from_millis(300),
+        max_spawns_per_frame: Some(20),
+        ..Default::default()
+    };
+    with_plugins(&mut app, QuickMenuPlugin::with_options(options))
+        .add_plugins(QuickMenuPlugin::<HudScreen>::new());
+    let kept = app.world().resource::<MenuOptions>();
+    assert_eq!(kept.transition_duration, Duration::from_millis(300));
+    assert_eq!(kept.max_spawns_per_frame, Some(20));
+}
+
+#[test]
+fn without_pointer_hovering_does_nothing() {
+    let mut app = App::new();
+    app.insert_resource(NavigationConfig {
+        hover_selects: true,
+        ..Default::default()
+    });
+    with_plugins(&mut app, QuickMenuPlugin::new().without_pointer()).insert_resource(list(3));
+    app.update();
+    app.update();
+    let world = app.world_mut();
+    let row = world
+        .query::<(Entity, &ButtonComponent<TestScreen>)>()
+        .iter(world)
+        .find(|(_, button)| button.menu_identifier.1 == 2)
+        .map(|(entity, _)| entity)
+        .unwrap();
+    world.entity_mut(row).insert(Interaction::Hovered);
+    app.update();
+    app.update();
+    let focussed = |app: &App| focus(app.world().resource::<MenuState<TestScreen>>());
+    assert_eq!(focussed(&app), Some(0));
+
+    app.world_mut().write_message(Down);
+    app.update();
+    assert_eq!(focussed(&app), Some(1));
+}
+
+#[test]
+fn hovered_row_and_focussed_row_keep_their_own_colors() {
+    let mut app = app(list(3));
+    let world = app.world_mut();
+    let row = world
+        .query::<(Entity, &ButtonComponent<TestScreen>)>()
+        .iter(world)
+        .find(|(_, button)| button.menu_identifier.1 == 1)
+        .map(|(entity, _)| entity)
+        .unwrap();
+    world.entity_mut(row).insert(Interaction::Hovered);
+    app.update();
+    // The background and text color of each row, ordered by their index
+    let colors = app
+        .world_mut()
+        .run_system_once(
+            |rows: Query<(Entity, &ButtonComponent<TestScreen>, &BackgroundColor)>,
+             children: Query<&Children>,
+             texts: Query<&TextColor>| {
+                let mut colors: Vec<_> = rows
+                    .iter()
+                    .map(|(entity, button, background)| {
+                        let text = texts.iter_many(children.iter_descendants(entity)).next();
+                        (button.menu_identifier.1, (background.0, text.unwrap().0))
+                    })
+                    .collect();
+                colors.sort_by_key(|(index, _)| *index);
+                colors
+                    .into_iter()
+                    .map(|(_, colors)| colors)
+                    .collect::<Vec<_>>()
+            },
+        )
+        .unwrap();
+    let style = Stylesheet::default().button;
+    assert_eq!(
+        colors,
+        [
+            (style.selected.bg, style.selected.fg),
+            (style.hover.bg, style.hover.fg),
+            (style.normal.bg, style.normal.fg),
+        ]
+    );
+}
+
+#[test]
+fn menus_are_placed_at_their_own_anchors() {
+    let mut menu = list(3);
+    menu.set_stylesheet(Stylesheet::default().with_layout(
+        MenuLayout::new(MenuAnchor::BottomRight).with_offset(Val::Px(10.0), Val::Px(20.0)),
+    ));
+    let mut app = app(menu);
+    app.add_plugins(QuickMenuPlugin::<HudScreen>::new());
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    let mut hud = MenuState::new(state, HudScreen, None);
+    hud.set_stylesheet(Stylesheet::default().with_layout(
+        MenuLayout::new(MenuAnchor::TopCenter).with_offset(Val::Px(0.0), Val::Px(30.0)),
+    ));
+    app.insert_resource(hud);
+    app.update();
+    with_layout(&mut app);
+
+    let world = app.world();
+    let menu_root = world.resource::<MenuState<TestScreen>>().root.unwrap();
+    let hud_root = world.resource::<MenuState<HudScreen>>().root.unwrap();
+    let node = world.get::<Node>(menu_root).unwrap();
+    assert_eq!(node.position_type, PositionType::Absolute);
+    assert_eq!((node.right, node.bottom), (Val::Px(10.0), Val::Px(20.0)));
+    assert_eq!((node.left, node.top), (Val::Auto, Val::Auto));
+    let node = world.get::<Node>(hud_root).unwrap();
+    assert_eq!(node.position_type, PositionType::Absolute);
+    assert_eq!((node.left, node.top), (Val::Percent(50.0), Val::Px(30.0)));
+    assert_eq!((node.right, node.bottom), (Val::Auto, Val::Auto));
+
+    // The bottom right corner of the menu and the top center of the HUD
+    let rect = |root| {
+        let size = world.get::<ComputedNode>(root).unwrap().size();
+        let center = world.get::<UiGlobalTransform>(root).unwrap().translation;
+        Rect::from_center_size(center, size)
+    };
+    assert_eq!(rect(menu_root).max, Vec2::new(790.0, 580.0));
+    let hud = rect(hud_root);
+    assert_eq!((hud.center().x, hud.min.y), (400.0, 30.0));
+}
+
+#[test]
+fn menus_are_drawn_above_ui_with_a_lower_z_index() {
+    let mut menu = list(2);
+    menu.set_stylesheet(Stylesheet::default().with_z_index(10));
+    let mut app = app(menu);
+    app.add_plugins(QuickMenuPlugin::<HudScreen>::new());
+    let state = TestState {
+        screens: |_, _| unreachable!(),
+        names: Vec::new(),
+        number: 0.0,
+    };
+    let mut hud = MenuState::new(state, HudScreen, None);
+    hud.set_stylesheet(Stylesheet::default().with_z_index(5));
+    app.insert_resource(hud);
+    app.update();
+    // Spawned after the menus, so it would be drawn on top of them without their z-index
+    let game_ui = app
+        .world_mut()
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            GlobalZIndex(1),
+        ))
+        .id();
+    with_layout(&mut app);
+
+    let world = app.world();
+    let menu_root = world.resource::<MenuState<TestScreen>>().root.unwrap();
+    let hud_root = world.resource::<MenuState<HudScreen>>().root.unwrap();
+    assert_eq!(
+        world.get::<GlobalZIndex>(menu_root),
+        Some(&GlobalZIndex(10))
+    );
+    assert_eq!(world.get::<GlobalZIndex>(hud_root), Some(&GlobalZIndex(5)));
+    let stack_index = |entity| world.get::<ComputedNode>(entity).unwrap().stack_index();
+    assert!(stack_index(game_ui) < stack_index(hud_root));
+    assert!(stack_index(hud_root) < stack_index(menu_root));
+}
+
+#[cfg(feature = "accesskit")]
+#[test]
+fn screen_readers_are_told_the_items_and_the_focus() {
+    use accesskit::{Role, Toggled};
+    use bevy_a11y::AccessibilityNode;
+    use bevy_input_focus::InputFocus;
+
+    let mut menu = menu(|_, state| {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::action("Play", Play),
+                MenuItem::toggle("Music", Play, true),
+                MenuItem::slider("Volume", Play, state.number, 0.0..=1.0, 0.25),
+            ],
+        )
+    });
+    menu.state_mut().number = 0.5;
+    let mut app = app(menu);
+    // The entity and AccessKit node of each row, ordered by their index
+    let rows = |app: &mut App| {
+        let world = app.world_mut();
+        let mut rows: Vec<_> = world
+            .query::<(Entity, &ButtonComponent<TestScreen>, &AccessibilityNode)>()
+            .iter(world)
+            .map(|(entity, button, node)| (button.menu_identifier.1, (entity, node.0.clone())))
+            .collect();
+        rows.sort_by_key(|(index, _)| *index);
+        rows.into_iter().map(|(_, row)| row).collect::<Vec<_>>()
+    };
+
+    let nodes = rows(&mut app);
+    let nodes: Vec<_> = nodes.iter().map(|(_, node)| node).collect();
+    assert_eq!(
+        nodes
+            .iter()
+            .map(|node| (node.role(), node.label()))
+            .collect::<Vec<_>>(),
+        [
+            (Role::Button, Some("Play")),
+            (Role::CheckBox, Some("Music")),
+            (Role::Slider, Some("Volume")),
+        ]
+    );
+    assert_eq!(nodes[0].toggled(), None);
+    assert_eq!(nodes[1].toggled(), Some(Toggled::True));
+    assert_eq!(nodes[2].numeric_value(), Some(0.5));
+    assert_eq!(
+        (nodes[2].min_numeric_value(), nodes[2].max_numeric_value()),
+        (Some(0.0), Some(1.0))
+    );
+
+    let focussed = |app: &mut App| {
+        let focus = app.world().resource::<InputFocus>().0;
+        rows(app)
+            .iter()
+            .position(|(entity, _)| Some(*entity) == focus)
+    };
+    assert_eq!(focussed(&mut app), Some(0));
+    app.world_mut().write_message(Down);
+    app.update();
+    assert_eq!(focussed(&mut app), Some(1));
+}
@@ -1,15 +1,59 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::time::Duration;
 
+use crate::style::StyleOverride;
 use crate::ScreenTrait;
-use bevy::asset::RenderAssetUsages;
+use bevy::asset::{LoadState, RenderAssetUsages};
 use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy::input::gamepad::GamepadInput;
 use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct QuickMenuComponent;
 
+/// The top-level entities of the menu for `S`, its root and highlight, so that
+/// they can be removed even after its `MenuState` is gone
+#[derive(Component)]
+pub(crate) struct MenuEntity<S: Send + Sync + 'static>(std::marker::PhantomData<S>);
+
+impl<S: Send + Sync + 'static> Default for MenuEntity<S> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+/// Where the menu for `S` and its focussed item are on the window, in logical pixels,
+/// e.g. to place a tooltip next to the focussed item. Updated in `PostUpdate` after
+/// the layout. `None` while there is no visible menu or it wasn't laid out yet
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct MenuBounds<S: Send + Sync + 'static> {
+    pub menu: Option<Rect>,
+    pub focussed: Option<Rect>,
+    _screens: std::marker::PhantomData<S>,
+}
+
+impl<S: Send + Sync + 'static> Default for MenuBounds<S> {
+    fn default() -> Self {
+        Self {
+            menu: None,
+            focussed: None,
+            _screens: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<S: Send + Sync + 'static> MenuBounds<S> {
+    pub(crate) fn new(menu: Option<Rect>, focussed: Option<Rect>) -> Self {
+        Self {
+            menu,
+            focussed,
+            ..Default::default()
+        }
+    }
+}
+
 /// The primary horizontal menu can be queried via this component
 #[derive(Component)]
 pub struct PrimaryMenu;
@@ -18,6 +62,42 @@ pub struct PrimaryMenu;
 #[derive(Component)]
 pub struct VerticalMenuComponent(pub WidgetId);
 
+/// The pane that shows the description of the focussed item, see
+/// `Stylesheet::with_description_pane`
+#[derive(Component)]
+pub struct DescriptionPane;
+
+/// The backdrop behind a menu, see `Stylesheet::with_backdrop`
+#[derive(Component)]
+pub(crate) struct MenuBackdrop;
+
+/// A tab that can be clicked to show its screen, see `Menu::with_tabs`
+#[derive(Component)]
+pub(crate) struct TabComponent<S: Send + Sync + 'static> {
+    pub screen: S,
+}
+
+/// A screen of the breadcrumb bar that can be clicked to go back to it,
+/// see `MenuState::with_breadcrumbs`
+#[derive(Component)]
+pub(crate) struct BreadcrumbComponent<S: Send + Sync + 'static> {
+    /// The position of the screen in the stack, starting with the root
+    pub depth: usize,
+    pub screen: S,
+}
+
+/// The list of an open `MenuItem::Dropdown`. Hidden until it was placed below
+/// or, if there is no room, above its row
+#[derive(Component, Default)]
+pub(crate) struct DropdownOverlay {
+    pub placed: bool,
+}
+
+/// The row of a selectable item, identified by the menu and the index of the item
+/// within the selectable items
+#[derive(Component)]
+pub struct MenuRowComponent(pub (WidgetId, usize));
+
 /// Each Button in the UI can be queried via this component in order
 /// to further change the appearance
 #[derive(Component)]
@@ -30,6 +110,7 @@ where
     pub selection: MenuSelection<S>,
     pub menu_identifier: (WidgetId, usize),
     pub selected: bool,
+    pub disabled: bool,
 }
 
 /// Helper to remove the Menu. This `Resource` is inserted to notify
@@ -37,20 +118,251 @@ where
 #[derive(Resource, Default)]
 pub struct CleanUpUI;
 
-/// This map holds the currently selected items in each screen / menu
+/// The menus that are fading out before `cleanup_system` removes them.
+/// `CleanUpUI` stays until all of them are gone
 #[derive(Resource, Default)]
-pub struct Selections(pub HashMap<WidgetId, usize>);
+pub(crate) struct ClosingMenus(pub std::collections::HashSet<std::any::TypeId>);
+
+/// The focussed row of each screen / menu, and the values of items that are edited
+/// in place (e.g. sliders). Values are kept by the id of their menu and the key of
+/// their row from `Menu::with_list`, or else their label, so that they stay with their
+/// item when other rows are added or removed. Once the state passes another value to
+/// the item than the one it had when the user edited it, the state wins.
+/// With the `serde` feature, it can be saved and restored via
+/// `MenuState::export_selections` / `MenuState::import_selections`
+#[derive(Resource, Default, Clone, Debug)]
+pub struct Selections {
+    rows: HashMap<WidgetId, usize>,
+    values: HashMap<ValueKey, EditedValue>,
+}
+
+/// Identifies the value of an item across changes of its menu: the id of the menu and
+/// the key of its row from `Menu::with_list`, or else its label
+pub(crate) type ValueKey = (WidgetId, String);
+
+/// A value the user gave an item, and the one the state passed to it at the time
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct EditedValue {
+    value: MenuValue,
+    initial: Option<MenuValue>,
+}
+
+/// `Selections` as lists, as formats like JSON only support string keys
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedSelections {
+    rows: Vec<(WidgetId, usize)>,
+    values: Vec<(WidgetId, String, EditedValue)>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Selections {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        SavedSelections {
+            rows: self
+                .rows
+                .iter()
+                .map(|(id, row)| (id.clone(), *row))
+                .collect(),
+            values: self
+                .values
+                .iter()
+                .map(|((id, item), value)| (id.clone(), item.clone(), value.clone()))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Selections {
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let saved = SavedSelections::deserialize(deserializer)?;
+        Ok(Selections {
+            rows: saved.rows.into_iter().collect(),
+            values: saved
+                .values
+                .into_iter()
+                .map(|(id, item, value)| ((id, item), value))
+                .collect(),
+        })
+    }
+}
+
+impl Selections {
+    /// The focussed row of the menu with the id `menu`, by its index among the
+    /// selectable items
+    pub fn row(&self, menu: &WidgetId) -> Option<usize> {
+        self.rows.get(menu).copied()
+    }
+
+    /// The value the user gave an item of the menu with the id `menu`, e.g. a slider.
+    /// `item` is the key of its row from `Menu::with_list`, or else its label (the key
+    /// of a localized one). Further items with the same label are `"{label}#2"` and on
+    pub fn value(&self, menu: &WidgetId, item: &str) -> Option<&MenuValue> {
+        self.values
+            .get(&(menu.clone(), item.to_string()))
+            .map(|edited| &edited.value)
+    }
+
+    pub(crate) fn set_row(&mut self, menu: WidgetId, row: usize) {
+        self.rows.insert(menu, row);
+    }
+
+    /// The value the user gave the item at `key`, unless the state passed another
+    /// value than `initial` to it since
+    pub(crate) fn edited(&self, key: &ValueKey, initial: Option<&MenuValue>) -> Option<&MenuValue> {
+        self.values
+            .get(key)
+            .filter(|edited| edited.initial.as_ref() == initial)
+            .map(|edited| &edited.value)
+    }
+
+    pub(crate) fn edited_mut(&mut self, key: &ValueKey) -> Option<&mut MenuValue> {
+        self.values.get_mut(key).map(|edited| &mut edited.value)
+    }
+
+    /// Keep `value` for the item at `key`, which the state passed `initial` to
+    pub(crate) fn set_edited(
+        &mut self,
+        key: ValueKey,
+        value: MenuValue,
+        initial: Option<MenuValue>,
+    ) {
+        self.values.insert(key, EditedValue { value, initial });
+    }
+
+    /// Forget the value of the item at `key` if the state passed another value than
+    /// `initial` to it since it was edited
+    pub(crate) fn forget_stale(&mut self, key: &ValueKey, initial: Option<&MenuValue>) {
+        if self
+            .values
+            .get(key)
+            .is_some_and(|edited| edited.initial.as_ref() != initial)
+        {
+            self.values.remove(key);
+        }
+    }
+
+    /// Whether the edited values are the same as in `other`
+    pub(crate) fn same_values(&self, other: &Selections) -> bool {
+        self.values == other.values
+    }
+}
+
+/// The value of a `MenuItem` that is edited in place
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MenuValue {
+    /// The value of a `MenuItem::Slider`
+    Float(f32),
+    /// The value of a `MenuItem::Toggle`
+    Bool(bool),
+    /// The chosen option of a `MenuItem::Stepper` or `MenuItem::Dropdown`
+    Index(usize),
+    /// The text of a `MenuItem::TextInput`
+    Text(String),
+    /// The input captured by a `MenuItem::BindingCapture`
+    Binding(Binding),
+    /// The group and the value of the selected `MenuItem::Radio`
+    Radio(WidgetId, usize),
+}
+
+/// The focussed item of the topmost menu, see `MenuState::current_selection`
+#[derive(Debug, Clone)]
+pub struct CurrentSelection {
+    /// The id of the topmost menu
+    pub menu: WidgetId,
+    /// The index within the selectable items, as stored in `Selections`
+    pub index: usize,
+    /// The label of the item, if it has one
+    pub label: Option<WidgetLabel>,
+    /// The value of items that are edited in place, including changes by the user
+    pub value: Option<MenuValue>,
+    /// The description of the item, see `MenuItem::with_description`
+    pub description: Option<WidgetLabel>,
+}
+
+/// A key or gamepad button, e.g. for remapping controls via `MenuItem::BindingCapture`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Binding {
+    Key(KeyCode),
+    Gamepad(GamepadButton),
+}
+
+impl Binding {
+    /// A short name to show in the menu, e.g. `A` for `KeyCode::KeyA`
+    pub fn name(&self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            Binding::Gamepad(button) => format!("{button:?}"),
+        }
+    }
+}
 
 /// GamePad and Cursor navigation generates these navigation events
 /// which are then processed by a system and applied to the menu.
 /// Navigation can be customized by sending these events into a
 /// `EventWriter<NavigationEvent>`
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Message)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Message)]
 pub enum NavigationEvent {
     Up,
     Down,
+    Left,
+    Right,
     Select,
     Back,
+    /// Behaves like `Back`, but removes the menu when the root screen is shown.
+    /// Not bound to any input by default
+    Close,
+    /// Go back to the root screen at once, e.g. from deep within the settings.
+    /// Not bound to any input by default
+    Root,
+    /// Show the previous of the tabs of the screen in its place, see `Menu::with_tabs`.
+    /// Bound to `LeftTrigger` by default. Screens without tabs focus their first
+    /// item instead, like `First`
+    PreviousTab,
+    /// Show the next of the tabs of the screen in its place. Bound to `RightTrigger`
+    /// by default, and like `Last` on screens without tabs
+    NextTab,
+    /// Focus the first enabled item, e.g. of a long list
+    First,
+    /// Focus the last enabled item
+    Last,
+    /// Move the focus up by the number of visible rows of a menu with `max_rows`,
+    /// or to the first item of other menus
+    PageUp,
+    /// Move the focus down by the number of visible rows
+    PageDown,
+    /// Type a number into the focussed slider or stepper. `Enter` sets it, `Escape`
+    /// keeps the value as it was
+    TypeNumber,
+}
+
+impl NavigationEvent {
+    /// Directions move the selection and can be repeated when held
+    pub fn is_direction(&self) -> bool {
+        matches!(self, Self::Up | Self::Down | Self::Left | Self::Right)
+    }
+
+    /// The event with `Left` and `Right` swapped, for right-to-left layouts
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::PreviousTab => Self::NextTab,
+            Self::NextTab => Self::PreviousTab,
+            event => event,
+        }
+    }
 }
 
 /// Whenever a state change in the `MenuState` is detected,
@@ -58,6 +370,344 @@ pub enum NavigationEvent {
 #[derive(Message)]
 pub struct RedrawEvent;
 
+/// Which sound to play for some input, see [`MenuSoundEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MenuSound {
+    /// The focus moved, or a slider or stepper changed its value
+    Move,
+    /// An item was selected
+    Select,
+    /// Went back one screen, or closed the menu
+    Back,
+    /// Nothing happened, e.g. moving past the last item or selecting a disabled one
+    Invalid,
+}
+
+/// Sent whenever a menu handles a `NavigationEvent`, or a button is clicked,
+/// so that games can play their own sounds without an audio dependency
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MenuSoundEvent {
+    pub kind: MenuSound,
+    /// The gamepad whose input caused it. `None` for keyboard, mouse and touch input
+    pub gamepad: Option<Entity>,
+}
+
+/// The cues of all menus for this frame's `NavigationEvent`s. Menus that share the
+/// inputs all see the same event, but only one `MenuSoundEvent` is written for it,
+/// that of a menu which handled it
+#[derive(Resource, Default)]
+pub(crate) struct NavigationSounds(pub Vec<MenuSoundEvent>);
+
+/// Sent along with a `MenuSoundEvent` for the kinds that `MenuHaptics` enables, so that
+/// games can rumble the gamepad, e.g. with a `GamepadRumbleRequest`
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct MenuHapticEvent {
+    pub kind: MenuSound,
+    /// The gamepad whose input caused it. `None` for keyboard, mouse and touch input
+    pub gamepad: Option<Entity>,
+    /// From `0.0` to `1.0`
+    pub intensity: f32,
+    pub duration: Duration,
+}
+
+/// Sent when a gamepad is disconnected, e.g. to ask the player to reconnect it.
+/// Menus bound to it via `MenuInputs::Gamepad` are handed to another gamepad if
+/// there is only one menu, see `gamepad_failover_system`
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GamepadLostEvent {
+    pub gamepad: Entity,
+}
+
+/// The strength and length of a rumble
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Haptic {
+    /// From `0.0` to `1.0`
+    pub intensity: f32,
+    pub duration: Duration,
+}
+
+impl Haptic {
+    pub fn new(intensity: f32, duration: Duration) -> Self {
+        Self {
+            intensity,
+            duration,
+        }
+    }
+}
+
+/// The kind of device that was used last, e.g. to hide the mouse cursor while a gamepad
+/// navigates. Updated for all input, whether or not a menu is shown. Touches count as
+/// `Mouse`, and small stick movements below `NavigationConfig::activate_threshold`
+/// are ignored
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LastInputDevice {
+    #[default]
+    Keyboard,
+    Mouse,
+    Gamepad,
+}
+
+/// Which kinds of `MenuSound` also send a `MenuHapticEvent`, and how strong.
+/// `None` disables haptics for that kind. Insert this resource to override the defaults,
+/// which are a light tick on `Move` and a buzz on `Invalid`
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct MenuHaptics {
+    pub on_move: Option<Haptic>,
+    pub on_select: Option<Haptic>,
+    pub on_back: Option<Haptic>,
+    pub on_invalid: Option<Haptic>,
+}
+
+impl Default for MenuHaptics {
+    fn default() -> Self {
+        Self {
+            on_move: Some(Haptic::new(0.1, Duration::from_millis(30))),
+            on_select: Some(Haptic::new(0.3, Duration::from_millis(60))),
+            on_back: Some(Haptic::new(0.2, Duration::from_millis(60))),
+            on_invalid: Some(Haptic::new(0.6, Duration::from_millis(150))),
+        }
+    }
+}
+
+impl MenuHaptics {
+    /// No haptics at all
+    pub fn none() -> Self {
+        Self {
+            on_move: None,
+            on_select: None,
+            on_back: None,
+            on_invalid: None,
+        }
+    }
+
+    /// Replace the haptic for `kind`, `None` disables it
+    pub fn with(mut self, kind: MenuSound, haptic: Option<Haptic>) -> Self {
+        *self.for_kind_mut(kind) = haptic;
+        self
+    }
+
+    pub fn for_kind(&self, kind: MenuSound) -> Option<Haptic> {
+        match kind {
+            MenuSound::Move => self.on_move,
+            MenuSound::Select => self.on_select,
+            MenuSound::Back => self.on_back,
+            MenuSound::Invalid => self.on_invalid,
+        }
+    }
+
+    fn for_kind_mut(&mut self, kind: MenuSound) -> &mut Option<Haptic> {
+        match kind {
+            MenuSound::Move => &mut self.on_move,
+            MenuSound::Select => &mut self.on_select,
+            MenuSound::Back => &mut self.on_back,
+            MenuSound::Invalid => &mut self.on_invalid,
+        }
+    }
+}
+
+/// A `NavigationEvent` that was triggered by a gamepad, including the repeats of a
+/// held stick. Menus that are bound to another gamepad via [`MenuInputs::Gamepad`]
+/// ignore it. Keyboard and mouse wheel input is sent as a plain `NavigationEvent`
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GamepadNavigationEvent {
+    pub gamepad: Entity,
+    pub event: NavigationEvent,
+}
+
+/// Which inputs navigate a menu. Mouse clicks and taps always work, unless the
+/// plugin was added `without_pointer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MenuInputs {
+    /// The keyboard, `NavigationEvent`s and all gamepads
+    #[default]
+    All,
+    /// The keyboard and `NavigationEvent`s, but no gamepads
+    Keyboard,
+    /// Only the given gamepad, e.g. for one player in split-screen
+    Gamepad(Entity),
+    /// The given gamepad and the keyboard, for the player that the keyboard
+    /// belongs to in split-screen
+    GamepadAndKeyboard(Entity),
+    /// Neither the keyboard nor gamepads, only clicks and taps, e.g. for a kiosk.
+    /// `NavigationEvent`s written by your systems are ignored, too
+    Pointer,
+}
+
+impl MenuInputs {
+    pub(crate) fn accepts_keyboard(&self) -> bool {
+        !matches!(self, MenuInputs::Gamepad(_) | MenuInputs::Pointer)
+    }
+
+    pub(crate) fn accepts_gamepad(&self, gamepad: Entity) -> bool {
+        match self {
+            MenuInputs::All => true,
+            MenuInputs::Keyboard | MenuInputs::Pointer => false,
+            MenuInputs::Gamepad(own) | MenuInputs::GamepadAndKeyboard(own) => *own == gamepad,
+        }
+    }
+}
+
+/// How many `QuickMenuPlugin`s were added, i.e. how many menus there can be at once
+#[derive(Resource, Default)]
+pub(crate) struct MenuPlugins(pub usize);
+
+/// Which menus react to the keyboard, gamepads and `NavigationEvent`s when several
+/// `QuickMenuPlugin`s are added, e.g. a main menu and the menu of the HUD. Clicks and
+/// taps always reach the menu under the pointer
+///
+/// ```ignore
+/// commands.insert_resource(InputContext::menu::<PauseScreens>());
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputContext {
+    /// Every shown menu reacts to its `MenuInputs`, e.g. one menu per player
+    #[default]
+    Shared,
+    /// Only the most recently opened or shown menu reacts. When it is removed or
+    /// hidden, the menu that was opened before takes over again
+    MostRecent,
+    /// Only the menu of one screen type reacts, see [`InputContext::menu`]
+    Menu(std::any::TypeId),
+}
+
+impl InputContext {
+    /// Only the menu of `S` reacts
+    pub fn menu<S: ScreenTrait + 'static>() -> Self {
+        Self::Menu(std::any::TypeId::of::<S>())
+    }
+
+    /// Whether the menu of `S` reacts to navigation
+    pub(crate) fn is_active<S: 'static>(&self, opened: &OpenedMenus) -> bool {
+        let id = std::any::TypeId::of::<S>();
+        match self {
+            InputContext::Shared => true,
+            InputContext::MostRecent => opened.0.last().is_none_or(|last| *last == id),
+            InputContext::Menu(menu) => *menu == id,
+        }
+    }
+}
+
+/// The screen types of the shown menus, the most recently opened last,
+/// for `InputContext::MostRecent`
+#[derive(Resource, Default)]
+pub(crate) struct OpenedMenus(pub Vec<std::any::TypeId>);
+
+/// The menus that currently take raw input, to edit a `MenuItem::TextInput` or
+/// for a `MenuItem::BindingCapture`. Captured input isn't translated into `NavigationEvent`s
+#[derive(Resource, Default)]
+pub(crate) struct CapturedInput {
+    keyboard: std::collections::HashSet<std::any::TypeId>,
+    gamepads: std::collections::HashSet<std::any::TypeId>,
+}
+
+impl CapturedInput {
+    pub fn set<S: 'static>(&mut self, keyboard: bool, gamepads: bool) {
+        let id = std::any::TypeId::of::<S>();
+        for (set, captured) in [
+            (&mut self.keyboard, keyboard),
+            (&mut self.gamepads, gamepads),
+        ] {
+            if captured {
+                set.insert(id);
+            } else {
+                set.remove(&id);
+            }
+        }
+    }
+
+    pub fn keyboard(&self) -> bool {
+        !self.keyboard.is_empty()
+    }
+
+    pub fn gamepads(&self) -> bool {
+        !self.gamepads.is_empty()
+    }
+}
+
+/// Only the focus moved within a menu. Instead of re-rendering everything,
+/// just the rows of the two items are rebuilt
+#[derive(Message)]
+pub(crate) struct RefocusEvent {
+    /// The rendered menu this applies to
+    pub root: Entity,
+    pub menu: WidgetId,
+    pub previous: usize,
+    pub current: usize,
+}
+
+/// The letters typed to jump to an item, see `MenuState::with_type_to_search`
+pub(crate) struct TypeSearch {
+    /// How long to wait for the next letter before starting over
+    pub reset_after: Duration,
+    pub typed: String,
+    /// When the last letter was typed, as `Time::elapsed`
+    pub typed_at: Duration,
+}
+
+/// A menu that is built over several frames, see `MenuOptions::max_spawns_per_frame`.
+/// It stays hidden until all of its rows are built
+pub(crate) struct PendingBuild {
+    pub root: Entity,
+    /// The rows that are still empty, with the menu and index of their item
+    pub rows: Vec<(Entity, WidgetId, usize)>,
+}
+
+/// Send whenever a different screen ends up on top of the menu, be it through
+/// user input or through `MenuState::push_screen` and friends
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenChangedEvent<S>
+where
+    S: ScreenTrait + 'static,
+{
+    /// The screen that was on top before
+    pub previous: S,
+    /// The screen that is on top now
+    pub current: S,
+}
+
+/// Send whenever the focus moves to another item of the topmost menu, be it through
+/// navigation, `hover_selects` or a different screen. Re-renders that leave the
+/// focus where it was don't send it, neither does showing a new menu
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct FocusChangedEvent<S>
+where
+    S: ScreenTrait + 'static,
+{
+    /// The screen that is on top now
+    pub screen: S,
+    /// The menu id and index of the item that was focussed before, if any
+    pub from: Option<(WidgetId, usize)>,
+    /// The menu id and index of the item that is focussed now, if any
+    pub to: Option<(WidgetId, usize)>,
+}
+
+/// Send whenever an item is selected with `Select`, a click or a tap, including items
+/// that don't perform an action such as toggles or collapsibles
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct ItemActivatedEvent<S>
+where
+    S: ScreenTrait + 'static,
+{
+    /// The screen of the item
+    pub screen: S,
+    /// The id of the menu of the item
+    pub menu: WidgetId,
+    /// The index of the item within the selectable items, as stored in `Selections`
+    pub index: usize,
+}
+
+/// Send whenever a `NavigationEvent` has no effect, e.g. `Back` on the root screen or
+/// `Select` on a disabled item, for fallbacks such as closing the menu
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct UnhandledNavigationEvent<S>
+where
+    S: ScreenTrait + 'static,
+{
+    /// The screen that is on top
+    pub screen: S,
+    pub event: NavigationEvent,
+}
+
 /// Create a menu with an identifier and a `Vec` of `MenuItem` entries
 pub struct Menu<S>
 where
@@ -67,6 +717,24 @@ where
     pub entries: Vec<MenuItem<S>>,
     pub style: Option<Node>,
     pub background: Option<BackgroundColor>,
+    /// Lay out the entries in a row and navigate them with `Left` / `Right`
+    pub horizontal: bool,
+    /// Moving past the last entry selects the first one and vice versa
+    pub wrap: bool,
+    /// Only show this many entries at once and scroll along with the focus
+    pub max_rows: Option<usize>,
+    /// Lay out the entries in a grid with this many columns, see `with_columns`
+    pub columns: Option<usize>,
+    /// The index within the selectable entries that is focussed when the menu is shown
+    /// for the first time. Afterwards, the focus is kept in the `Selections`
+    pub initial_focus: Option<usize>,
+    /// The sibling screens shown as tabs above the menu, see `with_tabs`
+    pub tabs: Vec<(WidgetLabel, S)>,
+    /// The keys of the rows added with `with_list`, by their position in `entries`.
+    /// Once flattened, by their index within the selectable entries
+    pub(crate) keys: HashMap<usize, String>,
+    /// Once flattened, the key of the value of each selectable entry, see `ValueKey`
+    pub(crate) value_ids: Vec<String>,
 }
 
 impl<S> Menu<S>
@@ -80,64 +748,821 @@ where
             entries,
             style: None,
             background: None,
+            horizontal: false,
+            wrap: false,
+            max_rows: None,
+            columns: None,
+            initial_focus: None,
+            tabs: Vec::new(),
+            keys: HashMap::new(),
+            value_ids: Vec::new(),
         }
     }
 
+    /// A small dialog for destructive actions, e.g. "Delete Save". `on_yes` is only
+    /// performed if "Yes" is selected, both answers go back. Return it for a screen of
+    /// its own and link to that screen instead of the action, or let
+    /// [`MenuItem::with_confirm`] show it on top of the menu
+    pub fn confirm(
+        id: impl Into<WidgetId>,
+        message: impl Into<WidgetLabel>,
+        on_yes: S::Action,
+    ) -> Self {
+        Menu::new(
+            id,
+            vec![
+                MenuItem::headline(message),
+                MenuItem::confirm("Yes", on_yes),
+                MenuItem::back("No"),
+            ],
+        )
+        .with_style(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            align_self: AlignSelf::Center,
+            padding: UiRect::all(Val::Px(20.0)),
+            ..default()
+        })
+        .with_background(BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)))
+    }
+
     pub fn with_background(mut self, bg: BackgroundColor) -> Self {
         self.background = Some(bg);
         self
     }
 
+    /// Show a row of tabs above the menu, one per screen, e.g. "Video" and "Audio" of
+    /// the settings. `NavigationEvent::PreviousTab` / `NextTab` and clicks on a tab show
+    /// that screen in place of this one, without going back first. Every screen of the
+    /// group returns the same tabs. Each keeps its own selections
+    pub fn with_tabs<L: Into<WidgetLabel>>(
+        mut self,
+        tabs: impl IntoIterator<Item = (L, S)>,
+    ) -> Self {
+        self.tabs = tabs
+            .into_iter()
+            .map(|(label, screen)| (label.into(), screen))
+            .collect();
+        self
+    }
+
+    /// Append a row for each element of `data`, e.g. the lobbies of a server browser.
+    /// `key` identifies an element across changes of the list, so that the focus stays
+    /// on the same lobby when the list is sorted again or others are added or removed.
+    /// Change the data in the state and the rows follow with the next redraw
+    pub fn with_list<T, K: ToString>(
+        mut self,
+        data: &[T],
+        key: impl Fn(&T) -> K,
+        row: impl Fn(&T) -> MenuItem<S>,
+    ) -> Self {
+        for element in data {
+            self.keys
+                .insert(self.entries.len(), key(element).to_string());
+            self.entries.push(row(element));
+        }
+        self
+    }
+
     pub fn with_style(mut self, style: Node) -> Self {
         self.style = Some(style);
         self
     }
-}
-
-/// Abstraction over MenuItems in a Screen / Menu
-#[allow(clippy::large_enum_variant)]
-pub enum MenuItem<S>
-where
-    S: ScreenTrait,
-{
-    Screen(WidgetLabel, MenuIcon, S),
-    Action(WidgetLabel, MenuIcon, S::Action),
-    Label(WidgetLabel, MenuIcon),
-    Headline(WidgetLabel, MenuIcon),
-    Image(Handle<Image>, Option<Node>),
-}
 
-impl<S> MenuItem<S>
-where
-    S: ScreenTrait,
-{
-    pub fn screen(s: impl Into<WidgetLabel>, screen: S) -> Self {
-        MenuItem::Screen(s.into(), MenuIcon::None, screen)
+    /// Lay out the entries in a row instead of a column
+    pub fn horizontal(mut self) -> Self {
+        self.horizontal = true;
+        self
+    }
+
+    /// Wrap around to the first entry when navigating past the last one
+    /// (and the other way around)
+    pub fn wrapping(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    /// Show at most `rows` entries and scroll the rest into view when navigating
+    pub fn with_max_rows(mut self, rows: usize) -> Self {
+        self.max_rows = Some(rows.max(1));
+        self
+    }
+
+    /// Lay out the entries in a grid of `columns`, filled row by row, e.g. for an
+    /// inventory. Every entry takes a cell, headlines and labels included. `Up` / `Down`
+    /// move to the nearest enabled entry of the column and `Left` / `Right` within the
+    /// row, stopping at the edges, so sliders and steppers can only be changed by
+    /// selecting them. With `with_max_rows`, that many rows of the grid are shown
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns.max(1));
+        self
+    }
+
+    /// How many entries are visible at once, if the menu scrolls
+    pub(crate) fn visible_entries(&self) -> Option<usize> {
+        self.max_rows.map(|rows| rows * self.columns.unwrap_or(1))
+    }
+
+    /// Focus the selectable entry at `index` instead of the first one, e.g. "Resume".
+    /// Only applies until the user moved the focus, so going back to this screen
+    /// keeps their choice
+    pub fn with_initial_focus(mut self, index: usize) -> Self {
+        self.initial_focus = Some(index);
+        self
+    }
+
+    /// Move the children of collapsibles behind them as `MenuItem::Nested`, so that
+    /// each child has its own index in the `Selections` whether it is shown or not
+    pub(crate) fn flattened(mut self, selections: &Selections) -> Self {
+        let entries = std::mem::take(&mut self.entries);
+        let mut keys = HashMap::with_capacity(self.keys.len());
+        let mut flat = Flattened {
+            id: &self.id,
+            selections,
+            entries: Vec::with_capacity(entries.len()),
+            value_ids: Vec::new(),
+            labels: HashMap::new(),
+        };
+        for (position, entry) in entries.into_iter().enumerate() {
+            let selectable = flat.value_ids.len();
+            let first = flat.entries.len();
+            let key = self.keys.remove(&position);
+            flat.push(vec![entry], 0, false, key.clone());
+            if let Some(key) = key {
+                if flat.entries[first].is_selectable() {
+                    keys.insert(selectable, key);
+                }
+            }
+        }
+        self.value_ids = flat.value_ids;
+        self.entries = flat.entries;
+        self.keys = keys;
+        self
+    }
+
+    /// The key of the value of the selectable entry at `index`
+    pub(crate) fn value_key(&self, index: usize) -> ValueKey {
+        value_key(&self.id, &self.value_ids, index)
+    }
+}
+
+/// The key of the value of the selectable entry at `index` of the menu `id`,
+/// given the `Menu::value_ids`
+pub(crate) fn value_key(id: &WidgetId, value_ids: &[String], index: usize) -> ValueKey {
+    let item = match value_ids.get(index) {
+        Some(item) => item.clone(),
+        None => format!("#{index}"),
+    };
+    (id.clone(), item)
+}
+
+/// The entries of a menu that is being flattened
+struct Flattened<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    id: &'a WidgetId,
+    selections: &'a Selections,
+    entries: Vec<MenuItem<S>>,
+    value_ids: Vec<String>,
+    /// How often each label was used so far, to tell equal labels apart
+    labels: HashMap<String, usize>,
+}
+
+impl<S> Flattened<'_, S>
+where
+    S: ScreenTrait + 'static,
+{
+    /// Append `items` and their children. `key` is the key of the row from
+    /// `Menu::with_list`, if any
+    fn push(&mut self, items: Vec<MenuItem<S>>, depth: usize, hidden: bool, key: Option<String>) {
+        for mut item in items {
+            let children = match item.unstyled_mut() {
+                MenuItem::Collapsible {
+                    children, expanded, ..
+                } => Some((std::mem::take(children), *expanded)),
+                _ => None,
+            };
+            let value_id = item
+                .is_selectable()
+                .then(|| self.value_id(&item, key.clone()));
+            self.entries.push(if depth == 0 {
+                item
+            } else {
+                MenuItem::Nested {
+                    item: Box::new(item),
+                    depth,
+                    hidden,
+                }
+            });
+            if let Some((children, expanded)) = children {
+                let key = (self.id.clone(), value_id.unwrap_or_default());
+                let initial = MenuValue::Bool(expanded);
+                let expanded = match self.selections.edited(&key, Some(&initial)) {
+                    Some(MenuValue::Bool(value)) => *value,
+                    _ => expanded,
+                };
+                let hidden = hidden || !expanded;
+                self.push(children, depth + 1, hidden, None);
+            }
+        }
+    }
+
+    /// The key of the row, or else the label of `item`. Later items with the same
+    /// label are told apart by a number
+    fn value_id(&mut self, item: &MenuItem<S>, key: Option<String>) -> String {
+        let id = key.unwrap_or_else(|| {
+            let label = item.text().map(WidgetLabel::debug_text).unwrap_or_default();
+            let count = self.labels.entry(label.clone()).or_default();
+            *count += 1;
+            match *count {
+                1 => label,
+                count => format!("{label}#{count}"),
+            }
+        });
+        self.value_ids.push(id.clone());
+        id
+    }
+}
+
+/// Spawns the contents of a [`MenuItem::Custom`] row
+pub type CustomBuild = Box<dyn Fn(&mut ChildSpawnerCommands) + Send + Sync>;
+
+/// Abstraction over MenuItems in a Screen / Menu
+#[allow(clippy::large_enum_variant)]
+pub enum MenuItem<S>
+where
+    S: ScreenTrait,
+{
+    /// Shows `screen` on top of the current one
+    Screen {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        screen: S,
+        disabled: bool,
+    },
+    /// Performs `action`, see [`MenuItem::repeatable`] for `repeatable` and
+    /// [`MenuItem::with_confirm`] for `confirm`
+    Action {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        action: S::Action,
+        disabled: bool,
+        repeatable: bool,
+        confirm: Option<WidgetLabel>,
+    },
+    /// Goes back one screen, after performing the action if there is one
+    Back {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        action: Option<S::Action>,
+        disabled: bool,
+    },
+    Label(WidgetLabel, MenuIcon),
+    /// A section title, skipped during navigation
+    Headline(WidgetLabel, MenuIcon),
+    /// A paragraph of text that wraps onto as many lines as it needs, skipped
+    /// during navigation
+    Body {
+        text: WidgetLabel,
+    },
+    Image(Handle<Image>, Option<Node>),
+    /// Empty space of `size` pixels between items, optionally with a line
+    /// in the middle. Separators can't be focussed
+    Separator {
+        size: f32,
+        line: bool,
+    },
+    /// Adjust a value between `min` and `max` with `Left` / `Right`.
+    /// Every change calls [`crate::ActionTrait::handle_value`] with the new value
+    Slider {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        action: S::Action,
+        min: f32,
+        max: f32,
+        step: f32,
+        value: f32,
+        format: fn(f32) -> String,
+        disabled: bool,
+    },
+    /// A read-only bar that is filled to `value` out of `max`, e.g. for health or
+    /// download progress. Meters can't be focussed, change `value` in your state
+    /// and send a `RedrawEvent` to update them
+    Meter {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        value: f32,
+        max: f32,
+        /// The color of the filled part, the text color if not set
+        color: Option<Color>,
+        format: fn(f32) -> String,
+    },
+    /// A boolean that is flipped when selected. Every change calls
+    /// [`crate::ActionTrait::handle_value`] with the new value
+    Toggle {
+        label: WidgetLabel,
+        action: S::Action,
+        value: bool,
+        on_icon: MenuIcon,
+        off_icon: MenuIcon,
+        disabled: bool,
+    },
+    /// One of several mutually exclusive options that are all visible at once.
+    /// Selecting it marks it active, clears the other options of its `group` on
+    /// the same screen and calls [`crate::ActionTrait::handle_value`] with `value`
+    Radio {
+        label: WidgetLabel,
+        action: S::Action,
+        group: WidgetId,
+        /// Reported as `MenuValue::Radio` with the `group` when selected
+        value: usize,
+        active: bool,
+        on_icon: MenuIcon,
+        off_icon: MenuIcon,
+        disabled: bool,
+    },
+    /// Cycle through `options` with `Left` / `Right` (or by selecting it).
+    /// Every change calls [`crate::ActionTrait::handle_value`] with the new index
+    Stepper {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        action: S::Action,
+        options: Vec<String>,
+        index: usize,
+        wrap: bool,
+        disabled: bool,
+    },
+    /// Shows `options[index]`. Selecting it opens a list of all options below the row,
+    /// in which `Up` / `Down` move and `Select` calls [`crate::ActionTrait::handle_value`]
+    /// with the chosen index. `Back` closes the list without a change
+    Dropdown {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        action: S::Action,
+        options: Vec<String>,
+        index: usize,
+        disabled: bool,
+    },
+    /// Free text, e.g. a player name. Selecting it starts editing with the keyboard,
+    /// `Enter` or `Back` ends editing and calls [`crate::ActionTrait::handle_value`]
+    TextInput {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        action: S::Action,
+        value: String,
+        /// The maximum number of characters
+        max_len: usize,
+        /// Shown while the text is empty
+        placeholder: String,
+        disabled: bool,
+    },
+    /// A key or gamepad button for remapping controls. Selecting it waits for the
+    /// next key or button and calls [`crate::ActionTrait::handle_value`] with it,
+    /// `Escape` cancels
+    BindingCapture {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        action: S::Action,
+        /// The current binding, if any
+        binding: Option<Binding>,
+        disabled: bool,
+    },
+    /// Shows its `children` below itself while expanded, instead of leading to
+    /// another screen. Selecting it expands or collapses it, and children can be
+    /// collapsibles themselves
+    Collapsible {
+        label: WidgetLabel,
+        icon: MenuIcon,
+        children: Vec<MenuItem<S>>,
+        /// Whether it starts expanded. Once changed, the state is kept in the `Selections`
+        expanded: bool,
+        disabled: bool,
+    },
+    /// A child of a [`MenuItem::Collapsible`], indented by `depth` levels. Created
+    /// when the menu is shown: hidden children keep their index in the `Selections`,
+    /// but aren't shown and can't be focussed
+    Nested {
+        item: Box<MenuItem<S>>,
+        depth: usize,
+        hidden: bool,
+    },
+    /// Content the other items can't express, e.g. a chart or a preview with overlays.
+    /// `build` spawns the children of the row on every redraw, so it must not rely on
+    /// entities it spawned before. The menu is kept in a resource, which is why the
+    /// closure has to be `Send + Sync + 'static` and can only capture owned data such
+    /// as handles. A `selectable` row can be focussed and hovered like a button, but
+    /// selecting it does nothing
+    Custom {
+        build: CustomBuild,
+        selectable: bool,
+    },
+    /// Another item with some of its style replaced, see [`MenuItem::styled`]
+    Styled(Box<MenuItem<S>>, StyleOverride),
+    /// Another item with a description for the description pane, see
+    /// [`MenuItem::with_description`]
+    Described(Box<MenuItem<S>>, WidgetLabel),
+}
+
+impl<S> MenuItem<S>
+where
+    S: ScreenTrait,
+{
+    pub fn screen(s: impl Into<WidgetLabel>, screen: S) -> Self {
+        MenuItem::Screen {
+            label: s.into(),
+            icon: MenuIcon::None,
+            screen,
+            disabled: false,
+        }
+    }
+
+    pub fn action(s: impl Into<WidgetLabel>, action: S::Action) -> Self {
+        MenuItem::Action {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action,
+            disabled: false,
+            repeatable: false,
+            confirm: None,
+        }
+    }
+
+    /// Go back one screen, like `NavigationEvent::Back`
+    pub fn back(s: impl Into<WidgetLabel>) -> Self {
+        MenuItem::Back {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action: None,
+            disabled: false,
+        }
+    }
+
+    /// Perform `action` and go back one screen, e.g. "Yes" in a [`Menu::confirm`] dialog
+    pub fn confirm(s: impl Into<WidgetLabel>, action: S::Action) -> Self {
+        MenuItem::Back {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action: Some(action),
+            disabled: false,
+        }
+    }
+
+    pub fn label(s: impl Into<WidgetLabel>) -> Self {
+        MenuItem::Label(s.into(), MenuIcon::None)
+    }
+
+    /// A section title such as "Audio" above the items it groups. Headlines use
+    /// the `headline` entry of the stylesheet, can't be focussed and don't count
+    /// towards the indexes in `Selections`
+    pub fn headline(s: impl Into<WidgetLabel>) -> Self {
+        MenuItem::Headline(s.into(), MenuIcon::None)
+    }
+
+    /// A paragraph for help screens, credits and the like. Bodies use the `body` entry
+    /// of the stylesheet, which wraps them at its `wrap_width`, and keep the line
+    /// breaks of `s`. Like headlines, they can't be focussed
+    pub fn body(s: impl Into<WidgetLabel>) -> Self {
+        MenuItem::Body { text: s.into() }
+    }
+
+    pub fn image(s: Handle<Image>) -> Self {
+        MenuItem::Image(s, None)
+    }
+
+    /// A thin line to visually group the items around it
+    pub fn separator() -> Self {
+        MenuItem::Separator {
+            size: 11.0,
+            line: true,
+        }
+    }
+
+    /// Empty space of `size` pixels
+    pub fn spacer(size: f32) -> Self {
+        MenuItem::Separator { size, line: false }
+    }
+
+    /// A slider starting at `value`. Once changed, the value is kept in the
+    /// `Selections` so it survives re-renders
+    pub fn slider(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        value: f32,
+        range: std::ops::RangeInclusive<f32>,
+        step: f32,
+    ) -> Self {
+        MenuItem::Slider {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action,
+            min: *range.start(),
+            max: *range.end(),
+            step,
+            value,
+            format: |value| format!("{value:.1}"),
+            disabled: false,
+        }
+    }
+
+    /// A meter filled to `value` out of `max`
+    pub fn meter(s: impl Into<WidgetLabel>, value: f32, max: f32) -> Self {
+        MenuItem::Meter {
+            label: s.into(),
+            icon: MenuIcon::None,
+            value,
+            max,
+            color: None,
+            format: |value| format!("{value:.0}"),
+        }
+    }
+
+    /// A row whose contents are spawned by `build`, see [`MenuItem::Custom`]
+    pub fn custom(build: impl Fn(&mut ChildSpawnerCommands) + Send + Sync + 'static) -> Self {
+        MenuItem::Custom {
+            build: Box::new(build),
+            selectable: false,
+        }
+    }
+
+    /// Let a custom row be focussed and hovered like a button
+    pub fn with_selectable(mut self, selectable: bool) -> Self {
+        if let MenuItem::Custom { selectable: s, .. } = self.unstyled_mut() {
+            *s = selectable;
+        }
+        self
+    }
+
+    /// A collapsed item that shows `children` below itself once it is selected
+    pub fn collapsible(s: impl Into<WidgetLabel>, children: Vec<MenuItem<S>>) -> Self {
+        MenuItem::Collapsible {
+            label: s.into(),
+            icon: MenuIcon::None,
+            children,
+            expanded: false,
+            disabled: false,
+        }
+    }
+
+    /// A toggle starting at `value`. Once changed, the value is kept in the
+    /// `Selections` so it survives re-renders
+    pub fn toggle(s: impl Into<WidgetLabel>, action: S::Action, value: bool) -> Self {
+        MenuItem::Toggle {
+            label: s.into(),
+            action,
+            value,
+            on_icon: MenuIcon::Checked,
+            off_icon: MenuIcon::Unchecked,
+            disabled: false,
+        }
+    }
+
+    /// An option of the radio `group`, e.g. one of several graphics presets.
+    /// The option that starts `active` is replaced by the one the user selects,
+    /// which is kept in the `Selections` so it survives re-renders
+    pub fn radio(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        group: impl Into<WidgetId>,
+        value: usize,
+        active: bool,
+    ) -> Self {
+        MenuItem::Radio {
+            label: s.into(),
+            action,
+            group: group.into(),
+            value,
+            active,
+            on_icon: MenuIcon::Checked,
+            off_icon: MenuIcon::Unchecked,
+            disabled: false,
+        }
+    }
+
+    /// Replace the icons a toggle or radio option shows for its two states
+    pub fn with_toggle_icons(mut self, on: MenuIcon, off: MenuIcon) -> Self {
+        if let MenuItem::Toggle {
+            on_icon, off_icon, ..
+        }
+        | MenuItem::Radio {
+            on_icon, off_icon, ..
+        } = self.unstyled_mut()
+        {
+            *on_icon = on;
+            *off_icon = off;
+        }
+        self
+    }
+
+    /// A stepper showing `options[index]`. Once changed, the index is kept in the
+    /// `Selections` so it survives re-renders
+    pub fn stepper(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        options: impl IntoIterator<Item = impl Into<String>>,
+        index: usize,
+    ) -> Self {
+        MenuItem::Stepper {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action,
+            options: options.into_iter().map(Into::into).collect(),
+            index,
+            wrap: false,
+            disabled: false,
+        }
+    }
+
+    /// A dropdown showing `options[index]`. Once changed, the index is kept in the
+    /// `Selections` so it survives re-renders
+    pub fn dropdown(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        options: impl IntoIterator<Item = impl Into<String>>,
+        index: usize,
+    ) -> Self {
+        MenuItem::Dropdown {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action,
+            options: options.into_iter().map(Into::into).collect(),
+            index,
+            disabled: false,
+        }
+    }
+
+    /// A text input starting with `value`. Once edited, the text is kept in the
+    /// `Selections` so it survives re-renders
+    pub fn text_input(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        value: impl Into<String>,
+    ) -> Self {
+        MenuItem::TextInput {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action,
+            value: value.into(),
+            max_len: 32,
+            placeholder: String::new(),
+            disabled: false,
+        }
+    }
+
+    /// Capture a new key or gamepad button for `action`, showing the current `binding`
+    pub fn binding_capture(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        binding: Option<Binding>,
+    ) -> Self {
+        MenuItem::BindingCapture {
+            label: s.into(),
+            icon: MenuIcon::None,
+            action,
+            binding,
+            disabled: false,
+        }
+    }
+
+    /// Limit the number of characters of a text input
+    pub fn with_max_len(mut self, max: usize) -> Self {
+        if let MenuItem::TextInput { max_len, .. } = self.unstyled_mut() {
+            *max_len = max;
+        }
+        self
+    }
+
+    /// The text a text input shows while it is empty
+    pub fn with_placeholder(mut self, text: impl Into<String>) -> Self {
+        if let MenuItem::TextInput { placeholder, .. } = self.unstyled_mut() {
+            *placeholder = text.into();
+        }
+        self
+    }
+
+    /// Let a stepper continue with the first option after the last one
+    pub fn with_wrap(mut self) -> Self {
+        if let MenuItem::Stepper { wrap, .. } = self.unstyled_mut() {
+            *wrap = true;
+        }
+        self
+    }
+
+    /// Change how the numeric value of a slider or meter is displayed
+    pub fn with_format(mut self, format: fn(f32) -> String) -> Self {
+        if let MenuItem::Slider { format: f, .. } | MenuItem::Meter { format: f, .. } =
+            self.unstyled_mut()
+        {
+            *f = format;
+        }
+        self
+    }
+
+    /// Let a collapsible start expanded
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        if let MenuItem::Collapsible { expanded: e, .. } = self.unstyled_mut() {
+            *e = expanded;
+        }
+        self
+    }
+
+    /// Fill a meter with `color` instead of the text color
+    pub fn with_color(mut self, color: Color) -> Self {
+        if let MenuItem::Meter { color: c, .. } = self.unstyled_mut() {
+            *c = Some(color);
+        }
+        self
+    }
+
+    /// Show an icon to the left of the label. Its size and spacing come from the
+    /// `icon_style` of the stylesheet
+    pub fn with_icon(mut self, icon: impl Into<MenuIcon>) -> Self {
+        let icon = icon.into();
+        match self.unstyled_mut() {
+            MenuItem::Screen { icon: i, .. }
+            | MenuItem::Action { icon: i, .. }
+            | MenuItem::Back { icon: i, .. }
+            | MenuItem::Label(_, i)
+            | MenuItem::Headline(_, i)
+            | MenuItem::Slider { icon: i, .. }
+            | MenuItem::Meter { icon: i, .. }
+            | MenuItem::Collapsible { icon: i, .. }
+            | MenuItem::Stepper { icon: i, .. }
+            | MenuItem::Dropdown { icon: i, .. }
+            | MenuItem::TextInput { icon: i, .. }
+            | MenuItem::BindingCapture { icon: i, .. } => *i = icon,
+            // Toggles show their state via `with_toggle_icons`
+            MenuItem::Image(_, _)
+            | MenuItem::Body { .. }
+            | MenuItem::Separator { .. }
+            | MenuItem::Toggle { .. }
+            | MenuItem::Radio { .. }
+            | MenuItem::Custom { .. }
+            | MenuItem::Nested { .. }
+            | MenuItem::Styled(_, _)
+            | MenuItem::Described(_, _) => (),
+        }
+        self
+    }
+
+    /// Replace parts of the style of this item, e.g. to make a "Delete" entry red.
+    /// Everything that isn't set in `style` keeps using the stylesheet
+    pub fn styled(self, style: StyleOverride) -> Self {
+        match self {
+            MenuItem::Styled(item, existing) => MenuItem::Styled(item, existing.merge(style)),
+            MenuItem::Described(item, text) => {
+                MenuItem::Described(Box::new(item.styled(style)), text)
+            }
+            item => MenuItem::Styled(Box::new(item), style),
+        }
     }
 
-    pub fn action(s: impl Into<WidgetLabel>, action: S::Action) -> Self {
-        MenuItem::Action(s.into(), MenuIcon::None, action)
+    /// Explain the item in the description pane while it is focussed, see
+    /// [`crate::style::Stylesheet::with_description_pane`]
+    pub fn with_description(self, text: impl Into<WidgetLabel>) -> Self {
+        match self {
+            MenuItem::Described(item, _) => MenuItem::Described(item, text.into()),
+            item => MenuItem::Described(Box::new(item), text.into()),
+        }
     }
 
-    pub fn label(s: impl Into<WidgetLabel>) -> Self {
-        MenuItem::Label(s.into(), MenuIcon::None)
+    /// The description of the item, if it has one
+    pub(crate) fn description(&self) -> Option<&WidgetLabel> {
+        match self {
+            MenuItem::Described(_, text) => Some(text),
+            // E.g. a described item that was styled afterwards, or the child of a collapsible
+            MenuItem::Styled(item, _) | MenuItem::Nested { item, .. } => item.description(),
+            _ => None,
+        }
     }
 
-    pub fn headline(s: impl Into<WidgetLabel>) -> Self {
-        MenuItem::Headline(s.into(), MenuIcon::None)
+    /// The item without a style override, description or nesting
+    pub(crate) fn unstyled(&self) -> &MenuItem<S> {
+        match self {
+            MenuItem::Styled(item, _)
+            | MenuItem::Described(item, _)
+            | MenuItem::Nested { item, .. } => item.unstyled(),
+            item => item,
+        }
     }
 
-    pub fn image(s: Handle<Image>) -> Self {
-        MenuItem::Image(s, None)
+    fn unstyled_mut(&mut self) -> &mut MenuItem<S> {
+        match self {
+            MenuItem::Styled(item, _)
+            | MenuItem::Described(item, _)
+            | MenuItem::Nested { item, .. } => item.unstyled_mut(),
+            item => item,
+        }
     }
 
-    pub fn with_icon(self, icon: MenuIcon) -> Self {
+    /// Whether the item is the child of a collapsed `MenuItem::Collapsible`
+    pub(crate) fn is_hidden(&self) -> bool {
         match self {
-            MenuItem::Screen(a, _, b) => MenuItem::Screen(a, icon, b),
-            MenuItem::Action(a, _, b) => MenuItem::Action(a, icon, b),
-            MenuItem::Label(a, _) => MenuItem::Label(a, icon),
-            MenuItem::Headline(a, _) => MenuItem::Headline(a, icon),
-            MenuItem::Image(a, b) => MenuItem::Image(a, b),
+            MenuItem::Nested { hidden: true, .. } => true,
+            MenuItem::Styled(item, _)
+            | MenuItem::Described(item, _)
+            | MenuItem::Nested { item, .. } => item.is_hidden(),
+            _ => false,
         }
     }
 
@@ -149,20 +1574,168 @@ where
         }
     }
 
+    /// Disabled items are rendered muted, skipped during navigation
+    /// and can't be selected
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        match self.unstyled_mut() {
+            MenuItem::Screen { disabled: d, .. }
+            | MenuItem::Action { disabled: d, .. }
+            | MenuItem::Back { disabled: d, .. }
+            | MenuItem::Slider { disabled: d, .. }
+            | MenuItem::Toggle { disabled: d, .. }
+            | MenuItem::Radio { disabled: d, .. }
+            | MenuItem::Collapsible { disabled: d, .. }
+            | MenuItem::Stepper { disabled: d, .. }
+            | MenuItem::Dropdown { disabled: d, .. }
+            | MenuItem::TextInput { disabled: d, .. }
+            | MenuItem::BindingCapture { disabled: d, .. } => *d = disabled,
+            MenuItem::Label(_, _)
+            | MenuItem::Headline(_, _)
+            | MenuItem::Body { .. }
+            | MenuItem::Image(_, _)
+            | MenuItem::Separator { .. }
+            | MenuItem::Meter { .. }
+            | MenuItem::Custom { .. }
+            | MenuItem::Nested { .. }
+            | MenuItem::Styled(_, _)
+            | MenuItem::Described(_, _) => (),
+        }
+        self
+    }
+
+    /// Repeatable actions, e.g. "+1 Gold", keep the focus on their row when selected,
+    /// also when clicked, so that they can be performed again right away. The menu is
+    /// re-rendered with the changed state. Only applies to `MenuItem::Action`
+    pub fn repeatable(mut self, repeatable: bool) -> Self {
+        if let MenuItem::Action { repeatable: r, .. } = self.unstyled_mut() {
+            *r = repeatable;
+        }
+        self
+    }
+
+    /// Ask `message` in a [`Menu::confirm`] dialog on top of the menu before the action
+    /// is performed, e.g. for "Delete Save". The action is only performed for "Yes",
+    /// "No" and `Back` close the dialog. Only applies to `MenuItem::Action`
+    pub fn with_confirm(mut self, message: impl Into<WidgetLabel>) -> Self {
+        if let MenuItem::Action { confirm, .. } = self.unstyled_mut() {
+            *confirm = Some(message.into());
+        }
+        self
+    }
+
+    pub(crate) fn is_repeatable(&self) -> bool {
+        matches!(
+            self.unstyled(),
+            MenuItem::Action {
+                repeatable: true,
+                ..
+            }
+        )
+    }
+
+    pub(crate) fn is_disabled(&self) -> bool {
+        // Hidden children are skipped like disabled items
+        if self.is_hidden() {
+            return true;
+        }
+        match self.unstyled() {
+            MenuItem::Screen { disabled: d, .. }
+            | MenuItem::Action { disabled: d, .. }
+            | MenuItem::Back { disabled: d, .. }
+            | MenuItem::Slider { disabled: d, .. }
+            | MenuItem::Toggle { disabled: d, .. }
+            | MenuItem::Radio { disabled: d, .. }
+            | MenuItem::Collapsible { disabled: d, .. }
+            | MenuItem::Stepper { disabled: d, .. }
+            | MenuItem::Dropdown { disabled: d, .. }
+            | MenuItem::TextInput { disabled: d, .. }
+            | MenuItem::BindingCapture { disabled: d, .. } => *d,
+            MenuItem::Label(_, _)
+            | MenuItem::Headline(_, _)
+            | MenuItem::Body { .. }
+            | MenuItem::Image(_, _)
+            | MenuItem::Separator { .. }
+            | MenuItem::Meter { .. }
+            | MenuItem::Custom { .. }
+            | MenuItem::Nested { .. }
+            | MenuItem::Styled(_, _)
+            | MenuItem::Described(_, _) => false,
+        }
+    }
+
     pub(crate) fn as_selection(&self) -> MenuSelection<S> {
-        match self {
-            MenuItem::Screen(_, _, a) => MenuSelection::Screen(*a),
-            MenuItem::Action(_, _, a) => MenuSelection::Action(*a),
+        match self.unstyled() {
+            MenuItem::Screen { screen, .. } => MenuSelection::Screen(*screen),
+            MenuItem::Action { action, .. } => MenuSelection::Action(*action),
+            MenuItem::Back { action, .. } => MenuSelection::Back(*action),
             MenuItem::Label(_, _) => MenuSelection::None,
             MenuItem::Headline(_, _) => MenuSelection::None,
+            MenuItem::Body { .. } => MenuSelection::None,
             MenuItem::Image(_, _) => MenuSelection::None,
+            MenuItem::Separator { .. } => MenuSelection::None,
+            MenuItem::Meter { .. } => MenuSelection::None,
+            MenuItem::Custom { .. } => MenuSelection::None,
+            MenuItem::Slider { action, .. } => MenuSelection::Action(*action),
+            MenuItem::Toggle { action, .. } => MenuSelection::Action(*action),
+            // The options of a group usually share their action
+            MenuItem::Radio {
+                action,
+                group,
+                value,
+                ..
+            } => MenuSelection::Value(*action, MenuValue::Radio(group.clone(), *value)),
+            MenuItem::Stepper { action, .. } => MenuSelection::Action(*action),
+            MenuItem::Dropdown { action, .. } => MenuSelection::Action(*action),
+            MenuItem::TextInput { action, .. } => MenuSelection::Action(*action),
+            MenuItem::BindingCapture { action, .. } => MenuSelection::Action(*action),
+            // Expanding is handled by the menu itself
+            MenuItem::Collapsible { .. } => MenuSelection::None,
+            MenuItem::Nested { .. } | MenuItem::Styled(_, _) | MenuItem::Described(_, _) => {
+                MenuSelection::None
+            }
+        }
+    }
+
+    /// The text shown for the item, if any
+    pub(crate) fn text(&self) -> Option<&WidgetLabel> {
+        match self.unstyled() {
+            MenuItem::Screen { label, .. }
+            | MenuItem::Action { label, .. }
+            | MenuItem::Back { label, .. }
+            | MenuItem::Label(label, _)
+            | MenuItem::Headline(label, _)
+            | MenuItem::Body { text: label }
+            | MenuItem::Slider { label, .. }
+            | MenuItem::Meter { label, .. }
+            | MenuItem::Toggle { label, .. }
+            | MenuItem::Radio { label, .. }
+            | MenuItem::Collapsible { label, .. }
+            | MenuItem::Stepper { label, .. }
+            | MenuItem::Dropdown { label, .. }
+            | MenuItem::TextInput { label, .. }
+            | MenuItem::BindingCapture { label, .. } => Some(label),
+            MenuItem::Image(_, _)
+            | MenuItem::Separator { .. }
+            | MenuItem::Custom { .. }
+            | MenuItem::Nested { .. }
+            | MenuItem::Styled(_, _)
+            | MenuItem::Described(_, _) => None,
         }
     }
 
     pub(crate) fn is_selectable(&self) -> bool {
         !matches!(
-            self,
-            MenuItem::Label(_, _) | MenuItem::Headline(_, _) | MenuItem::Image(_, _)
+            self.unstyled(),
+            MenuItem::Label(_, _)
+                | MenuItem::Headline(_, _)
+                | MenuItem::Body { .. }
+                | MenuItem::Image(_, _)
+                | MenuItem::Separator { .. }
+                | MenuItem::Meter { .. }
+                | MenuItem::Custom {
+                    selectable: false,
+                    ..
+                }
         )
     }
 }
@@ -173,11 +1746,82 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Screen(arg0, _, _) => f.debug_tuple("Screen").field(&arg0.debug_text()).finish(),
-            Self::Action(arg0, _, _) => f.debug_tuple("Action").field(&arg0.debug_text()).finish(),
+            Self::Screen { label, .. } => {
+                f.debug_tuple("Screen").field(&label.debug_text()).finish()
+            }
+            Self::Action { label, .. } => {
+                f.debug_tuple("Action").field(&label.debug_text()).finish()
+            }
+            Self::Back { label, .. } => f.debug_tuple("Back").field(&label.debug_text()).finish(),
             Self::Label(arg0, _) => f.debug_tuple("Label").field(&arg0.debug_text()).finish(),
             Self::Headline(arg0, _) => f.debug_tuple("Headline").field(&arg0.debug_text()).finish(),
+            Self::Body { text } => f.debug_tuple("Body").field(&text.debug_text()).finish(),
             Self::Image(arg0, _) => f.debug_tuple("Image").field(&arg0).finish(),
+            Self::Styled(item, _) | Self::Described(item, _) | Self::Nested { item, .. } => {
+                item.fmt(f)
+            }
+            Self::Collapsible {
+                label, children, ..
+            } => f
+                .debug_tuple("Collapsible")
+                .field(&label.debug_text())
+                .field(children)
+                .finish(),
+            Self::Custom { selectable, .. } => f.debug_tuple("Custom").field(selectable).finish(),
+            Self::Separator { size, line } => f
+                .debug_struct("Separator")
+                .field("size", size)
+                .field("line", line)
+                .finish(),
+            Self::Slider { label, value, .. } => f
+                .debug_tuple("Slider")
+                .field(&label.debug_text())
+                .field(value)
+                .finish(),
+            Self::Meter {
+                label, value, max, ..
+            } => f
+                .debug_tuple("Meter")
+                .field(&label.debug_text())
+                .field(value)
+                .field(max)
+                .finish(),
+            Self::Toggle { label, value, .. } => f
+                .debug_tuple("Toggle")
+                .field(&label.debug_text())
+                .field(value)
+                .finish(),
+            Self::Radio {
+                label,
+                group,
+                value,
+                ..
+            } => f
+                .debug_tuple("Radio")
+                .field(&label.debug_text())
+                .field(group)
+                .field(value)
+                .finish(),
+            Self::Stepper { label, index, .. } => f
+                .debug_tuple("Stepper")
+                .field(&label.debug_text())
+                .field(index)
+                .finish(),
+            Self::Dropdown { label, index, .. } => f
+                .debug_tuple("Dropdown")
+                .field(&label.debug_text())
+                .field(index)
+                .finish(),
+            Self::TextInput { label, value, .. } => f
+                .debug_tuple("TextInput")
+                .field(&label.debug_text())
+                .field(value)
+                .finish(),
+            Self::BindingCapture { label, binding, .. } => f
+                .debug_tuple("BindingCapture")
+                .field(&label.debug_text())
+                .field(binding)
+                .finish(),
         }
     }
 }
@@ -189,6 +1833,10 @@ where
 {
     Action(S::Action),
     Screen(S),
+    /// The value of an item was changed in place
+    Value(S::Action, MenuValue),
+    /// Go back one screen, after performing the action if there is one
+    Back(Option<S::Action>),
     None,
 }
 
@@ -200,6 +1848,8 @@ where
         match self {
             Self::Action(arg0) => Self::Action(*arg0),
             Self::Screen(arg0) => Self::Screen(*arg0),
+            Self::Value(arg0, arg1) => Self::Value(*arg0, arg1.clone()),
+            Self::Back(arg0) => Self::Back(*arg0),
             Self::None => Self::None,
         }
     }
@@ -213,6 +1863,8 @@ where
         match self {
             Self::Action(arg0) => f.debug_tuple("Action").field(&arg0).finish(),
             Self::Screen(arg0) => f.debug_tuple("Screen").field(&arg0).finish(),
+            Self::Value(arg0, arg1) => f.debug_tuple("Value").field(&arg0).field(&arg1).finish(),
+            Self::Back(arg0) => f.debug_tuple("Back").field(&arg0).finish(),
             Self::None => f.debug_tuple("None").finish(),
         }
     }
@@ -226,6 +1878,8 @@ where
         match (self, other) {
             (MenuSelection::Action(a1), MenuSelection::Action(a2)) => a1 == a2,
             (MenuSelection::Screen(s1), MenuSelection::Screen(s2)) => s1 == s2,
+            (MenuSelection::Value(a1, v1), MenuSelection::Value(a2, v2)) => a1 == a2 && v1 == v2,
+            (MenuSelection::Back(a1), MenuSelection::Back(a2)) => a1 == a2,
             (MenuSelection::None, MenuSelection::None) => true,
             _ => false,
         }
@@ -245,10 +1899,36 @@ pub enum MenuIcon {
     Players,
     Settings,
     Other(Handle<Image>),
+    /// Shows `focussed` instead of `normal` while the item is focussed, see
+    /// [`MenuIcon::with_focussed`]
+    Focus {
+        normal: Box<MenuIcon>,
+        focussed: Box<MenuIcon>,
+    },
+}
+
+/// Any image can be used as an icon, e.g. for inventories
+impl From<Handle<Image>> for MenuIcon {
+    fn from(image: Handle<Image>) -> Self {
+        MenuIcon::Other(image)
+    }
 }
 
 impl MenuIcon {
-    pub(crate) fn resolve_icon(&self, assets: &MenuAssets) -> Option<Handle<Image>> {
+    /// Show `focussed` instead of this icon while the item is focussed, e.g. a
+    /// brighter version of it. Toggles take one for each of their states
+    pub fn with_focussed(self, focussed: impl Into<MenuIcon>) -> Self {
+        MenuIcon::Focus {
+            normal: Box::new(self),
+            focussed: Box::new(focussed.into()),
+        }
+    }
+
+    pub(crate) fn resolve_icon(
+        &self,
+        assets: &MenuAssets,
+        focussed: bool,
+    ) -> Option<Handle<Image>> {
         match self {
             MenuIcon::None => None,
             MenuIcon::Checked => Some(assets.icon_checked.clone()),
@@ -259,6 +1939,10 @@ impl MenuIcon {
             MenuIcon::Players => Some(assets.icon_players.clone()),
             MenuIcon::Settings => Some(assets.icon_settings.clone()),
             MenuIcon::Other(s) => Some(s.clone()),
+            MenuIcon::Focus { focussed: icon, .. } if focussed => {
+                icon.resolve_icon(assets, focussed)
+            }
+            MenuIcon::Focus { normal: icon, .. } => icon.resolve_icon(assets, focussed),
         }
     }
 }
@@ -294,14 +1978,46 @@ impl RichTextEntry {
 pub enum WidgetLabel {
     PlainText(String),
     RichText(Vec<RichTextEntry>),
+    /// A key that is translated via the [`Localization`] resource when the menu is rendered
+    Localized {
+        key: String,
+        suffix: String,
+    },
 }
 
 impl WidgetLabel {
+    /// A label that shows the translation of `key`, see [`Localization`]
+    pub fn localized(key: impl Into<String>) -> Self {
+        Self::Localized {
+            key: key.into(),
+            suffix: String::new(),
+        }
+    }
+
+    /// This label with its key translated, if it is `Localized`
+    pub fn translated(&self, assets: &MenuAssets) -> Cow<'_, WidgetLabel> {
+        match (self, &assets.localization) {
+            (Self::Localized { key, suffix }, Some(localization)) => Cow::Owned(Self::PlainText(
+                format!("{}{suffix}", localization.translate(key)),
+            )),
+            (Self::Localized { key, suffix }, None) => {
+                Cow::Owned(Self::PlainText(format!("{key}{suffix}")))
+            }
+            _ => Cow::Borrowed(self),
+        }
+    }
+
     pub fn bundle(&self, default_font: &TextFont, default_color: &TextColor) -> Vec<impl Bundle> {
         match self {
             Self::PlainText(text) => {
                 vec![(Text(text.clone()), default_font.clone(), *default_color)]
             }
+            // Untranslated, see `translated`
+            Self::Localized { key, suffix } => vec![(
+                Text(format!("{key}{suffix}")),
+                default_font.clone(),
+                *default_color,
+            )],
             Self::RichText(entries) => entries
                 .iter()
                 .map(|entry| {
@@ -323,6 +2039,22 @@ impl WidgetLabel {
         }
     }
 
+    /// A copy of this label with `suffix` appended
+    pub fn with_suffix(&self, suffix: &str) -> Self {
+        match self {
+            Self::PlainText(text) => Self::PlainText(format!("{text}{suffix}")),
+            Self::RichText(entries) => {
+                let mut entries = entries.clone();
+                entries.push(RichTextEntry::new(suffix));
+                Self::RichText(entries)
+            }
+            Self::Localized { key, suffix: own } => Self::Localized {
+                key: key.clone(),
+                suffix: format!("{own}{suffix}"),
+            },
+        }
+    }
+
     pub fn debug_text(&self) -> String {
         match self {
             Self::PlainText(text) => text.clone(),
@@ -334,10 +2066,27 @@ impl WidgetLabel {
                 }
                 output
             }
+            Self::Localized { key, suffix } => format!("{key}{suffix}"),
         }
     }
 }
 
+/// Translates the labels created with [`WidgetLabel::localized`], e.g. with `fluent`
+/// or `rust-i18n`. Insert it as a resource. After switching the language, write a
+/// [`RedrawEvent`] to render the menu with the new translations
+#[derive(Resource, Clone)]
+pub struct Localization(std::sync::Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl Localization {
+    pub fn new(translate: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self(std::sync::Arc::new(translate))
+    }
+
+    pub fn translate(&self, key: &str) -> String {
+        (self.0)(key)
+    }
+}
+
 impl Default for WidgetLabel {
     fn default() -> Self {
         Self::PlainText(String::new())
@@ -384,6 +2133,517 @@ pub struct MenuOptions {
     pub icon_sound: Option<&'static str>,
     pub icon_players: Option<&'static str>,
     pub icon_settings: Option<&'static str>,
+    /// How long menus fade in after opening and fade out before they are removed.
+    /// Zero shows and removes them instantly
+    pub transition_duration: Duration,
+    /// How long a frame around the focussed item takes to slide to the next one.
+    /// Zero doesn't draw the frame
+    pub highlight_duration: Duration,
+    /// Show menus right away instead of waiting until the font and icons are loaded.
+    /// Only useful if they are loaded before the menu opens anyway
+    pub skip_load_check: bool,
+    /// Build at most this many rows of selectable items per frame, so that redrawing a
+    /// menu with hundreds of items doesn't stall a single frame. The previous menu
+    /// stays on screen until the new one is complete. `None` builds everything at once
+    pub max_spawns_per_frame: Option<usize>,
+}
+
+/// The frame that slides to the focussed item, see `MenuOptions::highlight_duration`
+#[derive(Component, Debug)]
+pub(crate) struct MenuHighlight {
+    from: Rect,
+    to: Rect,
+    timer: Timer,
+    shown: bool,
+}
+
+impl MenuHighlight {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            from: Rect::default(),
+            to: Rect::default(),
+            timer: Timer::new(duration, TimerMode::Once),
+            shown: false,
+        }
+    }
+
+    /// Start sliding to `target`. A highlight that wasn't shown appears there directly
+    pub fn slide_to(&mut self, target: Rect) {
+        if !self.shown {
+            self.shown = true;
+            self.from = target;
+            self.to = target;
+        } else if self.to != target {
+            self.from = self.current();
+            self.to = target;
+            self.timer.reset();
+        }
+    }
+
+    pub fn hide(&mut self) {
+        self.shown = false;
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        self.timer.tick(delta);
+    }
+
+    /// The rect between `from` and `to`, easing in and out
+    pub fn current(&self) -> Rect {
+        let t = self.timer.fraction();
+        let t = t * t * (3.0 - 2.0 * t);
+        Rect {
+            min: self.from.min.lerp(self.to.min, t),
+            max: self.from.max.lerp(self.to.max, t),
+        }
+    }
+}
+
+/// A menu that is fading in after opening or fading out before it is removed
+#[derive(Debug)]
+pub(crate) struct Fade {
+    pub timer: Timer,
+    pub closing: bool,
+}
+
+impl Fade {
+    pub fn new(duration: Duration, closing: bool) -> Self {
+        Self {
+            timer: Timer::new(duration, TimerMode::Once),
+            closing,
+        }
+    }
+
+    /// How opaque the menu currently is
+    pub fn alpha(&self) -> f32 {
+        let progress = self.timer.fraction();
+        if self.closing {
+            1.0 - progress
+        } else {
+            progress
+        }
+    }
+}
+
+/// The alpha values of an entity before it was faded
+#[derive(Component, Debug, Clone, Copy)]
+pub(crate) struct FadeAlpha {
+    pub background: f32,
+    pub text: f32,
+    pub image: f32,
+}
+
+/// Tweaks how gamepad, keyboard and mouse wheel input is translated into `NavigationEvent`s.
+/// Insert this resource before adding the [`crate::QuickMenuPlugin`] in order
+/// to override the defaults. Values out of range are replaced by their default, with a warning.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct NavigationConfig {
+    /// How far a stick has to be moved before it triggers a navigation.
+    /// Has to be in the range `(0.0, 1.0]`
+    pub activate_threshold: f32,
+    /// How far a stick has to move back before the same direction can trigger again.
+    /// Keeping this below `activate_threshold` stops a noisy stick that rests near the
+    /// threshold from firing over and over. Has to be in the range `[0.0, activate_threshold]`
+    pub release_threshold: f32,
+    /// How long a direction has to be held before it starts repeating
+    pub repeat_initial: Duration,
+    /// The time between two repeats of a held direction
+    pub repeat_interval: Duration,
+    /// Repeat held arrow keys with `repeat_initial` / `repeat_interval`
+    /// instead of ignoring the key repeat of the operating system
+    pub key_repeat: bool,
+    /// How many navigation steps one line of mouse wheel scrolling moves.
+    /// Has to be larger than `0.0`
+    pub wheel_sensitivity: f32,
+    /// Scrolling down moves the selection up, as with "natural" scrolling
+    pub natural_scrolling: bool,
+    /// Ignore an event if the same kind of event was handled less than this long ago,
+    /// e.g. a `Select` from both a click and a gamepad. Zero disables the cooldown
+    pub cooldown: Duration,
+    /// Hovering an item with the mouse also focusses it, so that keyboard and gamepad
+    /// navigation continue from there. Otherwise hover and focus are independent
+    pub hover_selects: bool,
+    /// Which stick axes navigate, e.g. to keep the right stick for the camera
+    pub stick_axes: StickAxes,
+    /// Read the analog values of the triggers: the left trigger sends `PageUp` and the right
+    /// one `PageDown`, with the same repeat as sticks. The triggers are ignored by the
+    /// `GamepadBindings` then
+    pub analog_triggers: bool,
+    /// How far a trigger has to be pressed with `analog_triggers`.
+    /// Has to be in the range `(0.0, 1.0]`
+    pub trigger_activate_threshold: f32,
+    /// How far a trigger has to be released before it can trigger again.
+    /// Has to be in the range `[0.0, trigger_activate_threshold]`
+    pub trigger_release_threshold: f32,
+}
+
+/// Enables or disables navigation for each of the four stick axes. All are enabled by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StickAxes {
+    pub left_x: bool,
+    pub left_y: bool,
+    pub right_x: bool,
+    pub right_y: bool,
+}
+
+impl Default for StickAxes {
+    fn default() -> Self {
+        Self {
+            left_x: true,
+            left_y: true,
+            right_x: true,
+            right_y: true,
+        }
+    }
+}
+
+impl StickAxes {
+    /// Only the left stick navigates
+    pub fn left() -> Self {
+        Self {
+            right_x: false,
+            right_y: false,
+            ..Default::default()
+        }
+    }
+
+    /// Only the right stick navigates
+    pub fn right() -> Self {
+        Self {
+            left_x: false,
+            left_y: false,
+            ..Default::default()
+        }
+    }
+
+    /// Whether `axis` navigates. Axes other than the sticks never do
+    pub fn is_enabled(&self, axis: GamepadAxis) -> bool {
+        match axis {
+            GamepadAxis::LeftStickX => self.left_x,
+            GamepadAxis::LeftStickY => self.left_y,
+            GamepadAxis::RightStickX => self.right_x,
+            GamepadAxis::RightStickY => self.right_y,
+            _ => false,
+        }
+    }
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        Self {
+            activate_threshold: 0.10,
+            release_threshold: 0.05,
+            repeat_initial: Duration::from_millis(400),
+            repeat_interval: Duration::from_millis(120),
+            key_repeat: false,
+            wheel_sensitivity: 1.0,
+            natural_scrolling: false,
+            cooldown: Duration::ZERO,
+            hover_selects: false,
+            stick_axes: StickAxes::default(),
+            analog_triggers: false,
+            trigger_activate_threshold: 0.5,
+            trigger_release_threshold: 0.25,
+        }
+    }
+}
+
+impl NavigationConfig {
+    /// Replaces every value that is out of range with its default and warns about it
+    pub(crate) fn validated(mut self) -> Self {
+        let defaults = Self::default();
+        if !(self.activate_threshold > 0.0 && self.activate_threshold <= 1.0) {
+            warn!(
+                "NavigationConfig::activate_threshold has to be in (0.0, 1.0], got {}, using {}",
+                self.activate_threshold, defaults.activate_threshold
+            );
+            self.activate_threshold = defaults.activate_threshold;
+        }
+        if !(self.release_threshold >= 0.0 && self.release_threshold <= self.activate_threshold) {
+            let fallback = defaults.release_threshold.min(self.activate_threshold);
+            warn!(
+                "NavigationConfig::release_threshold has to be in [0.0, activate_threshold], got {}, using {}",
+                self.release_threshold, fallback
+            );
+            self.release_threshold = fallback;
+        }
+        if !(self.trigger_activate_threshold > 0.0 && self.trigger_activate_threshold <= 1.0) {
+            warn!(
+                "NavigationConfig::trigger_activate_threshold has to be in (0.0, 1.0], got {}, using {}",
+                self.trigger_activate_threshold, defaults.trigger_activate_threshold
+            );
+            self.trigger_activate_threshold = defaults.trigger_activate_threshold;
+        }
+        if !(self.trigger_release_threshold >= 0.0
+            && self.trigger_release_threshold <= self.trigger_activate_threshold)
+        {
+            let fallback = defaults
+                .trigger_release_threshold
+                .min(self.trigger_activate_threshold);
+            warn!(
+                "NavigationConfig::trigger_release_threshold has to be in [0.0, trigger_activate_threshold], got {}, using {}",
+                self.trigger_release_threshold, fallback
+            );
+            self.trigger_release_threshold = fallback;
+        }
+        if self.repeat_interval.is_zero() {
+            warn!(
+                "NavigationConfig::repeat_interval must not be zero, using {:?}",
+                defaults.repeat_interval
+            );
+            self.repeat_interval = defaults.repeat_interval;
+        }
+        if self.wheel_sensitivity.is_nan() || self.wheel_sensitivity <= 0.0 {
+            warn!(
+                "NavigationConfig::wheel_sensitivity has to be larger than 0.0, got {}, using {}",
+                self.wheel_sensitivity, defaults.wheel_sensitivity
+            );
+            self.wheel_sensitivity = defaults.wheel_sensitivity;
+        }
+        self
+    }
+}
+
+/// Maps inputs (keys or gamepad buttons) to the `NavigationEvent` they trigger.
+/// Multiple inputs can be bound to the same event. If an input is bound to
+/// multiple events, the binding that comes first wins.
+/// Insert [`KeyBindings`] or [`GamepadBindings`] before adding the
+/// [`crate::QuickMenuPlugin`] in order to override the defaults.
+#[derive(Resource, Debug, Clone)]
+pub struct Bindings<T: Send + Sync + 'static>(pub Vec<(NavigationEvent, Vec<T>)>);
+
+/// Keyboard bindings. The default uses the arrow keys, `Enter` and `Backspace`.
+pub type KeyBindings = Bindings<KeyCode>;
+
+/// Gamepad bindings. The default uses the DPad to navigate, `South` / `West`
+/// to select and `East` / `North` to go back.
+pub type GamepadBindings = Bindings<GamepadButton>;
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self(vec![
+            (NavigationEvent::Up, vec![KeyCode::ArrowUp]),
+            (NavigationEvent::Down, vec![KeyCode::ArrowDown]),
+            (NavigationEvent::Left, vec![KeyCode::ArrowLeft]),
+            (NavigationEvent::Right, vec![KeyCode::ArrowRight]),
+            (NavigationEvent::Select, vec![KeyCode::Enter]),
+            (NavigationEvent::Back, vec![KeyCode::Backspace]),
+            (NavigationEvent::First, vec![KeyCode::Home]),
+            (NavigationEvent::Last, vec![KeyCode::End]),
+            (NavigationEvent::PageUp, vec![KeyCode::PageUp]),
+            (NavigationEvent::PageDown, vec![KeyCode::PageDown]),
+            (NavigationEvent::TypeNumber, vec![KeyCode::F2]),
+        ])
+    }
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        Self(vec![
+            (NavigationEvent::Down, vec![GamepadButton::DPadDown]),
+            (NavigationEvent::Up, vec![GamepadButton::DPadUp]),
+            (NavigationEvent::Left, vec![GamepadButton::DPadLeft]),
+            (NavigationEvent::Right, vec![GamepadButton::DPadRight]),
+            (
+                NavigationEvent::Select,
+                vec![GamepadButton::South, GamepadButton::West],
+            ),
+            (
+                NavigationEvent::Back,
+                vec![GamepadButton::East, GamepadButton::North],
+            ),
+            (
+                NavigationEvent::PreviousTab,
+                vec![GamepadButton::LeftTrigger],
+            ),
+            (NavigationEvent::NextTab, vec![GamepadButton::RightTrigger]),
+            (NavigationEvent::PageUp, vec![GamepadButton::LeftTrigger2]),
+            (
+                NavigationEvent::PageDown,
+                vec![GamepadButton::RightTrigger2],
+            ),
+        ])
+    }
+}
+
+impl<T> Bindings<T>
+where
+    T: Copy + PartialEq + Send + Sync + 'static,
+{
+    /// Replace the inputs bound to `event`
+    pub fn bind(&mut self, event: NavigationEvent, inputs: impl Into<Vec<T>>) {
+        let inputs = inputs.into();
+        match self.0.iter_mut().find(|(e, _)| *e == event) {
+            Some((_, existing)) => *existing = inputs,
+            None => self.0.push((event, inputs)),
+        }
+    }
+
+    pub fn with_binding(mut self, event: NavigationEvent, inputs: impl Into<Vec<T>>) -> Self {
+        self.bind(event, inputs);
+        self
+    }
+
+    /// The event an input is bound to
+    pub fn event_for(&self, input: T) -> Option<NavigationEvent> {
+        self.0
+            .iter()
+            .find(|(_, inputs)| inputs.contains(&input))
+            .map(|(event, _)| *event)
+    }
+
+    /// The first input bound to `event`, e.g. to show it in a prompt
+    pub fn first_input(&self, event: NavigationEvent) -> Option<T> {
+        self.0
+            .iter()
+            .find(|(e, _)| *e == event)
+            .and_then(|(_, inputs)| inputs.first().copied())
+    }
+
+    /// The first event for which `pressed` returns true for any bound input
+    pub fn first_pressed(&self, pressed: impl Fn(T) -> bool) -> Option<NavigationEvent> {
+        self.first_pressed_input(pressed).map(|(event, _)| event)
+    }
+
+    /// Like `first_pressed`, but also returns the input that was pressed
+    pub fn first_pressed_input(&self, pressed: impl Fn(T) -> bool) -> Option<(NavigationEvent, T)> {
+        self.0.iter().find_map(|(event, inputs)| {
+            inputs
+                .iter()
+                .find(|input| pressed(**input))
+                .map(|input| (*event, *input))
+        })
+    }
+}
+
+/// The button labels of a kind of gamepad, e.g. `A` on Xbox is `Cross` on PlayStation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GamepadLayout {
+    Xbox,
+    PlayStation,
+    Switch,
+    /// Unknown gamepads use the names of `GamepadButton`
+    #[default]
+    Generic,
+}
+
+impl GamepadLayout {
+    /// The layout of a connected gamepad, based on its USB vendor
+    pub fn from_gamepad(gamepad: &Gamepad) -> Self {
+        gamepad
+            .vendor_id()
+            .map(Self::from_vendor_id)
+            .unwrap_or_default()
+    }
+
+    pub fn from_vendor_id(vendor_id: u16) -> Self {
+        match vendor_id {
+            0x045e => GamepadLayout::Xbox,
+            0x054c => GamepadLayout::PlayStation,
+            0x057e => GamepadLayout::Switch,
+            _ => GamepadLayout::Generic,
+        }
+    }
+
+    /// The name that is printed on `button`
+    pub fn label(&self, button: GamepadButton) -> &'static str {
+        use GamepadButton::*;
+        use GamepadLayout::*;
+        match (self, button) {
+            (Xbox, South) | (Switch, East) => "A",
+            (Xbox, East) | (Switch, South) => "B",
+            (Xbox, West) | (Switch, North) => "X",
+            (Xbox, North) | (Switch, West) => "Y",
+            (PlayStation, South) => "Cross",
+            (PlayStation, East) => "Circle",
+            (PlayStation, West) => "Square",
+            (PlayStation, North) => "Triangle",
+            (Generic, South) => "South",
+            (Generic, East) => "East",
+            (Generic, West) => "West",
+            (Generic, North) => "North",
+            (PlayStation, LeftTrigger) => "L1",
+            (PlayStation, LeftTrigger2) => "L2",
+            (PlayStation, RightTrigger) => "R1",
+            (PlayStation, RightTrigger2) => "R2",
+            (Switch, LeftTrigger) => "L",
+            (Switch, LeftTrigger2) => "ZL",
+            (Switch, RightTrigger) => "R",
+            (Switch, RightTrigger2) => "ZR",
+            (_, LeftTrigger) => "LB",
+            (_, LeftTrigger2) => "LT",
+            (_, RightTrigger) => "RB",
+            (_, RightTrigger2) => "RT",
+            (Xbox, Select) => "View",
+            (Xbox, Start) => "Menu",
+            (PlayStation, Select) => "Share",
+            (PlayStation, Start) => "Options",
+            (Switch, Select) => "-",
+            (Switch, Start) => "+",
+            (_, Select) => "Select",
+            (_, Start) => "Start",
+            (Xbox, Mode) => "Xbox",
+            (PlayStation, Mode) => "PS",
+            (Switch, Mode) => "Home",
+            (Generic, Mode) => "Mode",
+            (PlayStation, LeftThumb) => "L3",
+            (PlayStation, RightThumb) => "R3",
+            (_, LeftThumb) => "LS",
+            (_, RightThumb) => "RS",
+            (_, DPadUp) => "Up",
+            (_, DPadDown) => "Down",
+            (_, DPadLeft) => "Left",
+            (_, DPadRight) => "Right",
+            (_, C) => "C",
+            (_, Z) => "Z",
+            (_, Other(_)) => "?",
+        }
+    }
+}
+
+/// Images for gamepad buttons, used instead of the `GamepadLayout::label` where available.
+/// Insert it before adding the [`crate::QuickMenuPlugin`]
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GamepadGlyphs {
+    /// Use this layout for all gamepads instead of detecting it, e.g. from a setting
+    pub layout: Option<GamepadLayout>,
+    images: HashMap<(GamepadLayout, GamepadButton), Handle<Image>>,
+}
+
+impl GamepadGlyphs {
+    pub fn with_glyph(
+        mut self,
+        layout: GamepadLayout,
+        button: GamepadButton,
+        image: Handle<Image>,
+    ) -> Self {
+        self.images.insert((layout, button), image);
+        self
+    }
+
+    pub fn with_layout(mut self, layout: GamepadLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// The layout to show for `gamepad`
+    pub fn layout_for(&self, gamepad: &Gamepad) -> GamepadLayout {
+        self.layout
+            .unwrap_or_else(|| GamepadLayout::from_gamepad(gamepad))
+    }
+
+    pub fn glyph(&self, layout: GamepadLayout, button: GamepadButton) -> Option<Handle<Image>> {
+        self.images.get(&(layout, button)).cloned()
+    }
+}
+
+/// One entry of the prompt bar, e.g. `A Select`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Prompt {
+    /// Shown instead of `input` if available
+    pub glyph: Option<Handle<Image>>,
+    pub input: String,
+    pub action: &'static str,
 }
 
 #[derive(Resource)]
@@ -396,6 +2656,8 @@ pub struct MenuAssets {
     pub icon_sound: Handle<Image>,
     pub icon_players: Handle<Image>,
     pub icon_settings: Handle<Image>,
+    /// A copy of the `Localization` resource, so that widgets can translate labels
+    pub(crate) localization: Option<Localization>,
 }
 
 impl FromWorld for MenuAssets {
@@ -476,6 +2738,7 @@ impl FromWorld for MenuAssets {
         );
 
         Self {
+            localization: world.get_resource::<Localization>().cloned(),
             font,
             icon_checked,
             icon_unchecked,
@@ -488,6 +2751,30 @@ impl FromWorld for MenuAssets {
     }
 }
 
+impl MenuAssets {
+    /// Whether the font or an icon is still being loaded by the `AssetServer`.
+    /// Failed assets count as done, so that a menu is still shown
+    pub(crate) fn is_loading(&self, server: &AssetServer) -> bool {
+        let images = [
+            &self.icon_checked,
+            &self.icon_unchecked,
+            &self.icon_back,
+            &self.icon_controls,
+            &self.icon_sound,
+            &self.icon_players,
+            &self.icon_settings,
+        ];
+        std::iter::once(self.font.id().untyped())
+            .chain(images.into_iter().map(|image| image.id().untyped()))
+            .any(|id| {
+                matches!(
+                    server.get_load_state(id),
+                    Some(LoadState::NotLoaded | LoadState::Loading)
+                )
+            })
+    }
+}
+
 #[derive(Eq, Clone)]
 pub struct WidgetId {
     id: Cow<'static, str>,
@@ -515,6 +2802,20 @@ impl PartialEq for WidgetId {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for WidgetId {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.serialize_str(&self.id)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WidgetId {
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        String::deserialize(deserializer).map(WidgetId::new)
+    }
+}
+
 impl WidgetId {
     /// Creates a new [`Name`] from any string-like type.
     ///
@@ -564,19 +2865,127 @@ impl<T: Into<Cow<'static, str>>> From<T> for WidgetId {
     }
 }
 
+/// Repeats a held navigation direction, similar to the key repeat of an OS.
+/// The first repeat happens after `NavigationConfig::repeat_initial`, every
+/// following one after `NavigationConfig::repeat_interval`.
+#[derive(Debug, Default)]
+pub struct NavigationRepeat {
+    held: Option<NavigationEvent>,
+    elapsed: Duration,
+    repeats: u32,
+}
+
+impl NavigationRepeat {
+    /// Start holding `event`. Holding a different event restarts the initial delay
+    pub fn hold(&mut self, event: NavigationEvent) {
+        *self = Self {
+            held: Some(event),
+            ..Default::default()
+        };
+    }
+
+    pub fn release(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Advance the timer and return the repeats that are due
+    pub fn tick(&mut self, delta: Duration, config: &NavigationConfig) -> Vec<NavigationEvent> {
+        let Some(event) = self.held else {
+            return Vec::new();
+        };
+        self.elapsed += delta;
+        let Some(repeating) = self.elapsed.checked_sub(config.repeat_initial) else {
+            return Vec::new();
+        };
+        let due = (repeating.as_nanos() / config.repeat_interval.as_nanos()) as u32 + 1;
+        let new = due.saturating_sub(self.repeats);
+        self.repeats = due;
+        vec![event; new as usize]
+    }
+}
+
 #[derive(Component)]
 pub struct GamepadActivation {
-    previous_axis_activation: HashMap<GamepadAxis, f32>,
+    /// The direction each axis or trigger fired last, until it moves back below the
+    /// release threshold
+    active_direction: HashMap<GamepadInput, NavigationEvent>,
+    axis_repeat: HashMap<GamepadInput, NavigationRepeat>,
+    /// The direction button that is held, e.g. on the D-pad
+    held_button: Option<GamepadButton>,
+    button_repeat: NavigationRepeat,
 }
 
 impl GamepadActivation {
     pub fn new() -> Self {
         Self {
-            previous_axis_activation: HashMap::new(),
+            active_direction: HashMap::new(),
+            axis_repeat: HashMap::new(),
+            held_button: None,
+            button_repeat: NavigationRepeat::default(),
+        }
+    }
+
+    /// Feed a new `value` of an axis or trigger and return the direction if it was triggered.
+    /// A direction triggers once when the value moves past `activate`, and
+    /// only re-arms after the value moved back below `release`
+    pub fn update(
+        &mut self,
+        k: GamepadInput,
+        value: f32,
+        (positive, negative): (NavigationEvent, NavigationEvent),
+        (activate, release): (f32, f32),
+    ) -> Option<NavigationEvent> {
+        let direction = if value > activate {
+            positive
+        } else if value < -activate {
+            negative
+        } else {
+            if value.abs() < release {
+                self.release(k);
+            }
+            return None;
+        };
+        if self.active_direction.get(&k) == Some(&direction) {
+            return None;
+        }
+        self.hold(k, direction);
+        Some(direction)
+    }
+
+    /// An axis moved past the threshold into the direction of `event`
+    fn hold(&mut self, k: GamepadInput, event: NavigationEvent) {
+        self.active_direction.insert(k, event);
+        self.axis_repeat.entry(k).or_default().hold(event);
+    }
+
+    /// An axis moved back into the deadzone
+    fn release(&mut self, k: GamepadInput) {
+        self.active_direction.remove(&k);
+        if let Some(repeat) = self.axis_repeat.get_mut(&k) {
+            repeat.release();
+        }
+    }
+
+    /// A button for the direction `event` was pressed. Holding it repeats `event`
+    pub fn hold_button(&mut self, button: GamepadButton, event: NavigationEvent) {
+        self.held_button = Some(button);
+        self.button_repeat.hold(event);
+    }
+
+    /// Stop repeating the held button once `released` returns true for it
+    pub fn release_button(&mut self, released: impl Fn(GamepadButton) -> bool) {
+        if self.held_button.is_some_and(released) {
+            self.held_button = None;
+            self.button_repeat.release();
         }
     }
 
-    pub fn insert(&mut self, k: GamepadAxis, v: f32) -> f32 {
-        self.previous_axis_activation.insert(k, v).unwrap_or(0.0)
+    /// Advance all held axes and buttons and collect the repeated events
+    pub fn tick(&mut self, delta: Duration, config: &NavigationConfig) -> Vec<NavigationEvent> {
+        self.axis_repeat
+            .values_mut()
+            .chain(std::iter::once(&mut self.button_repeat))
+            .flat_map(|repeat| repeat.tick(delta, config))
+            .collect()
     }
 }
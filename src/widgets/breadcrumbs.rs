@@ -0,0 +1,100 @@
+use super::{row_direction, Widget};
+use crate::style::StyleEntry;
+use crate::types::{BreadcrumbComponent, MenuAssets};
+use crate::ScreenTrait;
+use bevy::prelude::*;
+
+/// The titles of the shown screens, above the menu. All but the topmost
+/// screen can be clicked to go back to them
+pub(crate) struct BreadcrumbsWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    screens: &'a [S],
+    title: fn(&S) -> String,
+    style: &'a StyleEntry,
+    rtl: bool,
+}
+
+impl<'a, S> BreadcrumbsWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn new(
+        screens: &'a [S],
+        title: fn(&S) -> String,
+        style: &'a StyleEntry,
+        rtl: bool,
+    ) -> Self {
+        Self {
+            screens,
+            title,
+            style,
+            rtl,
+        }
+    }
+}
+
+impl<'a, S> Widget for BreadcrumbsWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        let BreadcrumbsWidget {
+            screens,
+            title,
+            style,
+            rtl,
+        } = self;
+
+        let font = style.text_font(assets, None);
+        let separator = if rtl { "<" } else { ">" };
+
+        parent
+            .spawn(Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Percent(100.0),
+                left: if rtl { Val::Auto } else { Val::Px(0.0) },
+                right: if rtl { Val::Px(0.0) } else { Val::Auto },
+                flex_direction: row_direction(rtl),
+                align_items: AlignItems::Center,
+                margin: style.margin,
+                ..default()
+            })
+            .with_children(|parent| {
+                let last = screens.len() - 1;
+                for (depth, screen) in screens.iter().enumerate() {
+                    if depth > 0 {
+                        parent.spawn((
+                            Text(separator.to_string()),
+                            font.clone(),
+                            TextColor(style.disabled.fg),
+                        ));
+                    }
+                    let colors = if depth == last {
+                        style.selected
+                    } else {
+                        style.normal
+                    };
+                    let mut crumb = parent.spawn((
+                        Node {
+                            padding: style.padding,
+                            ..default()
+                        },
+                        BackgroundColor(colors.bg),
+                    ));
+                    crumb.with_child((Text(title(screen)), font.clone(), TextColor(colors.fg)));
+                    // The topmost screen is already shown
+                    if depth < last {
+                        crumb.insert((
+                            Button,
+                            BreadcrumbComponent {
+                                depth,
+                                screen: *screen,
+                            },
+                        ));
+                    }
+                }
+            });
+    }
+}
@@ -13,6 +13,7 @@ where
     menu_identifier: (WidgetId, usize),
     selection: &'a MenuSelection<S>,
     selected: bool,
+    disabled: bool,
 }
 
 impl<'a, S> ButtonWidget<'a, S>
@@ -32,8 +33,15 @@ where
             menu_identifier,
             selection,
             selected,
+            disabled: false,
         }
     }
+
+    /// Render the widget muted and ignore mouse input
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
 }
 
 impl<'a, A, S, State> Widget for ButtonWidget<'a, S>
@@ -49,41 +57,48 @@ where
             menu_identifier,
             selection,
             selected,
+            disabled,
         } = self;
 
-        let (bg, fg) = if selected {
+        let (bg, fg) = if disabled {
+            (style.disabled.bg, style.disabled.fg)
+        } else if selected {
             (style.selected.bg, style.selected.fg)
         } else {
             (style.normal.bg, style.normal.fg)
         };
 
-        let font = TextFont {
-            font: assets.font.clone(),
-            font_size: style.size,
-            ..Default::default()
-        };
+        let font = style.text_font(
+            assets,
+            (selected && !disabled).then_some(&style.selected_font),
+        );
         let color = TextColor(fg);
 
-        parent
-            .spawn((
-                Node {
-                    margin: style.margin,
-                    padding: style.padding,
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                BackgroundColor(bg),
-            ))
+        let mut button = parent.spawn((
+            Node {
+                margin: style.margin,
+                padding: style.padding,
+                min_height: style.min_height,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(bg),
+        ));
+        if let Some(images) = &style.images {
+            button.insert(images.state(selected && !disabled).clone());
+        }
+        button
             .insert(ButtonComponent {
                 style: style.clone(),
                 selection: selection.clone(),
                 menu_identifier,
                 selected,
+                disabled,
             })
             .with_children(|parent| {
-                for bundle in text.bundle(&font, &color) {
-                    parent.spawn(bundle);
+                for bundle in text.translated(assets).bundle(&font, &color) {
+                    parent.spawn((bundle, style.text_layout()));
                 }
             });
     }
@@ -0,0 +1,91 @@
+use super::Widget;
+use crate::style::StyleEntry;
+use crate::types::{ButtonComponent, CustomBuild, MenuAssets, WidgetId};
+use crate::{ActionTrait, MenuSelection, ScreenTrait};
+use bevy::prelude::*;
+
+/// Hosts the entities spawned by a `MenuItem::Custom`. Selectable rows
+/// are wrapped in a `ButtonComponent` so that focus and hover work on them
+pub struct CustomWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    build: &'a CustomBuild,
+    style: &'a StyleEntry,
+    menu_identifier: (WidgetId, usize),
+    selection: &'a MenuSelection<S>,
+    selectable: bool,
+    selected: bool,
+}
+
+impl<'a, S> CustomWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn new(
+        build: &'a CustomBuild,
+        style: &'a StyleEntry,
+        menu_identifier: (WidgetId, usize),
+        selection: &'a MenuSelection<S>,
+        selectable: bool,
+        selected: bool,
+    ) -> Self {
+        Self {
+            build,
+            style,
+            menu_identifier,
+            selection,
+            selectable,
+            selected,
+        }
+    }
+}
+
+impl<'a, A, S, State> Widget for CustomWidget<'a, S>
+where
+    State: 'static,
+    A: ActionTrait<State = State> + 'static,
+    S: ScreenTrait<Action = A> + 'static,
+{
+    fn build(self, parent: &mut ChildSpawnerCommands, _assets: &MenuAssets) {
+        let CustomWidget {
+            build,
+            style,
+            menu_identifier,
+            selection,
+            selectable,
+            selected,
+        } = self;
+
+        let bg = if selectable && selected {
+            style.selected.bg
+        } else {
+            style.normal.bg
+        };
+
+        let mut row = parent.spawn((
+            Node {
+                margin: style.margin,
+                padding: style.padding,
+                min_height: style.min_height,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(bg),
+        ));
+        if let Some(images) = &style.images {
+            row.insert(images.state(selectable && selected).clone());
+        }
+        if selectable {
+            row.insert(ButtonComponent {
+                style: style.clone(),
+                selection: selection.clone(),
+                menu_identifier,
+                selected,
+                disabled: false,
+            });
+        }
+        row.with_children(|parent| build(parent));
+    }
+}
@@ -0,0 +1,46 @@
+use super::{LabelWidget, Widget};
+use crate::style::StyleEntry;
+use crate::types::{DescriptionPane, MenuAssets, WidgetLabel};
+use bevy::prelude::*;
+
+/// The description of the focussed item, placed by the stylesheet's `description_pane`
+pub(crate) struct DescriptionPaneWidget<'a> {
+    node: &'a Node,
+    description: Option<&'a WidgetLabel>,
+    style: &'a StyleEntry,
+}
+
+impl<'a> DescriptionPaneWidget<'a> {
+    pub fn new(
+        node: &'a Node,
+        description: Option<&'a WidgetLabel>,
+        style: &'a StyleEntry,
+    ) -> Self {
+        Self {
+            node,
+            description,
+            style,
+        }
+    }
+
+    /// Replace the text of an existing pane, e.g. because the focus moved
+    pub fn rebuild(self, pane: &mut EntityCommands, assets: &MenuAssets) {
+        pane.despawn_related::<Children>()
+            .with_children(|parent| self.add_text(parent, assets));
+    }
+
+    fn add_text(&self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        // Items without a description leave the pane blank
+        if let Some(description) = self.description {
+            LabelWidget::new(description, self.style).build(parent, assets);
+        }
+    }
+}
+
+impl<'a> Widget for DescriptionPaneWidget<'a> {
+    fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        parent
+            .spawn((self.node.clone(), DescriptionPane))
+            .with_children(|parent| self.add_text(parent, assets));
+    }
+}
@@ -0,0 +1,123 @@
+use super::{ButtonWidget, Widget};
+use crate::style::StyleEntry;
+use crate::types::{DropdownOverlay, MenuAssets, MenuValue, WidgetId, WidgetLabel};
+use crate::{ActionTrait, MenuSelection, ScreenTrait};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+pub struct DropdownWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    text: &'a WidgetLabel,
+    style: &'a StyleEntry,
+    menu_identifier: (WidgetId, usize),
+    selection: &'a MenuSelection<S>,
+    selected: bool,
+    disabled: bool,
+    action: S::Action,
+    options: &'a [String],
+    /// The highlighted option and the background of the list, while it is open
+    open: Option<(usize, Color)>,
+}
+
+impl<'a, S> DropdownWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn new(
+        text: &'a WidgetLabel,
+        style: &'a StyleEntry,
+        menu_identifier: (WidgetId, usize),
+        selection: &'a MenuSelection<S>,
+        selected: bool,
+        action: S::Action,
+        options: &'a [String],
+    ) -> Self {
+        Self {
+            text,
+            style,
+            menu_identifier,
+            selection,
+            selected,
+            disabled: false,
+            action,
+            options,
+            open: None,
+        }
+    }
+
+    /// Render the widget muted and ignore mouse input
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Show the list of options on a `background`, with `highlighted` styled as selected
+    pub fn with_open(mut self, highlighted: usize, background: Color) -> Self {
+        self.open = Some((highlighted, background));
+        self
+    }
+}
+
+impl<'a, A, S, State> Widget for DropdownWidget<'a, S>
+where
+    State: 'static,
+    A: ActionTrait<State = State> + 'static,
+    S: ScreenTrait<Action = A> + 'static,
+{
+    fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        let DropdownWidget {
+            text,
+            style,
+            menu_identifier,
+            selection,
+            selected,
+            disabled,
+            action,
+            options,
+            open,
+        } = self;
+
+        // The list is placed relative to this node
+        parent.spawn(Node::default()).with_children(|parent| {
+            ButtonWidget::new(text, style, menu_identifier.clone(), selection, selected)
+                .with_disabled(disabled)
+                .build(parent, assets);
+            let Some((highlighted, background)) = open else {
+                return;
+            };
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Percent(100.0),
+                        left: Val::Px(0.0),
+                        min_width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    BackgroundColor(background),
+                    // Above the rest of the menu, below the highlight frame
+                    GlobalZIndex(i32::MAX - 1),
+                    FocusPolicy::Block,
+                    Visibility::Hidden,
+                    DropdownOverlay::default(),
+                ))
+                .with_children(|parent| {
+                    for (index, option) in options.iter().enumerate() {
+                        // Clicking an option chooses it, see `press_button`
+                        let selection = MenuSelection::<S>::Value(action, MenuValue::Index(index));
+                        ButtonWidget::new(
+                            &WidgetLabel::from(option),
+                            style,
+                            menu_identifier.clone(),
+                            &selection,
+                            index == highlighted,
+                        )
+                        .build(parent, assets);
+                    }
+                });
+        });
+    }
+}
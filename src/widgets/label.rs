@@ -20,11 +20,7 @@ impl<'a> Widget for LabelWidget<'a> {
 
         let (bg, fg) = (style.normal.bg, style.selected.fg);
 
-        let font = TextFont {
-            font: assets.font.clone(),
-            font_size: style.size,
-            ..default()
-        };
+        let font = style.text_font(assets, None);
 
         let color = TextColor(fg);
 
@@ -33,6 +29,7 @@ impl<'a> Widget for LabelWidget<'a> {
                 Node {
                     margin: style.margin,
                     padding: style.padding,
+                    min_height: style.min_height,
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
@@ -40,8 +37,8 @@ impl<'a> Widget for LabelWidget<'a> {
                 BackgroundColor(bg),
             ))
             .with_children(|parent| {
-                for child in text.bundle(&font, &color) {
-                    parent.spawn(child);
+                for child in text.translated(assets).bundle(&font, &color) {
+                    parent.spawn((child, style.text_layout()));
                 }
             });
     }
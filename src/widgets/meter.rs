@@ -0,0 +1,104 @@
+use super::{row_direction, Widget};
+use crate::style::StyleEntry;
+use crate::types::{MenuAssets, WidgetLabel};
+use bevy::prelude::*;
+
+/// A read-only bar, e.g. for health or download progress
+pub struct MeterWidget<'a> {
+    text: &'a WidgetLabel,
+    style: &'a StyleEntry,
+    /// How much of the bar is filled, from `0.0` to `1.0`
+    fraction: f32,
+    /// The formatted value
+    readout: String,
+    /// Fill the bar from the right and show the readout on the left
+    rtl: bool,
+    /// The color of the filled part, the text color if not set
+    color: Option<Color>,
+}
+
+impl<'a> MeterWidget<'a> {
+    pub fn new(
+        text: &'a WidgetLabel,
+        style: &'a StyleEntry,
+        fraction: f32,
+        readout: String,
+        color: Option<Color>,
+    ) -> Self {
+        Self {
+            text,
+            style,
+            fraction,
+            readout,
+            color,
+            rtl: false,
+        }
+    }
+
+    /// Lay the widget out from right to left
+    pub fn with_rtl(mut self, rtl: bool) -> Self {
+        self.rtl = rtl;
+        self
+    }
+}
+
+impl<'a> Widget for MeterWidget<'a> {
+    fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        let MeterWidget {
+            text,
+            style,
+            fraction,
+            readout,
+            color: fill,
+            rtl,
+        } = self;
+
+        let (bg, fg) = (style.normal.bg, style.selected.fg);
+
+        let font = style.text_font(assets, None);
+        let color = TextColor(fg);
+        let fill = fill.unwrap_or(fg);
+
+        parent
+            .spawn((
+                Node {
+                    margin: style.margin,
+                    padding: style.padding,
+                    min_height: style.min_height,
+                    flex_direction: row_direction(rtl),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(bg),
+            ))
+            .with_children(|parent| {
+                for bundle in text.translated(assets).bundle(&font, &color) {
+                    parent.spawn((bundle, style.text_layout()));
+                }
+                // The bar, sized relative to the font
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(style.size * 5.0),
+                            height: Val::Px(style.size / 3.0),
+                            margin: UiRect::horizontal(Val::Px(style.size / 2.0)),
+                            flex_direction: row_direction(rtl),
+                            ..default()
+                        },
+                        BackgroundColor(fill.with_alpha(0.3)),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Percent(fraction.clamp(0.0, 1.0) * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(fill),
+                        ));
+                    });
+                parent.spawn((Text(readout), font.clone(), color));
+            });
+    }
+}
@@ -1,9 +1,26 @@
+mod breadcrumbs;
 mod button;
+mod custom;
+mod description_pane;
+mod dropdown;
 mod label;
+mod meter;
+mod prompt_bar;
+mod slider;
+mod tab_bar;
 mod vertical_menu;
 mod widget;
 
+pub(crate) use breadcrumbs::BreadcrumbsWidget;
 pub use button::ButtonWidget;
+pub use custom::CustomWidget;
+pub(crate) use description_pane::DescriptionPaneWidget;
+pub use dropdown::DropdownWidget;
 pub use label::LabelWidget;
+pub use meter::MeterWidget;
+pub(crate) use prompt_bar::PromptBarWidget;
+pub use slider::SliderWidget;
+pub(crate) use tab_bar::TabBarWidget;
 pub use vertical_menu::VerticalMenu;
+pub(crate) use widget::row_direction;
 pub use widget::Widget;
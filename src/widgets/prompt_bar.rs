@@ -0,0 +1,62 @@
+use super::Widget;
+use crate::style::StyleEntry;
+use crate::types::{MenuAssets, Prompt};
+use bevy::prelude::*;
+
+/// The inputs for `Select` and `Back`, below the menu
+pub(crate) struct PromptBarWidget<'a> {
+    prompts: &'a [Prompt],
+    style: &'a StyleEntry,
+}
+
+impl<'a> PromptBarWidget<'a> {
+    pub fn new(prompts: &'a [Prompt], style: &'a StyleEntry) -> Self {
+        Self { prompts, style }
+    }
+}
+
+impl<'a> Widget for PromptBarWidget<'a> {
+    fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        let PromptBarWidget { prompts, style } = self;
+
+        let font = style.text_font(assets, None);
+        let color = TextColor(style.normal.fg);
+
+        parent
+            .spawn(Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.0),
+                left: Val::Px(0.0),
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(style.size),
+                padding: style.padding,
+                ..default()
+            })
+            .with_children(|parent| {
+                for prompt in prompts {
+                    parent
+                        .spawn(Node {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(style.size / 3.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            match &prompt.glyph {
+                                Some(glyph) => parent.spawn((
+                                    Node {
+                                        width: Val::Px(style.size),
+                                        height: Val::Px(style.size),
+                                        ..default()
+                                    },
+                                    ImageNode::new(glyph.clone()),
+                                )),
+                                None => {
+                                    parent.spawn((Text(prompt.input.clone()), font.clone(), color))
+                                }
+                            };
+                            parent.spawn((Text(prompt.action.to_string()), font.clone(), color));
+                        });
+                }
+            });
+    }
+}
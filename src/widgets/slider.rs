@@ -0,0 +1,149 @@
+use super::{row_direction, Widget};
+use crate::style::StyleEntry;
+use crate::types::{ButtonComponent, MenuAssets, WidgetId, WidgetLabel};
+use crate::{ActionTrait, MenuSelection, ScreenTrait};
+use bevy::prelude::*;
+
+pub struct SliderWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    text: &'a WidgetLabel,
+    style: &'a StyleEntry,
+    menu_identifier: (WidgetId, usize),
+    selection: &'a MenuSelection<S>,
+    selected: bool,
+    disabled: bool,
+    /// How much of the bar is filled, from `0.0` to `1.0`
+    fraction: f32,
+    /// The formatted value
+    readout: String,
+    /// Fill the bar from the right and show the readout on the left
+    rtl: bool,
+}
+
+impl<'a, S> SliderWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn new(
+        text: &'a WidgetLabel,
+        style: &'a StyleEntry,
+        menu_identifier: (WidgetId, usize),
+        selection: &'a MenuSelection<S>,
+        selected: bool,
+        fraction: f32,
+        readout: String,
+    ) -> Self {
+        Self {
+            text,
+            style,
+            menu_identifier,
+            selection,
+            selected,
+            fraction,
+            readout,
+            disabled: false,
+            rtl: false,
+        }
+    }
+
+    /// Render the widget muted and ignore mouse input
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Lay the widget out from right to left
+    pub fn with_rtl(mut self, rtl: bool) -> Self {
+        self.rtl = rtl;
+        self
+    }
+}
+
+impl<'a, A, S, State> Widget for SliderWidget<'a, S>
+where
+    State: 'static,
+    A: ActionTrait<State = State> + 'static,
+    S: ScreenTrait<Action = A> + 'static,
+{
+    fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        let SliderWidget {
+            text,
+            style,
+            menu_identifier,
+            selection,
+            selected,
+            disabled,
+            fraction,
+            readout,
+            rtl,
+        } = self;
+
+        let (bg, fg) = if disabled {
+            (style.disabled.bg, style.disabled.fg)
+        } else if selected {
+            (style.selected.bg, style.selected.fg)
+        } else {
+            (style.normal.bg, style.normal.fg)
+        };
+
+        let font = style.text_font(
+            assets,
+            (selected && !disabled).then_some(&style.selected_font),
+        );
+        let color = TextColor(fg);
+
+        let mut button = parent.spawn((
+            Node {
+                margin: style.margin,
+                padding: style.padding,
+                min_height: style.min_height,
+                flex_direction: row_direction(rtl),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(bg),
+        ));
+        if let Some(images) = &style.images {
+            button.insert(images.state(selected && !disabled).clone());
+        }
+        button
+            .insert(ButtonComponent {
+                style: style.clone(),
+                selection: selection.clone(),
+                menu_identifier,
+                selected,
+                disabled,
+            })
+            .with_children(|parent| {
+                for bundle in text.translated(assets).bundle(&font, &color) {
+                    parent.spawn((bundle, style.text_layout()));
+                }
+                // The bar, sized relative to the font
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(style.size * 5.0),
+                            height: Val::Px(style.size / 3.0),
+                            margin: UiRect::horizontal(Val::Px(style.size / 2.0)),
+                            flex_direction: row_direction(rtl),
+                            ..default()
+                        },
+                        BackgroundColor(fg.with_alpha(0.3)),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Percent(fraction.clamp(0.0, 1.0) * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(fg),
+                        ));
+                    });
+                parent.spawn((Text(readout), font.clone(), color));
+            });
+    }
+}
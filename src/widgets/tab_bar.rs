@@ -0,0 +1,79 @@
+use super::{row_direction, Widget};
+use crate::style::StyleEntry;
+use crate::types::{MenuAssets, TabComponent, WidgetLabel};
+use crate::ScreenTrait;
+use bevy::prelude::*;
+
+/// The tabs above a menu, see `Menu::with_tabs`. The tab of `active` is
+/// highlighted, the others can be clicked
+pub(crate) struct TabBarWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    tabs: &'a [(WidgetLabel, S)],
+    active: S,
+    style: &'a StyleEntry,
+    rtl: bool,
+}
+
+impl<'a, S> TabBarWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn new(tabs: &'a [(WidgetLabel, S)], active: S, style: &'a StyleEntry, rtl: bool) -> Self {
+        Self {
+            tabs,
+            active,
+            style,
+            rtl,
+        }
+    }
+}
+
+impl<'a, S> Widget for TabBarWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets) {
+        let TabBarWidget {
+            tabs,
+            active,
+            style,
+            rtl,
+        } = self;
+
+        let font = style.text_font(assets, None);
+
+        parent
+            .spawn(Node {
+                flex_direction: row_direction(rtl),
+                margin: style.margin,
+                ..default()
+            })
+            .with_children(|parent| {
+                for (label, screen) in tabs {
+                    let colors = if *screen == active {
+                        style.selected
+                    } else {
+                        style.normal
+                    };
+                    let mut tab = parent.spawn((
+                        Node {
+                            padding: style.padding,
+                            ..default()
+                        },
+                        BackgroundColor(colors.bg),
+                    ));
+                    tab.with_children(|parent| {
+                        let label = label.translated(assets);
+                        for bundle in label.bundle(&font, &TextColor(colors.fg)) {
+                            parent.spawn(bundle);
+                        }
+                    });
+                    if *screen != active {
+                        tab.insert((Button, TabComponent { screen: *screen }));
+                    }
+                }
+            });
+    }
+}
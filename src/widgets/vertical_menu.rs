@@ -1,15 +1,15 @@
 use crate::{
     style::{StyleEntry, Stylesheet},
     types::{
-        MenuAssets, MenuIcon, MenuItem, MenuSelection, NavigationEvent, Selections,
-        VerticalMenuComponent, WidgetId,
+        value_key, Binding, Menu, MenuAssets, MenuIcon, MenuItem, MenuRowComponent, MenuSelection,
+        MenuValue, NavigationEvent, Selections, ValueKey, VerticalMenuComponent, WidgetId,
     },
     ScreenTrait,
 };
 use bevy::prelude::*;
 
-use super::Widget;
-use super::{ButtonWidget, LabelWidget};
+use super::{row_direction, Widget};
+use super::{ButtonWidget, CustomWidget, DropdownWidget, LabelWidget, MeterWidget, SliderWidget};
 
 pub struct VerticalMenu<'a, S>
 where
@@ -19,36 +19,78 @@ where
     pub id: WidgetId,
     // The items in the menu
     pub items: &'a [MenuItem<S>],
+    // The key of the value of each selectable item
+    pub value_ids: &'a [String],
     // Our Stylesheet
     pub stylesheet: &'a Stylesheet,
+    // The factor the sizes of the stylesheet were multiplied by
+    pub scale: f32,
     // Assets
     pub assets: &'a MenuAssets,
     // Overriding Bevy Style
     pub style: Option<&'a Node>,
     // Overriding Bevy Background Color
     pub background: Option<&'a BackgroundColor>,
+    // Lay out the items in a row
+    pub horizontal: bool,
+    // Lay out the items in a grid with this many columns instead
+    pub columns: Option<usize>,
+    // The item that is focussed until the selections say otherwise
+    pub initial_focus: Option<usize>,
+    // The first visible item and how many are visible
+    pub scroll: Option<(usize, usize)>,
+    // The index of the text input that is being edited
+    pub editing: Option<usize>,
+    // The number that is typed into the slider or stepper at `editing`
+    pub typed: Option<&'a str>,
+    // The index of the binding capture that waits for input
+    pub capturing: Option<usize>,
+    // The index of the open dropdown and its highlighted option
+    pub dropdown: Option<(usize, usize)>,
 }
 
 impl<'a, S> VerticalMenu<'a, S>
 where
     S: ScreenTrait + 'static,
 {
-    pub fn build(self, selections: &Selections, builder: &mut ChildSpawnerCommands) {
+    /// Only fills `budget` rows of selectable items. The others are left empty and
+    /// added to `deferred`, to be filled with `rebuild_row` later
+    pub fn build(
+        self,
+        selections: &Selections,
+        builder: &mut ChildSpawnerCommands,
+        budget: &mut usize,
+        deferred: &mut Vec<(Entity, WidgetId, usize)>,
+    ) {
         let VerticalMenu {
             id,
             items,
             stylesheet,
-            assets,
             ..
-        } = self;
+        } = &self;
         if items.is_empty() {
             return;
         }
 
         let style = self.style.cloned().unwrap_or_else(|| Node {
+            display: if self.columns.is_some() {
+                Display::Grid
+            } else {
+                Display::Flex
+            },
+            grid_template_columns: self
+                .columns
+                .map(|columns| RepeatedGridTrack::auto(columns as u16))
+                .unwrap_or_default(),
             align_items: AlignItems::FlexStart,
-            flex_direction: FlexDirection::Column,
+            flex_direction: if self.horizontal {
+                row_direction(stylesheet.rtl)
+            } else {
+                FlexDirection::Column
+            },
             padding: UiRect::all(Val::Px(stylesheet.vertical_spacing)),
+            row_gap: Val::Px(stylesheet.rows.gap),
+            column_gap: Val::Px(stylesheet.rows.gap),
             ..default()
         });
 
@@ -60,100 +102,743 @@ where
         builder
             .spawn((style, background_color))
             .with_children(|parent| {
-                let (selected_idx, selectables) = Self::current_selection(&id, items, selections);
+                let (selected_idx, _) =
+                    Self::current_selection(id, items, self.initial_focus, selections);
 
-                let selected = selectables[selected_idx].1.as_selection();
+                let visible = |position: usize| {
+                    self.scroll
+                        .is_none_or(|(first, rows)| (first..first + rows).contains(&position))
+                };
+                if self.scroll.is_some_and(|(first, _)| first > 0) {
+                    self.add_scroll_indicator(parent);
+                }
 
+                // Rows span vertical menus, so that their items can be aligned
+                let row = if self.in_line() {
+                    Node::default()
+                } else {
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_self: AlignSelf::Stretch,
+                        ..default()
+                    }
+                };
                 let mut index = 0;
-                for item in items {
-                    let is_label = matches!(item, MenuItem::Label(_, _));
-                    let item_selection = item.as_selection();
-                    let focussed = (selected == item_selection) && !is_label;
-
-                    match item {
-                        MenuItem::Screen(t, i, _) => Self::add_item(
-                            assets,
-                            parent,
-                            i,
-                            &stylesheet.button,
-                            ButtonWidget::new(
-                                t,
-                                &stylesheet.button,
-                                (id.clone(), index),
-                                &item_selection,
-                                focussed,
-                            ),
-                        ),
-                        MenuItem::Action(t, i, _) => Self::add_item(
-                            assets,
-                            parent,
-                            i,
-                            &stylesheet.button,
-                            ButtonWidget::new(
-                                t,
-                                &stylesheet.button,
-                                (id.clone(), index),
-                                &item_selection,
-                                focussed,
-                            ),
-                        ),
-                        MenuItem::Label(t, i) => Self::add_item(
-                            assets,
-                            parent,
-                            i,
-                            &stylesheet.label,
-                            LabelWidget::new(t, &stylesheet.label),
-                        ),
-                        MenuItem::Headline(t, i) => Self::add_item(
-                            assets,
-                            parent,
-                            i,
-                            &stylesheet.headline,
-                            LabelWidget::new(t, &stylesheet.headline),
-                        ),
-                        MenuItem::Image(i, s) => {
-                            let style = s.clone().unwrap_or_else(|| Node {
-                                align_self: AlignSelf::Center,
-                                ..Default::default()
+                // The position among the shown items, without collapsed children
+                let mut position = 0;
+                for item in items.iter() {
+                    let focussed = item.is_selectable() && index == selected_idx;
+
+                    if item.is_hidden() {
+                        // Collapsed children count towards the selectable indexes, but
+                        // don't take up rows
+                    } else if !visible(position) {
+                        // Hidden items still count towards the selectable indexes
+                    } else if item.is_selectable() {
+                        // Selectable items get their own row so that they can be
+                        // rebuilt in place when the focus moves
+                        let mut row = parent.spawn((
+                            self.cell(&row, position),
+                            MenuRowComponent((id.clone(), index)),
+                        ));
+                        if *budget > 0 {
+                            *budget -= 1;
+                            row.with_children(|parent| {
+                                self.build_item(parent, item, index, focussed, selections)
                             });
-                            parent.spawn((
-                                style,
-                                ImageNode {
-                                    image: i.clone(),
-                                    ..default()
-                                },
-                            ));
+                        } else {
+                            deferred.push((row.id(), id.clone(), index));
                         }
-                    };
+                    } else if self.columns.is_some() {
+                        parent
+                            .spawn(self.cell(&row, position))
+                            .with_children(|parent| {
+                                self.build_item(parent, item, index, focussed, selections)
+                            });
+                    } else {
+                        self.build_item(parent, item, index, focussed, selections);
+                    }
 
                     // Only increase for menu elements, so the indexes pair up
                     // with the `selectables` indexes
-                    if item_selection != MenuSelection::None {
+                    if item.is_selectable() {
                         index += 1;
                     }
+                    if !item.is_hidden() {
+                        position += 1;
+                    }
+                }
+
+                if position > 0 && !visible(position - 1) {
+                    self.add_scroll_indicator(parent);
                 }
             })
-            .insert(VerticalMenuComponent(id));
+            .insert(VerticalMenuComponent(id.clone()));
+    }
+
+    /// The key of the value of the selectable item at `index`, see `Menu::value_key`
+    fn value_key(&self, index: usize) -> ValueKey {
+        value_key(&self.id, self.value_ids, index)
+    }
+
+    /// Whether the items are laid out next to each other instead of in a column
+    fn in_line(&self) -> bool {
+        self.horizontal || self.columns.is_some()
+    }
+
+    /// The row of the item at `position` among the shown items. Grids are filled
+    /// from the right in right-to-left layouts
+    fn cell(&self, row: &Node, position: usize) -> Node {
+        let mut row = row.clone();
+        if let (Some(columns), true) = (self.columns, self.stylesheet.rtl) {
+            let first = self.scroll.map(|(first, _)| first).unwrap_or_default();
+            // Below the scroll indicator, if there is one
+            let top = if first > 0 { 2 } else { 1 };
+            let position = position - first;
+            row.grid_row = GridPlacement::start((position / columns + top) as i16);
+            row.grid_column = GridPlacement::start((columns - position % columns) as i16);
+        }
+        row
+    }
+
+    /// Replace the contents of the row of the selectable item at `index`, e.g.
+    /// because the focus moved to or away from it
+    pub fn rebuild_row(&self, row: &mut EntityCommands, index: usize, selections: &Selections) {
+        let (selected_idx, selectables) =
+            Self::current_selection(&self.id, self.items, self.initial_focus, selections);
+        let Some((_, item)) = selectables.get(index) else {
+            return;
+        };
+        let focussed = index == selected_idx;
+        row.despawn_related::<Children>()
+            .with_children(|parent| self.build_item(parent, item, index, focussed, selections));
+    }
+
+    /// The first visible entry of a menu with `max_rows`, chosen so that the focussed
+    /// item is visible. Starts from the previous `first` to avoid jumping around
+    pub fn scroll_into_view(menu: &'a Menu<S>, selections: &Selections, first: usize) -> usize {
+        // Grids scroll by whole rows
+        let columns = menu.columns.unwrap_or(1);
+        // The position of each selectable item among the shown items
+        let mut len = 0;
+        let mut positions = Vec::new();
+        for entry in &menu.entries {
+            if entry.is_selectable() {
+                positions.push(len / columns);
+            }
+            if !entry.is_hidden() {
+                len += 1;
+            }
+        }
+        let len = len.div_ceil(columns);
+        let Some(rows) = menu.max_rows.filter(|rows| *rows < len) else {
+            return 0;
+        };
+        let last_first = len - rows;
+        let Some(focussed) = Self::focussed_index(menu, selections) else {
+            return (first / columns).min(last_first) * columns;
+        };
+        let position = positions[focussed];
+        let mut first = first / columns;
+        if position < first {
+            first = position;
+        } else if position >= first + rows {
+            first = position + 1 - rows;
+        }
+        // Also reveal headlines and labels before the first / after the last item
+        if focussed == 0 && position < rows {
+            first = 0;
+        }
+        if focussed == positions.len() - 1 && position >= last_first {
+            first = last_first;
+        }
+        first.min(last_first) * columns
+    }
+
+    /// The index of the focussed item within the selectable items
+    pub fn focussed_index(menu: &'a Menu<S>, selections: &Selections) -> Option<usize> {
+        let (index, selectables) =
+            Self::current_selection(&menu.id, &menu.entries, menu.initial_focus, selections);
+        (!selectables.is_empty()).then_some(index)
+    }
+
+    fn build_item(
+        &self,
+        parent: &mut ChildSpawnerCommands,
+        item: &MenuItem<S>,
+        index: usize,
+        focussed: bool,
+        selections: &Selections,
+    ) {
+        let item_selection = item.as_selection();
+        let disabled = item.is_disabled();
+
+        match item {
+            MenuItem::Screen {
+                label: t, icon: i, ..
+            }
+            | MenuItem::Action {
+                label: t, icon: i, ..
+            }
+            | MenuItem::Back {
+                label: t, icon: i, ..
+            } => self.add_item(
+                parent,
+                Some(focussed),
+                i,
+                &self.stylesheet.button,
+                ButtonWidget::new(
+                    t,
+                    &self.stylesheet.button,
+                    (self.id.clone(), index),
+                    &item_selection,
+                    focussed,
+                )
+                .with_disabled(disabled),
+            ),
+            MenuItem::Label(t, i) => self.add_item(
+                parent,
+                None,
+                i,
+                &self.stylesheet.label,
+                LabelWidget::new(t, &self.stylesheet.label),
+            ),
+            MenuItem::Headline(t, i) => self.add_item(
+                parent,
+                None,
+                i,
+                &self.stylesheet.headline,
+                LabelWidget::new(t, &self.stylesheet.headline),
+            ),
+            MenuItem::Body { text } => self.add_item(
+                parent,
+                None,
+                &MenuIcon::None,
+                &self.stylesheet.body,
+                LabelWidget::new(text, &self.stylesheet.body),
+            ),
+            MenuItem::Image(i, s) => {
+                let style = s.clone().unwrap_or_else(|| Node {
+                    align_self: AlignSelf::Center,
+                    ..Default::default()
+                });
+                parent.spawn((
+                    style,
+                    ImageNode {
+                        image: i.clone(),
+                        ..default()
+                    },
+                ));
+            }
+            MenuItem::Styled(item, style) => {
+                let stylesheet = self.stylesheet.with_override(&style.scaled(self.scale));
+                VerticalMenu {
+                    id: self.id.clone(),
+                    stylesheet: &stylesheet,
+                    ..*self
+                }
+                .build_item(parent, item, index, focussed, selections)
+            }
+            MenuItem::Described(item, _) => {
+                self.build_item(parent, item, index, focussed, selections)
+            }
+            MenuItem::Nested { item, depth, .. } => {
+                // Indent by one font size per level
+                let indent = Val::Px(self.stylesheet.button.size * *depth as f32);
+                parent
+                    .spawn(Node {
+                        padding: if self.stylesheet.rtl {
+                            UiRect::right(indent)
+                        } else {
+                            UiRect::left(indent)
+                        },
+                        align_self: if self.in_line() {
+                            AlignSelf::Auto
+                        } else {
+                            self.stylesheet.button.align_self()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        self.build_item(parent, item, index, focussed, selections)
+                    });
+            }
+            MenuItem::Collapsible {
+                label,
+                icon,
+                expanded,
+                ..
+            } => {
+                let expanded = Self::toggle_value(&self.value_key(index), *expanded, selections);
+                let label = label.with_suffix(if expanded { "  [-]" } else { "  [+]" });
+                self.add_item(
+                    parent,
+                    Some(focussed),
+                    icon,
+                    &self.stylesheet.button,
+                    ButtonWidget::new(
+                        &label,
+                        &self.stylesheet.button,
+                        (self.id.clone(), index),
+                        &item_selection,
+                        focussed,
+                    )
+                    .with_disabled(disabled),
+                )
+            }
+            MenuItem::Custom { build, selectable } => self.add_item(
+                parent,
+                selectable.then_some(focussed),
+                &MenuIcon::None,
+                &self.stylesheet.button,
+                CustomWidget::new(
+                    build,
+                    &self.stylesheet.button,
+                    (self.id.clone(), index),
+                    &item_selection,
+                    *selectable,
+                    focussed,
+                ),
+            ),
+            MenuItem::Separator { size, line } => Self::add_separator(
+                parent,
+                self.stylesheet,
+                *size * self.scale,
+                *line,
+                self.horizontal,
+            ),
+            MenuItem::Slider {
+                label,
+                icon,
+                min,
+                max,
+                value,
+                format,
+                ..
+            } => {
+                let value = Self::slider_value(&self.value_key(index), *value, selections);
+                let fraction = if max > min {
+                    (value - min) / (max - min)
+                } else {
+                    0.0
+                };
+                // A cursor after the typed number
+                let readout = match self.typed.filter(|_| self.editing == Some(index)) {
+                    Some(typed) => format!("{typed}_"),
+                    None => format(value),
+                };
+                self.add_item(
+                    parent,
+                    Some(focussed),
+                    icon,
+                    &self.stylesheet.button,
+                    SliderWidget::new(
+                        label,
+                        &self.stylesheet.button,
+                        (self.id.clone(), index),
+                        &item_selection,
+                        focussed,
+                        fraction,
+                        readout,
+                    )
+                    .with_disabled(disabled)
+                    .with_rtl(self.stylesheet.rtl),
+                )
+            }
+            MenuItem::Meter {
+                label,
+                icon,
+                value,
+                max,
+                color,
+                format,
+            } => {
+                let fraction = if *max > 0.0 { value / max } else { 0.0 };
+                self.add_item(
+                    parent,
+                    None,
+                    icon,
+                    &self.stylesheet.label,
+                    MeterWidget::new(
+                        label,
+                        &self.stylesheet.label,
+                        fraction,
+                        format(*value),
+                        *color,
+                    )
+                    .with_rtl(self.stylesheet.rtl),
+                )
+            }
+            MenuItem::Stepper {
+                label,
+                icon,
+                options,
+                index: initial,
+                ..
+            } => {
+                let current =
+                    Self::stepper_value(&self.value_key(index), *initial, options, selections);
+                let option = options.get(current).map(String::as_str).unwrap_or("");
+                let label = match self.typed.filter(|_| self.editing == Some(index)) {
+                    Some(typed) => label.with_suffix(&format!("  < {typed}_ >")),
+                    None => label.with_suffix(&format!("  < {option} >")),
+                };
+                self.add_item(
+                    parent,
+                    Some(focussed),
+                    icon,
+                    &self.stylesheet.button,
+                    ButtonWidget::new(
+                        &label,
+                        &self.stylesheet.button,
+                        (self.id.clone(), index),
+                        &item_selection,
+                        focussed,
+                    )
+                    .with_disabled(disabled),
+                )
+            }
+            MenuItem::Dropdown {
+                label,
+                icon,
+                action,
+                options,
+                index: initial,
+                ..
+            } => {
+                let current =
+                    Self::stepper_value(&self.value_key(index), *initial, options, selections);
+                let option = options.get(current).map(String::as_str).unwrap_or("");
+                let label = label.with_suffix(&format!("  [{option}] v"));
+                let mut widget = DropdownWidget::new(
+                    &label,
+                    &self.stylesheet.button,
+                    (self.id.clone(), index),
+                    &item_selection,
+                    focussed,
+                    *action,
+                    options,
+                )
+                .with_disabled(disabled);
+                if let Some((_, highlighted)) = self.dropdown.filter(|(open, _)| *open == index) {
+                    // The list covers the items below, so it needs a background
+                    let background = self
+                        .background
+                        .or(self.stylesheet.background.as_ref())
+                        .map(|background| background.0)
+                        .filter(|color| !color.is_fully_transparent())
+                        .unwrap_or(Color::BLACK);
+                    widget = widget.with_open(highlighted, background);
+                }
+                self.add_item(
+                    parent,
+                    Some(focussed),
+                    icon,
+                    &self.stylesheet.button,
+                    widget,
+                )
+            }
+            MenuItem::TextInput {
+                label,
+                icon,
+                value,
+                placeholder,
+                ..
+            } => {
+                let value = Self::text_value(&self.value_key(index), value, selections);
+                let shown = if self.editing == Some(index) {
+                    // A cursor
+                    format!("{value}_")
+                } else if value.is_empty() {
+                    placeholder.clone()
+                } else {
+                    value
+                };
+                let label = label.with_suffix(&format!("  [{shown}]"));
+                self.add_item(
+                    parent,
+                    Some(focussed),
+                    icon,
+                    &self.stylesheet.button,
+                    ButtonWidget::new(
+                        &label,
+                        &self.stylesheet.button,
+                        (self.id.clone(), index),
+                        &item_selection,
+                        focussed,
+                    )
+                    .with_disabled(disabled),
+                )
+            }
+            MenuItem::BindingCapture {
+                label,
+                icon,
+                binding,
+                ..
+            } => {
+                let shown = if self.capturing == Some(index) {
+                    "Press any key...".to_string()
+                } else {
+                    Self::binding_value(&self.value_key(index), *binding, selections)
+                        .map(|binding| binding.name())
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                let label = label.with_suffix(&format!("  [{shown}]"));
+                self.add_item(
+                    parent,
+                    Some(focussed),
+                    icon,
+                    &self.stylesheet.button,
+                    ButtonWidget::new(
+                        &label,
+                        &self.stylesheet.button,
+                        (self.id.clone(), index),
+                        &item_selection,
+                        focussed,
+                    )
+                    .with_disabled(disabled),
+                )
+            }
+            MenuItem::Toggle {
+                label,
+                value,
+                on_icon,
+                off_icon,
+                ..
+            }
+            | MenuItem::Radio {
+                label,
+                active: value,
+                on_icon,
+                off_icon,
+                ..
+            } => {
+                let value = Self::toggle_value(&self.value_key(index), *value, selections);
+                self.add_item(
+                    parent,
+                    Some(focussed),
+                    if value { on_icon } else { off_icon },
+                    &self.stylesheet.button,
+                    ButtonWidget::new(
+                        label,
+                        &self.stylesheet.button,
+                        (self.id.clone(), index),
+                        &item_selection,
+                        focussed,
+                    )
+                    .with_disabled(disabled),
+                )
+            }
+        }
     }
 
     pub fn apply_event(
         event: &NavigationEvent,
-        id: WidgetId,
-        items: &'a [MenuItem<S>],
+        menu: &'a Menu<S>,
         selections: &mut Selections,
     ) -> Option<MenuSelection<S>> {
-        let (mut selectable_index, selectables) = Self::current_selection(&id, items, selections);
+        let items = &menu.entries;
+        let (mut selectable_index, selectables) =
+            Self::current_selection(&menu.id, items, menu.initial_focus, selections);
+        if selectables.is_empty() {
+            return None;
+        }
 
         let mut select_navigation = false;
 
-        let mut selected = selectables[selectable_index].1.as_selection();
+        let focussed_item = selectables[selectable_index].1.unstyled();
+        let mut selected = focussed_item.as_selection();
+        // Disabled items can't be edited or selected
+        let editable = !focussed_item.is_disabled();
+
+        // Grids use all four directions to navigate
+        if let Some(index) = menu.columns.and_then(|columns| {
+            Self::grid_step(menu, &selectables, selectable_index, columns, event)
+        }) {
+            selections.set_row(menu.id.clone(), index);
+            return None;
+        }
+
+        // Horizontal menus navigate with Left / Right instead of Up / Down
+        let (previous, next) = if menu.horizontal {
+            (NavigationEvent::Left, NavigationEvent::Right)
+        } else {
+            (NavigationEvent::Up, NavigationEvent::Down)
+        };
+
+        // The other axis edits the focussed item in place
+        let (decrement, increment) = if menu.horizontal {
+            (NavigationEvent::Down, NavigationEvent::Up)
+        } else {
+            (NavigationEvent::Left, NavigationEvent::Right)
+        };
+
+        if let (
+            true,
+            MenuItem::Slider {
+                action,
+                min,
+                max,
+                step,
+                value,
+                ..
+            },
+        ) = (editable, focussed_item)
+        {
+            if *event == decrement || *event == increment {
+                let key = menu.value_key(selectable_index);
+                let current = Self::slider_value(&key, *value, selections);
+                let delta = if *event == increment { *step } else { -*step };
+                let mut new = current + delta;
+                // Snap to the step so repeated changes don't accumulate float errors
+                if *step > 0.0 {
+                    new = min + ((new - min) / step).round() * step;
+                }
+                let new = new.clamp(*min, *max);
+                if new == current {
+                    return None;
+                }
+                selections.set_edited(key, MenuValue::Float(new), Some(MenuValue::Float(*value)));
+                return Some(MenuSelection::Value(*action, MenuValue::Float(new)));
+            }
+            // Sliders can't be selected
+            if *event == NavigationEvent::Select {
+                return None;
+            }
+        }
+
+        if let (
+            true,
+            MenuItem::Stepper {
+                action,
+                options,
+                index,
+                wrap,
+                ..
+            },
+        ) = (editable, focussed_item)
+        {
+            let forward = *event == increment || *event == NavigationEvent::Select;
+            if !options.is_empty() && (forward || *event == decrement) {
+                let key = menu.value_key(selectable_index);
+                let current = Self::stepper_value(&key, *index, options, selections);
+                let last = options.len() - 1;
+                // Selecting always wraps so that a mouse click can reach every option
+                let wrap = *wrap || *event == NavigationEvent::Select;
+                let new = match (forward, current) {
+                    (true, c) if c < last => c + 1,
+                    (true, _) if wrap => 0,
+                    (false, c) if c > 0 => c - 1,
+                    (false, _) if wrap => last,
+                    (_, c) => c,
+                };
+                if new == current {
+                    return None;
+                }
+                selections.set_row(menu.id.clone(), selectable_index);
+                selections.set_edited(key, MenuValue::Index(new), Some(MenuValue::Index(*index)));
+                return Some(MenuSelection::Value(*action, MenuValue::Index(new)));
+            }
+        }
+
+        // Editing text and capturing bindings is started by the input systems,
+        // dropdowns are opened by the `NavigationMenu`
+        if matches!(
+            focussed_item,
+            MenuItem::TextInput { .. }
+                | MenuItem::BindingCapture { .. }
+                | MenuItem::Dropdown { .. }
+        ) && *event == NavigationEvent::Select
+        {
+            return None;
+        }
+
+        if let (true, MenuItem::Toggle { action, value, .. }) = (editable, focussed_item) {
+            if *event == NavigationEvent::Select {
+                let key = menu.value_key(selectable_index);
+                let new = !Self::toggle_value(&key, *value, selections);
+                selections.set_row(menu.id.clone(), selectable_index);
+                selections.set_edited(key, MenuValue::Bool(new), Some(MenuValue::Bool(*value)));
+                return Some(MenuSelection::Value(*action, MenuValue::Bool(new)));
+            }
+        }
+
+        if let (true, MenuItem::Collapsible { expanded, .. }) = (editable, focussed_item) {
+            if *event == NavigationEvent::Select {
+                let key = menu.value_key(selectable_index);
+                let new = !Self::toggle_value(&key, *expanded, selections);
+                selections.set_row(menu.id.clone(), selectable_index);
+                selections.set_edited(key, MenuValue::Bool(new), Some(MenuValue::Bool(*expanded)));
+                // Nothing to handle, but the menu has to be redrawn
+                return Some(MenuSelection::None);
+            }
+        }
+
+        if let (true, MenuItem::Radio { group, .. }) = (editable, focussed_item) {
+            if *event == NavigationEvent::Select {
+                // Only the selected option of the group stays active
+                for (index, item) in &selectables {
+                    if let MenuItem::Radio {
+                        group: other,
+                        active: initial,
+                        ..
+                    } = item.unstyled()
+                    {
+                        if other == group {
+                            let active = *index == selectable_index;
+                            selections.set_edited(
+                                menu.value_key(*index),
+                                MenuValue::Bool(active),
+                                Some(MenuValue::Bool(*initial)),
+                            );
+                        }
+                    }
+                }
+                selections.set_row(menu.id.clone(), selectable_index);
+                return Some(focussed_item.as_selection());
+            }
+        }
+
+        if let (
+            true,
+            MenuItem::Action {
+                action,
+                repeatable: true,
+                ..
+            },
+        ) = (editable, focussed_item)
+        {
+            if *event == NavigationEvent::Select {
+                // Stay on this row instead of the first item with the same action
+                selections.set_row(menu.id.clone(), selectable_index);
+                return Some(MenuSelection::Action(*action));
+            }
+        }
 
         match event {
-            NavigationEvent::Up if selectable_index > 0 => selectable_index -= 1,
-            NavigationEvent::Down if selectable_index < (selectables.len() - 1) => {
-                selectable_index += 1
+            e if *e == previous => {
+                selectable_index = Self::step(&selectables, selectable_index, false, menu.wrap)
+            }
+            e if *e == next => {
+                selectable_index = Self::step(&selectables, selectable_index, true, menu.wrap)
+            }
+            NavigationEvent::First => {
+                selectable_index = Self::edge(&selectables, selectable_index, false)
+            }
+            NavigationEvent::Last => {
+                selectable_index = Self::edge(&selectables, selectable_index, true)
+            }
+            NavigationEvent::PageUp | NavigationEvent::PageDown => {
+                // Without a viewport the whole menu is one page
+                let rows = menu.visible_entries().unwrap_or(selectables.len()).max(1);
+                selectable_index = Self::page(
+                    &selectables,
+                    selectable_index,
+                    rows,
+                    *event == NavigationEvent::PageDown,
+                )
             }
-            NavigationEvent::Select => select_navigation = true,
+            NavigationEvent::Select => select_navigation = editable,
             _ => (),
         }
 
@@ -161,21 +846,272 @@ where
             selected = selectables[selectable_index].1.as_selection();
         }
         for item in items {
-            let is_label = matches!(item, MenuItem::Label(_, _));
+            let is_label = matches!(
+                item.unstyled(),
+                MenuItem::Label(_, _) | MenuItem::Body { .. }
+            );
             let item_selection = item.as_selection();
             let focussed = (selected == item_selection) && !is_label;
             if !is_label && (select_navigation && focussed) {
                 return Some(item_selection);
             }
         }
-        selections.0.insert(id, selectable_index);
+        selections.set_row(menu.id.clone(), selectable_index);
         None
     }
 
+    /// The nearest enabled item before or after `from`. Stays on `from`
+    /// if there is none
+    fn step(
+        selectables: &[(usize, &MenuItem<S>)],
+        from: usize,
+        forward: bool,
+        wrap: bool,
+    ) -> usize {
+        let len = selectables.len();
+        let candidates: Vec<usize> = if forward {
+            let wrapped = if wrap { 0..from } else { 0..0 };
+            (from + 1..len).chain(wrapped).collect()
+        } else {
+            let wrapped = if wrap { from + 1..len } else { 0..0 };
+            (0..from).rev().chain(wrapped.rev()).collect()
+        };
+        candidates
+            .into_iter()
+            .find(|index| !selectables[*index].1.is_disabled())
+            .unwrap_or(from)
+    }
+
+    /// The item `event` moves to in a grid of `columns`: the nearest enabled item to the
+    /// left or right within the row, or above or below within the column. Stays on
+    /// `from` at the edges, `None` if `event` doesn't move
+    fn grid_step(
+        menu: &Menu<S>,
+        selectables: &[(usize, &MenuItem<S>)],
+        from: usize,
+        columns: usize,
+        event: &NavigationEvent,
+    ) -> Option<usize> {
+        // The cell of each selectable item, collapsed children take none
+        let mut cells = Vec::new();
+        let mut len = 0;
+        for entry in &menu.entries {
+            if entry.is_selectable() {
+                cells.push(len);
+            }
+            if !entry.is_hidden() {
+                len += 1;
+            }
+        }
+        let cell = cells[from];
+        let row = cell - cell % columns;
+        let candidates: Vec<usize> = match event {
+            NavigationEvent::Left => (row..cell).rev().collect(),
+            NavigationEvent::Right => (cell + 1..(row + columns).min(len)).collect(),
+            NavigationEvent::Up => (0..cell / columns)
+                .rev()
+                .map(|r| r * columns + cell % columns)
+                .collect(),
+            NavigationEvent::Down => (cell + columns..len).step_by(columns).collect(),
+            _ => return None,
+        };
+        let enabled_at = |cell: usize| {
+            cells
+                .iter()
+                .enumerate()
+                .find(|(index, c)| **c == cell && !selectables[*index].1.is_disabled())
+                .map(|(index, _)| index)
+        };
+        Some(candidates.into_iter().find_map(enabled_at).unwrap_or(from))
+    }
+
+    /// The first or `last` enabled item. Stays on `from` if there is none
+    fn edge(selectables: &[(usize, &MenuItem<S>)], from: usize, last: bool) -> usize {
+        let enabled = |(_, item): &(usize, &MenuItem<S>)| !item.is_disabled();
+        if last {
+            selectables.iter().rposition(enabled)
+        } else {
+            selectables.iter().position(enabled)
+        }
+        .unwrap_or(from)
+    }
+
+    /// The enabled item `rows` items before or after `from`, clamped to the ends.
+    /// Falls back to the nearest enabled item between the two, or stays on `from`
+    fn page(
+        selectables: &[(usize, &MenuItem<S>)],
+        from: usize,
+        rows: usize,
+        forward: bool,
+    ) -> usize {
+        let candidates: Vec<usize> = if forward {
+            let target = (from + rows).min(selectables.len() - 1);
+            (from + 1..=target).rev().collect()
+        } else {
+            (from.saturating_sub(rows)..from).collect()
+        };
+        candidates
+            .into_iter()
+            .find(|index| !selectables[*index].1.is_disabled())
+            .unwrap_or(from)
+    }
+
+    /// The value of a slider, preferring the one changed by the user
+    fn slider_value(key: &ValueKey, initial: f32, selections: &Selections) -> f32 {
+        match selections.edited(key, Some(&MenuValue::Float(initial))) {
+            Some(MenuValue::Float(value)) => *value,
+            _ => initial,
+        }
+    }
+
+    /// The index of a stepper or dropdown, preferring the one changed by the user
+    pub fn stepper_value(
+        key: &ValueKey,
+        initial: usize,
+        options: &[String],
+        selections: &Selections,
+    ) -> usize {
+        let index = match selections.edited(key, Some(&MenuValue::Index(initial))) {
+            Some(MenuValue::Index(value)) => *value,
+            _ => initial,
+        };
+        index.min(options.len().saturating_sub(1))
+    }
+
+    /// The value of a toggle, preferring the one changed by the user
+    fn toggle_value(key: &ValueKey, initial: bool, selections: &Selections) -> bool {
+        match selections.edited(key, Some(&MenuValue::Bool(initial))) {
+            Some(MenuValue::Bool(value)) => *value,
+            _ => initial,
+        }
+    }
+
+    /// The text of a text input, preferring the one entered by the user
+    pub fn text_value(key: &ValueKey, initial: &str, selections: &Selections) -> String {
+        match selections.edited(key, Some(&MenuValue::Text(initial.to_string()))) {
+            Some(MenuValue::Text(value)) => value.clone(),
+            _ => initial.to_string(),
+        }
+    }
+
+    /// The focussed item and its value, if it is edited in place
+    pub fn focussed_item(
+        menu: &'a Menu<S>,
+        selections: &Selections,
+    ) -> Option<(usize, &'a MenuItem<S>, Option<MenuValue>)> {
+        let (index, selectables) =
+            Self::current_selection(&menu.id, &menu.entries, menu.initial_focus, selections);
+        let item = selectables.get(index)?.1;
+        let value = Self::item_value(&menu.value_key(index), item, selections);
+        Some((index, item, value))
+    }
+
+    /// The selectable item at `index`
+    pub fn selectable(menu: &'a Menu<S>, index: usize) -> Option<&'a MenuItem<S>> {
+        menu.entries.iter().filter(|e| e.is_selectable()).nth(index)
+    }
+
+    /// The value of the item at `key`, if it is edited in place
+    pub fn item_value(
+        key: &ValueKey,
+        item: &MenuItem<S>,
+        selections: &Selections,
+    ) -> Option<MenuValue> {
+        match item.unstyled() {
+            MenuItem::Slider { value, .. } => Some(MenuValue::Float(Self::slider_value(
+                key, *value, selections,
+            ))),
+            MenuItem::Toggle { value, .. }
+            | MenuItem::Radio { active: value, .. }
+            | MenuItem::Collapsible {
+                expanded: value, ..
+            } => Some(MenuValue::Bool(Self::toggle_value(key, *value, selections))),
+            MenuItem::Stepper { options, index, .. }
+            | MenuItem::Dropdown { options, index, .. } => Some(MenuValue::Index(
+                Self::stepper_value(key, *index, options, selections),
+            )),
+            MenuItem::TextInput { value, .. } => {
+                Some(MenuValue::Text(Self::text_value(key, value, selections)))
+            }
+            MenuItem::BindingCapture { binding, .. } => {
+                Self::binding_value(key, *binding, selections).map(MenuValue::Binding)
+            }
+            _ => None,
+        }
+    }
+
+    /// The value the state passes to `item`, if it is edited in place
+    pub fn initial_value(item: &MenuItem<S>) -> Option<MenuValue> {
+        match item.unstyled() {
+            MenuItem::Slider { value, .. } => Some(MenuValue::Float(*value)),
+            MenuItem::Toggle { value, .. }
+            | MenuItem::Radio { active: value, .. }
+            | MenuItem::Collapsible {
+                expanded: value, ..
+            } => Some(MenuValue::Bool(*value)),
+            MenuItem::Stepper { index, .. } | MenuItem::Dropdown { index, .. } => {
+                Some(MenuValue::Index(*index))
+            }
+            MenuItem::TextInput { value, .. } => Some(MenuValue::Text(value.clone())),
+            MenuItem::BindingCapture { binding, .. } => binding.map(MenuValue::Binding),
+            _ => None,
+        }
+    }
+
+    /// `value` made to fit `item`: sliders are clamped to their range, indexes to the
+    /// options and texts to their maximum length. `None` if `item` has no such value
+    pub fn fitted_value(item: &MenuItem<S>, value: MenuValue) -> Option<MenuValue> {
+        match (item.unstyled(), value) {
+            (MenuItem::Slider { min, max, .. }, MenuValue::Float(value)) => {
+                Some(MenuValue::Float(value.clamp(*min, *max)))
+            }
+            (
+                MenuItem::Toggle { .. } | MenuItem::Radio { .. } | MenuItem::Collapsible { .. },
+                MenuValue::Bool(value),
+            ) => Some(MenuValue::Bool(value)),
+            (
+                MenuItem::Stepper { options, .. } | MenuItem::Dropdown { options, .. },
+                MenuValue::Index(index),
+            ) if !options.is_empty() => Some(MenuValue::Index(index.min(options.len() - 1))),
+            (MenuItem::TextInput { max_len, .. }, MenuValue::Text(text)) => {
+                Some(MenuValue::Text(text.chars().take(*max_len).collect()))
+            }
+            (MenuItem::BindingCapture { .. }, MenuValue::Binding(binding)) => {
+                Some(MenuValue::Binding(binding))
+            }
+            _ => None,
+        }
+    }
+
+    /// The focussed item, if it is enabled
+    pub fn focussed_enabled(
+        menu: &'a Menu<S>,
+        selections: &Selections,
+    ) -> Option<(usize, &'a MenuItem<S>)> {
+        let (index, selectables) =
+            Self::current_selection(&menu.id, &menu.entries, menu.initial_focus, selections);
+        let item = selectables.get(index)?.1.unstyled();
+        (!item.is_disabled()).then_some((index, item))
+    }
+
+    /// The binding of a binding capture, preferring the one captured by the user
+    pub fn binding_value(
+        key: &ValueKey,
+        initial: Option<Binding>,
+        selections: &Selections,
+    ) -> Option<Binding> {
+        let initial_value = initial.map(MenuValue::Binding);
+        match selections.edited(key, initial_value.as_ref()) {
+            Some(MenuValue::Binding(binding)) => Some(*binding),
+            _ => initial,
+        }
+    }
+
     #[allow(clippy::type_complexity)]
     fn current_selection(
         id: &WidgetId,
         items: &'a [MenuItem<S>],
+        initial_focus: Option<usize>,
         selections: &Selections,
     ) -> (usize, Vec<(usize, &'a MenuItem<S>)>) {
         let selectables: Vec<_> = items
@@ -184,39 +1120,127 @@ where
             .enumerate()
             .collect();
 
-        let selected_idx = selections.0.get(id).copied().unwrap_or_else(|| {
-            let non_none = selectables
-                .iter()
-                .find(|(_, e)| e.as_selection() != MenuSelection::None)
-                .map(|(i, _)| *i);
-            non_none.unwrap_or_default()
-        });
+        let selected_idx = selections
+            .row(id)
+            .or(initial_focus)
+            .unwrap_or_else(|| {
+                let non_none = selectables
+                    .iter()
+                    .find(|(_, e)| !e.is_disabled())
+                    .map(|(i, _)| *i);
+                non_none.unwrap_or_default()
+            })
+            // The menu may have shrunk since the selection was stored
+            .min(selectables.len().saturating_sub(1));
 
         (selected_idx, selectables)
     }
 
+    /// Hints that there are more entries above or below
+    fn add_scroll_indicator(&self, parent: &mut ChildSpawnerCommands) {
+        let style = &self.stylesheet.label;
+        parent.spawn((
+            Node {
+                align_self: AlignSelf::Center,
+                // A row of its own in grids
+                grid_column: self
+                    .columns
+                    .map(|columns| GridPlacement::span(columns as u16))
+                    .unwrap_or_default(),
+                ..default()
+            },
+            Text::new("..."),
+            style.text_font(self.assets, None),
+            TextColor(style.normal.fg),
+        ));
+    }
+
+    fn add_separator(
+        parent: &mut ChildSpawnerCommands,
+        stylesheet: &Stylesheet,
+        size: f32,
+        line: bool,
+        horizontal: bool,
+    ) {
+        let color = if line {
+            stylesheet.label.normal.fg
+        } else {
+            Color::NONE
+        };
+        // The line runs across the menu, the spacing along it
+        let (width, height, margin) = if horizontal {
+            (
+                Val::Px(1.0),
+                Val::Percent(100.0),
+                UiRect::horizontal(Val::Px((size - 1.0).max(0.0) / 2.0)),
+            )
+        } else {
+            (
+                Val::Percent(100.0),
+                Val::Px(1.0),
+                UiRect::vertical(Val::Px((size - 1.0).max(0.0) / 2.0)),
+            )
+        };
+        parent.spawn((
+            Node {
+                width,
+                height,
+                margin,
+                align_self: AlignSelf::Stretch,
+                ..default()
+            },
+            BackgroundColor(color),
+        ));
+    }
+
+    /// `focussed` is `None` for items that can't be focussed, which leave no room for
+    /// the pointer
     fn add_item(
-        assets: &MenuAssets,
+        &self,
         parent: &mut ChildSpawnerCommands,
+        focussed: Option<bool>,
         icon: &MenuIcon,
         style: &StyleEntry,
         widget: impl Widget,
     ) {
+        let assets = self.assets;
         parent
             .spawn(Node {
                 align_items: AlignItems::Center,
-                flex_direction: FlexDirection::Row,
+                flex_direction: row_direction(self.stylesheet.rtl),
+                // `text_align` places the items of vertical menus
+                align_self: if self.in_line() {
+                    AlignSelf::Auto
+                } else {
+                    style.align_self()
+                },
                 ..default()
             })
             .with_children(|parent| {
-                if let Some(image_handle) = icon.resolve_icon(assets) {
+                let icon_node = Node {
+                    width: style.icon_style.width,
+                    height: style.icon_style.height,
+                    margin: style.icon_style.padding,
+                    ..default()
+                };
+                if let (Some(pointer), Some(focussed)) = (&self.stylesheet.pointer, focussed) {
                     parent.spawn((
-                        Node {
-                            width: style.icon_style.width,
-                            height: style.icon_style.height,
-                            margin: style.icon_style.padding,
+                        icon_node.clone(),
+                        ImageNode {
+                            image: pointer.clone(),
                             ..default()
                         },
+                        BackgroundColor(style.icon_style.tint_color),
+                        if focussed {
+                            Visibility::Inherited
+                        } else {
+                            Visibility::Hidden
+                        },
+                    ));
+                }
+                if let Some(image_handle) = icon.resolve_icon(assets, focussed == Some(true)) {
+                    parent.spawn((
+                        icon_node,
                         ImageNode {
                             image: image_handle,
                             ..default()
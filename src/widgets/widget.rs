@@ -1,7 +1,17 @@
 use bevy::ecs::hierarchy::ChildSpawnerCommands;
+use bevy::ui::FlexDirection;
 
 use crate::types::MenuAssets;
 
 pub trait Widget {
     fn build(self, parent: &mut ChildSpawnerCommands, assets: &MenuAssets);
 }
+
+/// The direction of a row, mirrored for right-to-left languages
+pub(crate) fn row_direction(rtl: bool) -> FlexDirection {
+    if rtl {
+        FlexDirection::RowReverse
+    } else {
+        FlexDirection::Row
+    }
+}